@@ -2,7 +2,7 @@
 //! Manages ad creative assets, validation, and performance tracking on Stellar.
 
 #![no_std]
-use soroban_sdk::{contract, contractimpl, contracttype, symbol_short, Address, Env, String};
+use soroban_sdk::{contract, contractimpl, contracttype, symbol_short, Address, Env, String, Vec};
 
 // ============================================================
 // Data Types
@@ -231,6 +231,20 @@ impl AdRegistryContract {
             .instance()
             .set(&DataKey::ContentNonce, &content_id);
 
+        let _ttl_key = DataKey::CampaignContents(campaign_id);
+        let mut contents: Vec<u64> = env
+            .storage()
+            .persistent()
+            .get(&_ttl_key)
+            .unwrap_or(Vec::new(&env));
+        contents.push_back(content_id);
+        env.storage().persistent().set(&_ttl_key, &contents);
+        env.storage().persistent().extend_ttl(
+            &_ttl_key,
+            PERSISTENT_LIFETIME_THRESHOLD,
+            PERSISTENT_BUMP_AMOUNT,
+        );
+
         env.events().publish(
             (symbol_short!("register"), symbol_short!("content")),
             (content_id, campaign_id),
@@ -458,6 +472,47 @@ impl AdRegistryContract {
         }
     }
 
+    pub fn get_campaign_contents(env: Env, campaign_id: u64) -> Vec<u64> {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        env.storage()
+            .persistent()
+            .get(&DataKey::CampaignContents(campaign_id))
+            .unwrap_or(Vec::new(&env))
+    }
+
+    /// True when the campaign has at least one live creative and every
+    /// creative that is not archived has been approved.
+    pub fn campaign_approved(env: Env, campaign_id: u64) -> bool {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        let contents: Vec<u64> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::CampaignContents(campaign_id))
+            .unwrap_or(Vec::new(&env));
+
+        let mut live = 0u32;
+        for content_id in contents.iter() {
+            let content: AdContent = match env
+                .storage()
+                .persistent()
+                .get(&DataKey::Content(content_id))
+            {
+                Some(c) => c,
+                None => continue,
+            };
+            match content.status {
+                ContentStatus::Archived => {}
+                ContentStatus::Approved => live += 1,
+                _ => return false,
+            }
+        }
+        live > 0
+    }
+
     pub fn get_nonce(env: Env) -> u64 {
         env.storage()
             .instance()
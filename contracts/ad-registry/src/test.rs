@@ -256,3 +256,40 @@ fn test_accept_admin_unauthorized() {
     c.propose_admin(&admin, &new_admin);
     c.accept_admin(&stranger);
 }
+
+#[test]
+fn test_campaign_contents_tracked() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (c, _) = setup(&env);
+    let a = register(&c, &env);
+    let b = register(&c, &env);
+    let contents = c.get_campaign_contents(&1u64);
+    assert_eq!(contents.len(), 2);
+    assert_eq!(contents.get(0).unwrap(), a);
+    assert_eq!(contents.get(1).unwrap(), b);
+    assert_eq!(c.get_campaign_contents(&2u64).len(), 0);
+}
+
+#[test]
+fn test_campaign_approved_requires_all_creatives() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (c, admin) = setup(&env);
+    assert!(!c.campaign_approved(&1u64));
+
+    let a = register(&c, &env);
+    let b = register(&c, &env);
+    c.update_status(&admin, &a, &ContentStatus::Approved);
+    assert!(!c.campaign_approved(&1u64));
+
+    c.update_status(&admin, &b, &ContentStatus::Approved);
+    assert!(c.campaign_approved(&1u64));
+
+    c.update_status(&admin, &b, &ContentStatus::Rejected);
+    assert!(!c.campaign_approved(&1u64));
+
+    // archived creatives no longer block the campaign
+    c.update_status(&admin, &b, &ContentStatus::Archived);
+    assert!(c.campaign_approved(&1u64));
+}
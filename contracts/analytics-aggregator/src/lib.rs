@@ -53,6 +53,8 @@ const INSTANCE_LIFETIME_THRESHOLD: u32 = 17_280;
 const INSTANCE_BUMP_AMOUNT: u32 = 86_400;
 const PERSISTENT_LIFETIME_THRESHOLD: u32 = 120_960;
 const PERSISTENT_BUMP_AMOUNT: u32 = 1_051_200;
+/// Hourly stats stay readable for about a day so completed hours can be scanned.
+const HOURLY_STATS_TTL: u32 = 17_280;
 
 #[contract]
 pub struct AnalyticsAggregatorContract;
@@ -143,6 +145,9 @@ impl AnalyticsAggregatorContract {
         hourly.impressions += 1;
         hourly.spend += spend;
         env.storage().temporary().set(&hourly_key, &hourly);
+        env.storage()
+            .temporary()
+            .extend_ttl(&hourly_key, HOURLY_STATS_TTL, HOURLY_STATS_TTL);
 
         // Update global stats
         let mut global: GlobalStats = env.storage().instance().get(&DataKey::GlobalStats).unwrap();
@@ -178,6 +183,24 @@ impl AnalyticsAggregatorContract {
             PERSISTENT_BUMP_AMOUNT,
         );
 
+        let hour = env.ledger().timestamp() / 3600;
+        let hourly_key = DataKey::HourlyStats(campaign_id, hour);
+        let mut hourly: HourlyStats =
+            env.storage()
+                .temporary()
+                .get(&hourly_key)
+                .unwrap_or(HourlyStats {
+                    impressions: 0,
+                    clicks: 0,
+                    spend: 0,
+                    timestamp: env.ledger().timestamp(),
+                });
+        hourly.clicks += 1;
+        env.storage().temporary().set(&hourly_key, &hourly);
+        env.storage()
+            .temporary()
+            .extend_ttl(&hourly_key, HOURLY_STATS_TTL, HOURLY_STATS_TTL);
+
         let mut global: GlobalStats = env.storage().instance().get(&DataKey::GlobalStats).unwrap();
         global.total_clicks += 1;
         env.storage().instance().set(&DataKey::GlobalStats, &global);
@@ -63,6 +63,21 @@ fn test_record_click() {
     assert_eq!(a.total_clicks, 1);
 }
 
+#[test]
+fn test_hourly_stats_count_clicks() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (c, _) = setup(&env);
+    let caller = Address::generate(&env);
+    c.record_impression(&caller, &1u64, &100i128);
+    c.record_impression(&caller, &1u64, &100i128);
+    c.record_click(&caller, &1u64);
+    let hourly = c.get_hourly_stats(&1u64, &0u64).unwrap();
+    assert_eq!(hourly.impressions, 2);
+    assert_eq!(hourly.clicks, 1);
+    assert_eq!(hourly.spend, 200);
+}
+
 #[test]
 fn test_get_campaign_analytics_nonexistent() {
     let env = Env::default();
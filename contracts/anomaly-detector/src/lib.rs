@@ -1,8 +1,21 @@
 //! PulsarTrack - Anomaly Detector (Soroban)
 //! On-chain anomaly detection for ad campaign traffic on Stellar.
+//!
+//! Auto-actions: a report filed with `auto_action` (every `scan_campaign`
+//! report is) runs the `ActionPolicy` for its severity, flagging the publisher
+//! in fraud-prevention, pausing the campaign in campaign-lifecycle and holding
+//! the publisher's escrows in escrow-vault. `resolve_anomaly` undoes them.
+//!
+//! Adaptive baselines: every scanned campaign hour and every publisher hour
+//! pushed by the oracle updates an EWMA mean and variance of impressions and
+//! clicks. After `warmup_hours` samples, hours are flagged by z-score instead
+//! of the fixed `set_baseline` percentages.
 
 #![no_std]
-use soroban_sdk::{contract, contractimpl, contracttype, symbol_short, Address, Env, String};
+use soroban_sdk::{
+    contract, contractimpl, contracttype, symbol_short, Address, Env, IntoVal, String, Symbol, Val,
+    Vec,
+};
 
 #[contracttype]
 #[derive(Clone)]
@@ -50,6 +63,76 @@ pub struct TrafficBaseline {
     pub last_updated: u64,
 }
 
+/// Mirror of analytics-aggregator's `HourlyStats`.
+#[contracttype]
+#[derive(Clone)]
+pub struct HourlyStats {
+    pub impressions: u64,
+    pub clicks: u64,
+    pub spend: i128,
+    pub timestamp: u64,
+}
+
+#[contracttype]
+#[derive(Clone)]
+pub struct ScanConfig {
+    pub ctr_threshold_pct: u32, // CTR above this % of the baseline CTR is click farming
+    pub min_impressions: u64,   // hours with fewer impressions skip the CTR check
+}
+
+/// Exponentially weighted mean and variance, scaled by EWMA_SCALE and
+/// EWMA_SCALE^2 respectively
+#[contracttype]
+#[derive(Clone)]
+pub struct EwmaStat {
+    pub mean: i128,
+    pub variance: i128,
+}
+
+#[contracttype]
+#[derive(Clone)]
+pub struct AdaptiveBaseline {
+    pub impressions: EwmaStat,
+    pub clicks: EwmaStat,
+    pub samples: u32,
+    pub last_hour: u64,
+    pub last_updated: u64,
+}
+
+#[contracttype]
+#[derive(Clone)]
+pub enum BaselineSubject {
+    Campaign(u64),
+    Publisher(Address),
+}
+
+#[contracttype]
+#[derive(Clone)]
+pub struct EwmaConfig {
+    pub alpha_bps: u32,         // weight of each new hour, 1000 = 0.1
+    pub z_threshold_milli: u32, // 3000 = flag beyond 3 standard deviations
+    pub warmup_hours: u32,      // hours observed before z-scores are trusted
+}
+
+/// External actions taken when a report of a given severity is filed.
+/// All false means notify only (the "detected" event).
+#[contracttype]
+#[derive(Clone)]
+pub struct ActionPolicy {
+    pub flag_publisher: bool, // flag_suspicious in fraud-prevention
+    pub pause_campaign: bool, // pause_for_fraud in campaign-lifecycle
+    pub hold_escrows: bool,   // hold_for_fraud on the publisher's escrows in escrow-vault
+}
+
+/// Actions that succeeded for a report; resolve_anomaly reverses them
+#[contracttype]
+#[derive(Clone)]
+pub struct ReportActions {
+    pub flagged_publisher: bool,
+    pub paused_campaign: bool,
+    pub held_escrows: Vec<u64>,
+}
+
 #[contracttype]
 #[derive(Clone)]
 pub enum DataKey {
@@ -61,12 +144,25 @@ pub enum DataKey {
     Report(u64),
     Baseline(u64), // campaign_id
     FlaggedPublisher(Address),
+    AnalyticsAggregator,
+    ScanConfig,
+    Scanned(u64, u64), // campaign_id, hour
+    FraudPrevention,
+    CampaignLifecycle,
+    EscrowVault,
+    ActionPolicy(AnomalySeverity),
+    ReportActions(u64), // report_id
+    EwmaConfig,
+    AdaptiveBaseline(BaselineSubject),
+    OpenCriticalReports(Address), // publisher -> unresolved critical reports keeping it flagged
 }
 
 const INSTANCE_LIFETIME_THRESHOLD: u32 = 17_280;
 const INSTANCE_BUMP_AMOUNT: u32 = 86_400;
 const PERSISTENT_LIFETIME_THRESHOLD: u32 = 34_560;
 const PERSISTENT_BUMP_AMOUNT: u32 = 259_200;
+/// Fixed-point scale of EWMA means
+const EWMA_SCALE: i128 = 1_000;
 
 #[contract]
 pub struct AnomalyDetectorContract;
@@ -91,6 +187,301 @@ impl AnomalyDetectorContract {
             .set(&DataKey::SpikeThreshold, &300u32); // 300% = 3x normal
     }
 
+    pub fn set_analytics_aggregator(env: Env, admin: Address, aggregator: Address) {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        admin.require_auth();
+        let stored_admin: Address = env.storage().instance().get(&DataKey::Admin).unwrap();
+        if admin != stored_admin {
+            panic!("unauthorized");
+        }
+        env.storage()
+            .instance()
+            .set(&DataKey::AnalyticsAggregator, &aggregator);
+    }
+
+    pub fn set_action_contracts(
+        env: Env,
+        admin: Address,
+        fraud_prevention: Address,
+        lifecycle: Address,
+        vault: Address,
+    ) {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        admin.require_auth();
+        let stored_admin: Address = env.storage().instance().get(&DataKey::Admin).unwrap();
+        if admin != stored_admin {
+            panic!("unauthorized");
+        }
+        env.storage()
+            .instance()
+            .set(&DataKey::FraudPrevention, &fraud_prevention);
+        env.storage()
+            .instance()
+            .set(&DataKey::CampaignLifecycle, &lifecycle);
+        env.storage().instance().set(&DataKey::EscrowVault, &vault);
+    }
+
+    pub fn set_action_policy(
+        env: Env,
+        admin: Address,
+        severity: AnomalySeverity,
+        policy: ActionPolicy,
+    ) {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        admin.require_auth();
+        let stored_admin: Address = env.storage().instance().get(&DataKey::Admin).unwrap();
+        if admin != stored_admin {
+            panic!("unauthorized");
+        }
+        env.storage()
+            .instance()
+            .set(&DataKey::ActionPolicy(severity), &policy);
+    }
+
+    /// Defaults: Low and Medium notify only, High flags the publisher and
+    /// Critical also pauses the campaign and holds the publisher's escrows.
+    pub fn get_action_policy(env: Env, severity: AnomalySeverity) -> ActionPolicy {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        let key = DataKey::ActionPolicy(severity.clone());
+        env.storage()
+            .instance()
+            .get(&key)
+            .unwrap_or(match severity {
+                AnomalySeverity::Low | AnomalySeverity::Medium => ActionPolicy {
+                    flag_publisher: false,
+                    pause_campaign: false,
+                    hold_escrows: false,
+                },
+                AnomalySeverity::High => ActionPolicy {
+                    flag_publisher: true,
+                    pause_campaign: false,
+                    hold_escrows: false,
+                },
+                AnomalySeverity::Critical => ActionPolicy {
+                    flag_publisher: true,
+                    pause_campaign: true,
+                    hold_escrows: true,
+                },
+            })
+    }
+
+    pub fn get_report_actions(env: Env, report_id: u64) -> Option<ReportActions> {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        env.storage()
+            .persistent()
+            .get(&DataKey::ReportActions(report_id))
+    }
+
+    pub fn set_scan_config(env: Env, admin: Address, config: ScanConfig) {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        admin.require_auth();
+        let stored_admin: Address = env.storage().instance().get(&DataKey::Admin).unwrap();
+        if admin != stored_admin {
+            panic!("unauthorized");
+        }
+        if config.ctr_threshold_pct <= 100 {
+            panic!("invalid ctr threshold");
+        }
+        env.storage().instance().set(&DataKey::ScanConfig, &config);
+    }
+
+    pub fn get_scan_config(env: Env) -> ScanConfig {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        env.storage()
+            .instance()
+            .get(&DataKey::ScanConfig)
+            .unwrap_or(ScanConfig {
+                ctr_threshold_pct: 300,
+                min_impressions: 100,
+            })
+    }
+
+    /// Scan a completed hour of a campaign's analytics-aggregator stats and
+    /// file a report if it is anomalous. Once the campaign's adaptive baseline
+    /// has warmed up the hour is judged by z-score, before that by the fixed
+    /// baseline from set_baseline if there is one. Callable by anyone; each
+    /// (campaign, hour) can be scanned once. Returns the report id, if any.
+    pub fn scan_campaign(env: Env, campaign_id: u64, hour: u64) -> Option<u64> {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        if hour >= env.ledger().timestamp() / 3600 {
+            panic!("hour not complete");
+        }
+        let scanned_key = DataKey::Scanned(campaign_id, hour);
+        if env.storage().persistent().has(&scanned_key) {
+            panic!("hour already scanned");
+        }
+        let aggregator: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::AnalyticsAggregator)
+            .expect("analytics aggregator not set");
+
+        let stats: Option<HourlyStats> = env.invoke_contract(
+            &aggregator,
+            &Symbol::new(&env, "get_hourly_stats"),
+            Vec::from_array(&env, [campaign_id.into_val(&env), hour.into_val(&env)]),
+        );
+
+        env.storage().persistent().set(&scanned_key, &true);
+        env.storage().persistent().extend_ttl(
+            &scanned_key,
+            PERSISTENT_LIFETIME_THRESHOLD,
+            PERSISTENT_BUMP_AMOUNT,
+        );
+
+        let stats = stats?;
+        let z_scores = Self::_observe(
+            &env,
+            &BaselineSubject::Campaign(campaign_id),
+            hour,
+            stats.impressions,
+            stats.clicks,
+        );
+        let (anomaly_type, worst_pct, description) = match z_scores {
+            Some((impressions_z, clicks_z)) => Self::_classify_z(&env, impressions_z, clicks_z)?,
+            None => {
+                let baseline: TrafficBaseline = env
+                    .storage()
+                    .persistent()
+                    .get(&DataKey::Baseline(campaign_id))?;
+                Self::_classify_pct(&env, &stats, &baseline)?
+            }
+        };
+
+        let ctr_bps = Self::_ctr_bps(stats.clicks, stats.impressions);
+        let snapshot =
+            Self::_metrics_snapshot(&env, hour, stats.impressions, stats.clicks, ctr_bps);
+        let report_id = Self::_file_report(
+            &env,
+            campaign_id,
+            None,
+            anomaly_type,
+            Self::_severity_for(worst_pct),
+            String::from_str(&env, description),
+            snapshot,
+            true,
+        );
+        Some(report_id)
+    }
+
+    /// Feed a publisher's traffic for a completed hour into its adaptive
+    /// baseline and file a publisher-wide report (campaign_id 0) if the hour
+    /// deviates by more than the z-score threshold once warmed up.
+    pub fn observe_publisher_hour(
+        env: Env,
+        oracle: Address,
+        publisher: Address,
+        hour: u64,
+        impressions: u64,
+        clicks: u64,
+    ) -> Option<u64> {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        oracle.require_auth();
+        let stored_oracle: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::OracleAddress)
+            .unwrap();
+        if oracle != stored_oracle {
+            panic!("unauthorized");
+        }
+        if hour >= env.ledger().timestamp() / 3600 {
+            panic!("hour not complete");
+        }
+        let subject = BaselineSubject::Publisher(publisher.clone());
+        if let Some(baseline) = Self::get_adaptive_baseline(env.clone(), subject.clone()) {
+            if hour <= baseline.last_hour {
+                panic!("stale hour");
+            }
+        }
+
+        let (impressions_z, clicks_z) = Self::_observe(&env, &subject, hour, impressions, clicks)?;
+        let (anomaly_type, worst_pct, description) =
+            Self::_classify_z(&env, impressions_z, clicks_z)?;
+
+        let ctr_bps = Self::_ctr_bps(clicks, impressions);
+        let snapshot = Self::_metrics_snapshot(&env, hour, impressions, clicks, ctr_bps);
+        let report_id = Self::_file_report(
+            &env,
+            0,
+            Some(publisher),
+            anomaly_type,
+            Self::_severity_for(worst_pct),
+            String::from_str(&env, description),
+            snapshot,
+            true,
+        );
+        Some(report_id)
+    }
+
+    pub fn set_ewma_config(env: Env, admin: Address, config: EwmaConfig) {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        admin.require_auth();
+        let stored_admin: Address = env.storage().instance().get(&DataKey::Admin).unwrap();
+        if admin != stored_admin {
+            panic!("unauthorized");
+        }
+        if config.alpha_bps == 0 || config.alpha_bps > 10_000 {
+            panic!("invalid alpha");
+        }
+        if config.z_threshold_milli == 0 {
+            panic!("invalid z threshold");
+        }
+        env.storage().instance().set(&DataKey::EwmaConfig, &config);
+    }
+
+    pub fn get_ewma_config(env: Env) -> EwmaConfig {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        env.storage()
+            .instance()
+            .get(&DataKey::EwmaConfig)
+            .unwrap_or(EwmaConfig {
+                alpha_bps: 1_000,
+                z_threshold_milli: 3_000,
+                warmup_hours: 24,
+            })
+    }
+
+    pub fn get_adaptive_baseline(env: Env, subject: BaselineSubject) -> Option<AdaptiveBaseline> {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        env.storage()
+            .persistent()
+            .get(&DataKey::AdaptiveBaseline(subject))
+    }
+
+    pub fn is_hour_scanned(env: Env, campaign_id: u64, hour: u64) -> bool {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        env.storage()
+            .persistent()
+            .has(&DataKey::Scanned(campaign_id, hour))
+    }
+
     pub fn set_baseline(
         env: Env,
         oracle: Address,
@@ -160,28 +551,128 @@ impl AnomalyDetectorContract {
             .storage()
             .persistent()
             .get(&DataKey::Baseline(campaign_id));
-        
+
         if let Some(b) = baseline {
             // Calculate threshold multiplier (e.g., 300% = 3.0x)
             let threshold_multiplier = b.spike_threshold_pct as u64;
-            
+
             // Check if current metrics exceed baseline thresholds
-            let impressions_threshold = b.avg_impressions_per_hour
+            let impressions_threshold = b
+                .avg_impressions_per_hour
                 .saturating_mul(threshold_multiplier)
                 .saturating_div(100);
-            let clicks_threshold = b.avg_clicks_per_hour
+            let clicks_threshold = b
+                .avg_clicks_per_hour
                 .saturating_mul(threshold_multiplier)
                 .saturating_div(100);
-            
+
             // Validate that at least one metric exceeds the threshold
             let impressions_exceeded = current_impressions_per_hour > impressions_threshold;
             let clicks_exceeded = current_clicks_per_hour > clicks_threshold;
-            
+
             if !impressions_exceeded && !clicks_exceeded {
                 panic!("metrics do not exceed baseline thresholds");
             }
         }
 
+        Self::_file_report(
+            &env,
+            campaign_id,
+            publisher,
+            anomaly_type,
+            severity,
+            description,
+            metrics_snapshot,
+            auto_action,
+        )
+    }
+
+    pub fn resolve_anomaly(env: Env, admin: Address, report_id: u64) {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        admin.require_auth();
+        let stored_admin: Address = env.storage().instance().get(&DataKey::Admin).unwrap();
+        if admin != stored_admin {
+            panic!("unauthorized");
+        }
+
+        let mut report: AnomalyReport = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Report(report_id))
+            .expect("report not found");
+        if report.resolved {
+            panic!("already resolved");
+        }
+
+        Self::_undo_actions(&env, &report);
+        if let (AnomalySeverity::Critical, Some(publisher)) = (&report.severity, &report.publisher)
+        {
+            Self::_release_publisher_flag(&env, publisher);
+        }
+        report.resolved = true;
+        report.resolved_at = Some(env.ledger().timestamp());
+        let _ttl_key = DataKey::Report(report_id);
+        env.storage().persistent().set(&_ttl_key, &report);
+        env.storage().persistent().extend_ttl(
+            &_ttl_key,
+            PERSISTENT_LIFETIME_THRESHOLD,
+            PERSISTENT_BUMP_AMOUNT,
+        );
+    }
+
+    pub fn get_report(env: Env, report_id: u64) -> Option<AnomalyReport> {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        env.storage().persistent().get(&DataKey::Report(report_id))
+    }
+
+    pub fn get_baseline(env: Env, campaign_id: u64) -> Option<TrafficBaseline> {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        env.storage()
+            .persistent()
+            .get(&DataKey::Baseline(campaign_id))
+    }
+
+    pub fn is_publisher_flagged(env: Env, publisher: Address) -> bool {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        env.storage()
+            .persistent()
+            .get(&DataKey::FlaggedPublisher(publisher))
+            .unwrap_or(false)
+    }
+
+    pub fn get_report_count(env: Env) -> u64 {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        env.storage()
+            .instance()
+            .get(&DataKey::ReportCounter)
+            .unwrap_or(0)
+    }
+
+    // ============================================================
+    // Internal Helpers
+    // ============================================================
+
+    #[allow(clippy::too_many_arguments)]
+    fn _file_report(
+        env: &Env,
+        campaign_id: u64,
+        publisher: Option<Address>,
+        anomaly_type: AnomalyType,
+        severity: AnomalySeverity,
+        description: String,
+        metrics_snapshot: String,
+        auto_action: bool,
+    ) -> u64 {
         let counter: u64 = env
             .storage()
             .instance()
@@ -200,11 +691,22 @@ impl AnomalyDetectorContract {
                         PERSISTENT_LIFETIME_THRESHOLD,
                         PERSISTENT_BUMP_AMOUNT,
                     );
+                    let _ttl_key = DataKey::OpenCriticalReports(pub_addr.clone());
+                    let open: u32 = env.storage().persistent().get(&_ttl_key).unwrap_or(0);
+                    env.storage().persistent().set(&_ttl_key, &(open + 1));
+                    env.storage().persistent().extend_ttl(
+                        &_ttl_key,
+                        PERSISTENT_LIFETIME_THRESHOLD,
+                        PERSISTENT_BUMP_AMOUNT,
+                    );
                 }
                 _ => {}
             }
         }
 
+        let auto_action_taken =
+            auto_action && Self::_take_actions(env, report_id, campaign_id, &publisher, &severity);
+
         let report = AnomalyReport {
             report_id,
             campaign_id,
@@ -213,7 +715,7 @@ impl AnomalyDetectorContract {
             severity,
             description,
             metrics_snapshot,
-            auto_action_taken: auto_action,
+            auto_action_taken,
             reported_at: env.ledger().timestamp(),
             resolved: false,
             resolved_at: None,
@@ -238,67 +740,453 @@ impl AnomalyDetectorContract {
         report_id
     }
 
-    pub fn resolve_anomaly(env: Env, admin: Address, report_id: u64) {
+    /// Apply the severity's action policy. Each call is isolated so a missing
+    /// or refusing contract does not block the report; only successful
+    /// actions are recorded. Returns whether anything was done.
+    fn _take_actions(
+        env: &Env,
+        report_id: u64,
+        campaign_id: u64,
+        publisher: &Option<Address>,
+        severity: &AnomalySeverity,
+    ) -> bool {
+        let policy = Self::get_action_policy(env.clone(), severity.clone());
+        let this = env.current_contract_address();
+        let mut actions = ReportActions {
+            flagged_publisher: false,
+            paused_campaign: false,
+            held_escrows: Vec::new(env),
+        };
+
+        if let Some(publisher) = publisher {
+            if policy.flag_publisher {
+                if let Some(fraud) = env
+                    .storage()
+                    .instance()
+                    .get::<DataKey, Address>(&DataKey::FraudPrevention)
+                {
+                    actions.flagged_publisher = Self::_try_call(
+                        env,
+                        &fraud,
+                        "flag_suspicious",
+                        Vec::from_array(env, [this.into_val(env), publisher.into_val(env)]),
+                    );
+                    if actions.flagged_publisher {
+                        env.events().publish(
+                            (symbol_short!("anomaly"), symbol_short!("flagged")),
+                            (report_id, publisher.clone()),
+                        );
+                    }
+                }
+            }
+
+            if policy.hold_escrows {
+                if let Some(vault) = env
+                    .storage()
+                    .instance()
+                    .get::<DataKey, Address>(&DataKey::EscrowVault)
+                {
+                    let escrow_ids = env.try_invoke_contract::<Vec<u64>, soroban_sdk::Error>(
+                        &vault,
+                        &Symbol::new(env, "get_beneficiary_escrows"),
+                        Vec::from_array(env, [publisher.into_val(env)]),
+                    );
+                    if let Ok(Ok(escrow_ids)) = escrow_ids {
+                        for escrow_id in escrow_ids.iter() {
+                            let held = Self::_try_call(
+                                env,
+                                &vault,
+                                "hold_for_fraud",
+                                Vec::from_array(env, [this.into_val(env), escrow_id.into_val(env)]),
+                            );
+                            if held {
+                                actions.held_escrows.push_back(escrow_id);
+                                env.events().publish(
+                                    (symbol_short!("anomaly"), symbol_short!("held")),
+                                    (report_id, escrow_id),
+                                );
+                            }
+                        }
+                    }
+                }
+            }
+        }
+
+        if policy.pause_campaign {
+            if let Some(lifecycle) = env
+                .storage()
+                .instance()
+                .get::<DataKey, Address>(&DataKey::CampaignLifecycle)
+            {
+                actions.paused_campaign = Self::_try_call(
+                    env,
+                    &lifecycle,
+                    "pause_for_fraud",
+                    Vec::from_array(env, [this.into_val(env), campaign_id.into_val(env)]),
+                );
+                if actions.paused_campaign {
+                    env.events().publish(
+                        (symbol_short!("anomaly"), symbol_short!("paused")),
+                        (report_id, campaign_id),
+                    );
+                }
+            }
+        }
+
+        let taken = actions.flagged_publisher
+            || actions.paused_campaign
+            || !actions.held_escrows.is_empty();
+        if taken {
+            let _ttl_key = DataKey::ReportActions(report_id);
+            env.storage().persistent().set(&_ttl_key, &actions);
+            env.storage().persistent().extend_ttl(
+                &_ttl_key,
+                PERSISTENT_LIFETIME_THRESHOLD,
+                PERSISTENT_BUMP_AMOUNT,
+            );
+        }
+        taken
+    }
+
+    /// Drop one critical report's claim on the publisher flag; the flag is
+    /// lifted once no unresolved critical report remains
+    fn _release_publisher_flag(env: &Env, publisher: &Address) {
+        let _ttl_key = DataKey::OpenCriticalReports(publisher.clone());
+        let open: u32 = env.storage().persistent().get(&_ttl_key).unwrap_or(0);
+        if open > 1 {
+            env.storage().persistent().set(&_ttl_key, &(open - 1));
+            env.storage().persistent().extend_ttl(
+                &_ttl_key,
+                PERSISTENT_LIFETIME_THRESHOLD,
+                PERSISTENT_BUMP_AMOUNT,
+            );
+            return;
+        }
+        env.storage().persistent().remove(&_ttl_key);
         env.storage()
-            .instance()
-            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
-        admin.require_auth();
-        let stored_admin: Address = env.storage().instance().get(&DataKey::Admin).unwrap();
-        if admin != stored_admin {
-            panic!("unauthorized");
+            .persistent()
+            .remove(&DataKey::FlaggedPublisher(publisher.clone()));
+    }
+
+    /// Reverse whatever _take_actions did for the report
+    fn _undo_actions(env: &Env, report: &AnomalyReport) {
+        let _ttl_key = DataKey::ReportActions(report.report_id);
+        let actions: ReportActions = match env.storage().persistent().get(&_ttl_key) {
+            Some(actions) => actions,
+            None => return,
+        };
+        env.storage().persistent().remove(&_ttl_key);
+        let this = env.current_contract_address();
+
+        if let (true, Some(publisher)) = (actions.flagged_publisher, &report.publisher) {
+            if let Some(fraud) = env
+                .storage()
+                .instance()
+                .get::<DataKey, Address>(&DataKey::FraudPrevention)
+            {
+                let retracted = Self::_try_call(
+                    env,
+                    &fraud,
+                    "retract_flag",
+                    Vec::from_array(env, [this.into_val(env), publisher.into_val(env)]),
+                );
+                if retracted {
+                    env.events().publish(
+                        (symbol_short!("anomaly"), symbol_short!("unflagged")),
+                        (report.report_id, publisher.clone()),
+                    );
+                }
+            }
         }
 
-        let mut report: AnomalyReport = env
+        if let Some(vault) = env
             .storage()
-            .persistent()
-            .get(&DataKey::Report(report_id))
-            .expect("report not found");
+            .instance()
+            .get::<DataKey, Address>(&DataKey::EscrowVault)
+        {
+            for escrow_id in actions.held_escrows.iter() {
+                let released = Self::_try_call(
+                    env,
+                    &vault,
+                    "release_fraud_hold",
+                    Vec::from_array(env, [this.into_val(env), escrow_id.into_val(env)]),
+                );
+                if released {
+                    env.events().publish(
+                        (symbol_short!("anomaly"), symbol_short!("released")),
+                        (report.report_id, escrow_id),
+                    );
+                }
+            }
+        }
 
-        report.resolved = true;
-        report.resolved_at = Some(env.ledger().timestamp());
-        let _ttl_key = DataKey::Report(report_id);
-        env.storage().persistent().set(&_ttl_key, &report);
+        if actions.paused_campaign {
+            if let Some(lifecycle) = env
+                .storage()
+                .instance()
+                .get::<DataKey, Address>(&DataKey::CampaignLifecycle)
+            {
+                let resumed = Self::_try_call(
+                    env,
+                    &lifecycle,
+                    "resume_after_fraud",
+                    Vec::from_array(env, [this.into_val(env), report.campaign_id.into_val(env)]),
+                );
+                if resumed {
+                    env.events().publish(
+                        (symbol_short!("anomaly"), symbol_short!("resumed")),
+                        (report.report_id, report.campaign_id),
+                    );
+                }
+            }
+        }
+    }
+
+    fn _try_call(env: &Env, contract: &Address, func: &str, args: Vec<Val>) -> bool {
+        let result = env.try_invoke_contract::<(), soroban_sdk::Error>(
+            contract,
+            &Symbol::new(env, func),
+            args,
+        );
+        matches!(result, Ok(Ok(())))
+    }
+
+    /// Score an hour against the subject's adaptive baseline, then fold it in.
+    /// Returns the (impressions, clicks) z-scores in thousandths once the
+    /// baseline has seen `warmup_hours` samples. Hours not newer than the
+    /// last one folded in are scored but not folded in again.
+    fn _observe(
+        env: &Env,
+        subject: &BaselineSubject,
+        hour: u64,
+        impressions: u64,
+        clicks: u64,
+    ) -> Option<(i128, i128)> {
+        let config = Self::get_ewma_config(env.clone());
+        let _ttl_key = DataKey::AdaptiveBaseline(subject.clone());
+        let existing: Option<AdaptiveBaseline> = env.storage().persistent().get(&_ttl_key);
+
+        let z_scores = match &existing {
+            Some(b) if b.samples >= config.warmup_hours => Some((
+                Self::_z_score(&b.impressions, impressions),
+                Self::_z_score(&b.clicks, clicks),
+            )),
+            _ => None,
+        };
+
+        let updated = match existing {
+            None => AdaptiveBaseline {
+                impressions: EwmaStat {
+                    mean: impressions as i128 * EWMA_SCALE,
+                    variance: 0,
+                },
+                clicks: EwmaStat {
+                    mean: clicks as i128 * EWMA_SCALE,
+                    variance: 0,
+                },
+                samples: 1,
+                last_hour: hour,
+                last_updated: env.ledger().timestamp(),
+            },
+            Some(b) if hour > b.last_hour => AdaptiveBaseline {
+                impressions: Self::_ewma_update(&b.impressions, impressions, config.alpha_bps),
+                clicks: Self::_ewma_update(&b.clicks, clicks, config.alpha_bps),
+                samples: b.samples.saturating_add(1),
+                last_hour: hour,
+                last_updated: env.ledger().timestamp(),
+            },
+            Some(_) => return z_scores,
+        };
+        env.storage().persistent().set(&_ttl_key, &updated);
         env.storage().persistent().extend_ttl(
             &_ttl_key,
             PERSISTENT_LIFETIME_THRESHOLD,
             PERSISTENT_BUMP_AMOUNT,
         );
+
+        z_scores
     }
 
-    pub fn get_report(env: Env, report_id: u64) -> Option<AnomalyReport> {
-        env.storage()
-            .instance()
-            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
-        env.storage().persistent().get(&DataKey::Report(report_id))
+    /// mean += a(x - mean); variance = (1 - a)(variance + a(x - mean)^2)
+    fn _ewma_update(stat: &EwmaStat, observed: u64, alpha_bps: u32) -> EwmaStat {
+        let alpha = alpha_bps as i128;
+        let diff = observed as i128 * EWMA_SCALE - stat.mean;
+        let increment = diff * alpha / 10_000;
+        EwmaStat {
+            mean: stat.mean + increment,
+            variance: (10_000 - alpha) * (stat.variance + diff * increment) / 10_000,
+        }
     }
 
-    pub fn get_baseline(env: Env, campaign_id: u64) -> Option<TrafficBaseline> {
-        env.storage()
-            .instance()
-            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
-        env.storage()
-            .persistent()
-            .get(&DataKey::Baseline(campaign_id))
+    /// z-score in thousandths. The deviation is floored at the Poisson
+    /// deviation sqrt(mean), and at one, so steady traffic cannot make
+    /// every small wobble an outlier.
+    fn _z_score(stat: &EwmaStat, observed: u64) -> i128 {
+        let diff = observed as i128 * EWMA_SCALE - stat.mean;
+        let std_dev = Self::_isqrt(stat.variance)
+            .max(Self::_isqrt(stat.mean.max(0) * EWMA_SCALE))
+            .max(EWMA_SCALE);
+        diff * 1_000 / std_dev
     }
 
-    pub fn is_publisher_flagged(env: Env, publisher: Address) -> bool {
-        env.storage()
-            .instance()
-            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
-        env.storage()
-            .persistent()
-            .get(&DataKey::FlaggedPublisher(publisher))
-            .unwrap_or(false)
+    fn _isqrt(value: i128) -> i128 {
+        if value <= 0 {
+            return 0;
+        }
+        let mut x = value;
+        let mut y = (x + 1) / 2;
+        while y < x {
+            x = y;
+            y = (x + value / x) / 2;
+        }
+        x
     }
 
-    pub fn get_report_count(env: Env) -> u64 {
-        env.storage()
-            .instance()
-            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
-        env.storage()
-            .instance()
-            .get(&DataKey::ReportCounter)
-            .unwrap_or(0)
+    /// Upward z-score outliers. A click outlier without an impression outlier
+    /// is click farming; anything else is a traffic spike. The worst z-score
+    /// is returned as a % of the threshold.
+    fn _classify_z(
+        env: &Env,
+        impressions_z: i128,
+        clicks_z: i128,
+    ) -> Option<(AnomalyType, u64, &'static str)> {
+        let threshold = Self::get_ewma_config(env.clone()).z_threshold_milli as i128;
+        let impressions_pct = (impressions_z.max(0) * 100 / threshold) as u64;
+        let clicks_pct = (clicks_z.max(0) * 100 / threshold) as u64;
+        if clicks_pct > 100 && impressions_pct <= 100 {
+            Some((
+                AnomalyType::ClickFarming,
+                clicks_pct,
+                "click z-score outlier",
+            ))
+        } else if impressions_pct > 100 || clicks_pct > 100 {
+            Some((
+                AnomalyType::SuddenTrafficSpike,
+                impressions_pct.max(clicks_pct),
+                "traffic z-score outlier",
+            ))
+        } else {
+            None
+        }
+    }
+
+    /// Fixed-baseline checks: volume against `spike_threshold_pct` and CTR
+    /// against the scan config's `ctr_threshold_pct`
+    fn _classify_pct(
+        env: &Env,
+        stats: &HourlyStats,
+        baseline: &TrafficBaseline,
+    ) -> Option<(AnomalyType, u64, &'static str)> {
+        let config = Self::get_scan_config(env.clone());
+
+        // Each signal is measured as a % of its threshold; >100 means exceeded
+        let impressions_pct = Self::_pct_of_threshold(
+            stats.impressions,
+            baseline.avg_impressions_per_hour,
+            baseline.spike_threshold_pct,
+        );
+        let clicks_pct = Self::_pct_of_threshold(
+            stats.clicks,
+            baseline.avg_clicks_per_hour,
+            baseline.spike_threshold_pct,
+        );
+        let ctr_bps = Self::_ctr_bps(stats.clicks, stats.impressions);
+        let baseline_ctr_bps = Self::_ctr_bps(
+            baseline.avg_clicks_per_hour,
+            baseline.avg_impressions_per_hour,
+        );
+        let ctr_pct = if stats.impressions >= config.min_impressions {
+            Self::_pct_of_threshold(ctr_bps, baseline_ctr_bps, config.ctr_threshold_pct)
+        } else {
+            0
+        };
+
+        // Click farming inflates CTR; otherwise a volume spike is a traffic spike
+        if ctr_pct > 100 {
+            Some((AnomalyType::ClickFarming, ctr_pct, "ctr outlier"))
+        } else if impressions_pct > 100 || clicks_pct > 100 {
+            Some((
+                AnomalyType::SuddenTrafficSpike,
+                impressions_pct.max(clicks_pct),
+                "traffic spike",
+            ))
+        } else {
+            None
+        }
+    }
+
+    /// Severity from how far past its threshold the worst signal went
+    fn _severity_for(worst_pct: u64) -> AnomalySeverity {
+        if worst_pct >= 400 {
+            AnomalySeverity::Critical
+        } else if worst_pct >= 200 {
+            AnomalySeverity::High
+        } else if worst_pct >= 150 {
+            AnomalySeverity::Medium
+        } else {
+            AnomalySeverity::Low
+        }
+    }
+
+    /// Click-through rate in basis points; 0 without impressions
+    fn _ctr_bps(clicks: u64, impressions: u64) -> u64 {
+        if impressions == 0 {
+            return 0;
+        }
+        let bps = clicks as u128 * 10_000 / impressions as u128;
+        bps.min(u64::MAX as u128) as u64
+    }
+
+    /// `observed` as a percentage of `average * threshold_pct / 100`
+    fn _pct_of_threshold(observed: u64, average: u64, threshold_pct: u32) -> u64 {
+        let threshold = average.saturating_mul(threshold_pct as u64) / 100;
+        if threshold == 0 {
+            return 0;
+        }
+        observed.saturating_mul(100) / threshold
+    }
+
+    /// JSON snapshot: {"hour":H,"impressions":I,"clicks":C,"ctr_bps":R}
+    fn _metrics_snapshot(
+        env: &Env,
+        hour: u64,
+        impressions: u64,
+        clicks: u64,
+        ctr_bps: u64,
+    ) -> String {
+        let mut buf = [0u8; 128];
+        let mut len = 0;
+        let fields: [(&[u8], u64); 4] = [
+            (b"{\"hour\":", hour),
+            (b",\"impressions\":", impressions),
+            (b",\"clicks\":", clicks),
+            (b",\"ctr_bps\":", ctr_bps),
+        ];
+        for (label, value) in fields.iter() {
+            buf[len..len + label.len()].copy_from_slice(label);
+            len += label.len();
+            len += Self::_write_u64(&mut buf[len..], *value);
+        }
+        buf[len] = b'}';
+        len += 1;
+        String::from_bytes(env, &buf[..len])
+    }
+
+    fn _write_u64(out: &mut [u8], mut value: u64) -> usize {
+        let mut digits = [0u8; 20];
+        let mut n = 0;
+        loop {
+            digits[n] = b'0' + (value % 10) as u8;
+            n += 1;
+            value /= 10;
+            if value == 0 {
+                break;
+            }
+        }
+        for (i, slot) in out.iter_mut().take(n).enumerate() {
+            *slot = digits[n - 1 - i];
+        }
+        n
     }
 
     pub fn propose_admin(env: Env, current_admin: Address, new_admin: Address) {
@@ -1,6 +1,10 @@
 #![cfg(test)]
 use super::*;
-use soroban_sdk::{testutils::Address as _, Address, Env, String};
+use soroban_sdk::{
+    contract, contractimpl, symbol_short,
+    testutils::{Address as _, Ledger},
+    Address, Env, String, Vec,
+};
 
 fn setup(env: &Env) -> (AnomalyDetectorContractClient<'_>, Address, Address) {
     let admin = Address::generate(env);
@@ -69,10 +73,10 @@ fn test_report_anomaly() {
     env.mock_all_auths();
     let (c, _, oracle) = setup(&env);
     let publisher = Address::generate(&env);
-    
+
     // Set baseline first
     c.set_baseline(&oracle, &1u64, &1000u64, &50u64, &300u32);
-    
+
     // Report anomaly with metrics exceeding threshold (300% = 3x)
     // 4000 impressions > 3000 threshold (1000 * 300%)
     c.report_anomaly(
@@ -122,10 +126,10 @@ fn test_report_anomaly_below_threshold() {
     env.mock_all_auths();
     let (c, _, oracle) = setup(&env);
     let publisher = Address::generate(&env);
-    
+
     // Set baseline
     c.set_baseline(&oracle, &1u64, &1000u64, &50u64, &300u32);
-    
+
     // Try to report anomaly with metrics NOT exceeding threshold
     // 2000 impressions < 3000 threshold (1000 * 300%)
     // 100 clicks < 150 threshold (50 * 300%)
@@ -149,7 +153,7 @@ fn test_report_anomaly_no_baseline() {
     env.mock_all_auths();
     let (c, _, oracle) = setup(&env);
     let publisher = Address::generate(&env);
-    
+
     // Report anomaly without setting baseline (should succeed)
     c.report_anomaly(
         &oracle,
@@ -172,10 +176,10 @@ fn test_report_anomaly_clicks_exceed_threshold() {
     env.mock_all_auths();
     let (c, _, oracle) = setup(&env);
     let publisher = Address::generate(&env);
-    
+
     // Set baseline
     c.set_baseline(&oracle, &1u64, &1000u64, &50u64, &300u32);
-    
+
     // Report anomaly where only clicks exceed threshold
     // 2000 impressions < 3000 threshold
     // 200 clicks > 150 threshold (50 * 300%)
@@ -193,3 +197,645 @@ fn test_report_anomaly_clicks_exceed_threshold() {
     );
     assert_eq!(c.get_report_count(), 1);
 }
+
+// ─── scan_campaign ───────────────────────────────────────────────────────────
+
+#[contract]
+pub struct MockAnalytics;
+
+#[contractimpl]
+impl MockAnalytics {
+    pub fn set_stats(env: Env, campaign_id: u64, hour: u64, impressions: u64, clicks: u64) {
+        let stats = HourlyStats {
+            impressions,
+            clicks,
+            spend: 0,
+            timestamp: hour * 3600,
+        };
+        env.storage().instance().set(&(campaign_id, hour), &stats);
+    }
+
+    pub fn get_hourly_stats(env: Env, campaign_id: u64, hour: u64) -> Option<HourlyStats> {
+        env.storage().instance().get(&(campaign_id, hour))
+    }
+}
+
+fn setup_scan<'a>(env: &'a Env) -> (AnomalyDetectorContractClient<'a>, MockAnalyticsClient<'a>) {
+    let (c, admin, oracle) = setup(env);
+    let analytics = MockAnalyticsClient::new(env, &env.register(MockAnalytics, ()));
+    c.set_analytics_aggregator(&admin, &analytics.address);
+    // 1000 impressions and 20 clicks (2% CTR) per hour, alert above 300%
+    c.set_baseline(&oracle, &1u64, &1000u64, &20u64, &300u32);
+    env.ledger().with_mut(|li| li.timestamp = 10 * 3600);
+    (c, analytics)
+}
+
+#[test]
+fn test_scan_campaign_normal_traffic() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (c, analytics) = setup_scan(&env);
+    analytics.set_stats(&1u64, &5u64, &1200u64, &25u64);
+
+    assert_eq!(c.scan_campaign(&1u64, &5u64), None);
+    assert!(c.is_hour_scanned(&1u64, &5u64));
+    assert_eq!(c.get_report_count(), 0);
+}
+
+#[test]
+fn test_scan_campaign_traffic_spike() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (c, analytics) = setup_scan(&env);
+    // 6000 impressions = 200% of the 3000 threshold, CTR unchanged
+    analytics.set_stats(&1u64, &5u64, &6000u64, &120u64);
+
+    let report_id = c.scan_campaign(&1u64, &5u64).unwrap();
+    let report = c.get_report(&report_id).unwrap();
+    assert!(matches!(
+        report.anomaly_type,
+        AnomalyType::SuddenTrafficSpike
+    ));
+    assert!(matches!(report.severity, AnomalySeverity::High));
+    assert!(report.publisher.is_none());
+    assert_eq!(
+        report.metrics_snapshot,
+        s(
+            &env,
+            "{\"hour\":5,\"impressions\":6000,\"clicks\":120,\"ctr_bps\":200}"
+        )
+    );
+}
+
+#[test]
+fn test_scan_campaign_click_farming() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (c, analytics) = setup_scan(&env);
+    // Normal volume but 30% CTR against a 2% baseline
+    analytics.set_stats(&1u64, &5u64, &1000u64, &300u64);
+
+    let report_id = c.scan_campaign(&1u64, &5u64).unwrap();
+    let report = c.get_report(&report_id).unwrap();
+    assert!(matches!(report.anomaly_type, AnomalyType::ClickFarming));
+    assert!(matches!(report.severity, AnomalySeverity::Critical));
+}
+
+#[test]
+fn test_scan_campaign_ctr_needs_min_impressions() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (c, analytics) = setup_scan(&env);
+    analytics.set_stats(&1u64, &5u64, &50u64, &20u64);
+
+    assert_eq!(c.scan_campaign(&1u64, &5u64), None);
+}
+
+#[test]
+fn test_scan_campaign_missing_stats() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (c, _) = setup_scan(&env);
+    assert_eq!(c.scan_campaign(&1u64, &5u64), None);
+}
+
+#[test]
+#[should_panic(expected = "hour not complete")]
+fn test_scan_campaign_current_hour() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (c, _) = setup_scan(&env);
+    c.scan_campaign(&1u64, &10u64);
+}
+
+#[test]
+#[should_panic(expected = "hour already scanned")]
+fn test_scan_campaign_twice() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (c, analytics) = setup_scan(&env);
+    analytics.set_stats(&1u64, &5u64, &6000u64, &120u64);
+    c.scan_campaign(&1u64, &5u64);
+    c.scan_campaign(&1u64, &5u64);
+}
+
+#[test]
+fn test_scan_campaign_without_baseline() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (c, analytics) = setup_scan(&env);
+    // No fixed baseline for campaign 2: the hour only warms up its EWMA
+    analytics.set_stats(&2u64, &5u64, &6000u64, &120u64);
+    assert_eq!(c.scan_campaign(&2u64, &5u64), None);
+    let baseline = c
+        .get_adaptive_baseline(&BaselineSubject::Campaign(2))
+        .unwrap();
+    assert_eq!(baseline.samples, 1);
+    assert_eq!(baseline.impressions.mean, 6_000_000);
+}
+
+// ─── auto-actions ────────────────────────────────────────────────────────────
+
+#[contract]
+pub struct MockFraud;
+
+#[contractimpl]
+impl MockFraud {
+    pub fn flag_suspicious(env: Env, caller: Address, publisher: Address) {
+        let _ = (caller, publisher);
+        let flags: u32 = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("flags"))
+            .unwrap_or(0);
+        env.storage()
+            .instance()
+            .set(&symbol_short!("flags"), &(flags + 1));
+    }
+
+    pub fn retract_flag(env: Env, caller: Address, publisher: Address) {
+        let _ = (caller, publisher);
+        let flags: u32 = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("flags"))
+            .unwrap_or(0);
+        env.storage()
+            .instance()
+            .set(&symbol_short!("flags"), &(flags - 1));
+    }
+
+    pub fn flags(env: Env) -> u32 {
+        env.storage()
+            .instance()
+            .get(&symbol_short!("flags"))
+            .unwrap_or(0)
+    }
+}
+
+#[contract]
+pub struct MockLifecycle;
+
+#[contractimpl]
+impl MockLifecycle {
+    pub fn pause_for_fraud(env: Env, caller: Address, campaign_id: u64) {
+        let _ = caller;
+        if env.storage().instance().has(&campaign_id) {
+            panic!("invalid state transition");
+        }
+        env.storage().instance().set(&campaign_id, &true);
+    }
+
+    pub fn resume_after_fraud(env: Env, caller: Address, campaign_id: u64) {
+        let _ = caller;
+        env.storage().instance().remove(&campaign_id);
+    }
+
+    pub fn is_paused(env: Env, campaign_id: u64) -> bool {
+        env.storage().instance().has(&campaign_id)
+    }
+}
+
+#[contract]
+pub struct MockVault;
+
+#[contractimpl]
+impl MockVault {
+    pub fn get_beneficiary_escrows(env: Env, beneficiary: Address) -> Vec<u64> {
+        let _ = beneficiary;
+        soroban_sdk::vec![&env, 7u64, 8u64]
+    }
+
+    pub fn hold_for_fraud(env: Env, caller: Address, escrow_id: u64) {
+        let _ = caller;
+        env.storage().instance().set(&escrow_id, &true);
+    }
+
+    pub fn release_fraud_hold(env: Env, caller: Address, escrow_id: u64) {
+        let _ = caller;
+        env.storage().instance().remove(&escrow_id);
+    }
+
+    pub fn is_held(env: Env, escrow_id: u64) -> bool {
+        env.storage().instance().has(&escrow_id)
+    }
+}
+
+struct ActionStubs<'a> {
+    fraud: MockFraudClient<'a>,
+    lifecycle: MockLifecycleClient<'a>,
+    vault: MockVaultClient<'a>,
+}
+
+fn setup_actions<'a>(
+    env: &'a Env,
+    c: &AnomalyDetectorContractClient,
+    admin: &Address,
+) -> ActionStubs<'a> {
+    let stubs = ActionStubs {
+        fraud: MockFraudClient::new(env, &env.register(MockFraud, ())),
+        lifecycle: MockLifecycleClient::new(env, &env.register(MockLifecycle, ())),
+        vault: MockVaultClient::new(env, &env.register(MockVault, ())),
+    };
+    c.set_action_contracts(
+        admin,
+        &stubs.fraud.address,
+        &stubs.lifecycle.address,
+        &stubs.vault.address,
+    );
+    stubs
+}
+
+fn report(
+    env: &Env,
+    c: &AnomalyDetectorContractClient,
+    oracle: &Address,
+    publisher: &Address,
+    severity: AnomalySeverity,
+    auto_action: bool,
+) -> u64 {
+    c.report_anomaly(
+        oracle,
+        &1u64,
+        &Some(publisher.clone()),
+        &AnomalyType::ClickFarming,
+        &severity,
+        &s(env, "spike"),
+        &s(env, "{}"),
+        &auto_action,
+        &4000u64,
+        &200u64,
+    )
+}
+
+#[test]
+fn test_critical_report_actions_undone_on_resolve() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (c, admin, oracle) = setup(&env);
+    let stubs = setup_actions(&env, &c, &admin);
+    let publisher = Address::generate(&env);
+
+    let report_id = report(
+        &env,
+        &c,
+        &oracle,
+        &publisher,
+        AnomalySeverity::Critical,
+        true,
+    );
+    assert!(c.get_report(&report_id).unwrap().auto_action_taken);
+    assert_eq!(stubs.fraud.flags(), 1);
+    assert!(stubs.lifecycle.is_paused(&1u64));
+    assert!(stubs.vault.is_held(&7u64) && stubs.vault.is_held(&8u64));
+    let actions = c.get_report_actions(&report_id).unwrap();
+    assert!(actions.flagged_publisher && actions.paused_campaign);
+    assert_eq!(actions.held_escrows, soroban_sdk::vec![&env, 7u64, 8u64]);
+
+    c.resolve_anomaly(&admin, &report_id);
+    assert_eq!(stubs.fraud.flags(), 0);
+    assert!(!stubs.lifecycle.is_paused(&1u64));
+    assert!(!stubs.vault.is_held(&7u64) && !stubs.vault.is_held(&8u64));
+    assert!(c.get_report_actions(&report_id).is_none());
+}
+
+#[test]
+fn test_low_severity_notifies_only() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (c, admin, oracle) = setup(&env);
+    let stubs = setup_actions(&env, &c, &admin);
+
+    let report_id = report(
+        &env,
+        &c,
+        &oracle,
+        &Address::generate(&env),
+        AnomalySeverity::Low,
+        true,
+    );
+    assert!(!c.get_report(&report_id).unwrap().auto_action_taken);
+    assert_eq!(stubs.fraud.flags(), 0);
+    assert!(!stubs.lifecycle.is_paused(&1u64));
+    assert!(c.get_report_actions(&report_id).is_none());
+}
+
+#[test]
+fn test_auto_action_disabled_skips_policy() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (c, admin, oracle) = setup(&env);
+    let stubs = setup_actions(&env, &c, &admin);
+
+    report(
+        &env,
+        &c,
+        &oracle,
+        &Address::generate(&env),
+        AnomalySeverity::Critical,
+        false,
+    );
+    assert_eq!(stubs.fraud.flags(), 0);
+    assert!(!stubs.lifecycle.is_paused(&1u64));
+}
+
+#[test]
+fn test_custom_action_policy() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (c, admin, oracle) = setup(&env);
+    let stubs = setup_actions(&env, &c, &admin);
+    let policy = ActionPolicy {
+        flag_publisher: false,
+        pause_campaign: true,
+        hold_escrows: false,
+    };
+    c.set_action_policy(&admin, &AnomalySeverity::Medium, &policy);
+
+    report(
+        &env,
+        &c,
+        &oracle,
+        &Address::generate(&env),
+        AnomalySeverity::Medium,
+        true,
+    );
+    assert_eq!(stubs.fraud.flags(), 0);
+    assert!(stubs.lifecycle.is_paused(&1u64));
+    assert!(!stubs.vault.is_held(&7u64));
+}
+
+#[test]
+fn test_failed_action_is_isolated() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (c, admin, oracle) = setup(&env);
+    let stubs = setup_actions(&env, &c, &admin);
+    let publisher = Address::generate(&env);
+
+    let first = report(
+        &env,
+        &c,
+        &oracle,
+        &publisher,
+        AnomalySeverity::Critical,
+        true,
+    );
+    // The campaign is already paused, so only the flag and holds succeed
+    let second = report(
+        &env,
+        &c,
+        &oracle,
+        &publisher,
+        AnomalySeverity::Critical,
+        true,
+    );
+    assert_eq!(stubs.fraud.flags(), 2);
+    let actions = c.get_report_actions(&second).unwrap();
+    assert!(actions.flagged_publisher && !actions.paused_campaign);
+
+    // Resolving the second report leaves the first report's pause in place
+    c.resolve_anomaly(&admin, &second);
+    assert!(stubs.lifecycle.is_paused(&1u64));
+    c.resolve_anomaly(&admin, &first);
+    assert!(!stubs.lifecycle.is_paused(&1u64));
+    assert_eq!(stubs.fraud.flags(), 0);
+}
+
+#[test]
+fn test_resolve_critical_report_unflags_publisher() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (c, admin, oracle) = setup(&env);
+    let publisher = Address::generate(&env);
+
+    let first = report(
+        &env,
+        &c,
+        &oracle,
+        &publisher,
+        AnomalySeverity::Critical,
+        false,
+    );
+    let second = report(
+        &env,
+        &c,
+        &oracle,
+        &publisher,
+        AnomalySeverity::Critical,
+        false,
+    );
+
+    // Still flagged while another critical report is open
+    c.resolve_anomaly(&admin, &first);
+    assert!(c.is_publisher_flagged(&publisher));
+    c.resolve_anomaly(&admin, &second);
+    assert!(!c.is_publisher_flagged(&publisher));
+}
+
+#[test]
+#[should_panic(expected = "already resolved")]
+fn test_resolve_anomaly_twice() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (c, admin, oracle) = setup(&env);
+    let report_id = report(
+        &env,
+        &c,
+        &oracle,
+        &Address::generate(&env),
+        AnomalySeverity::High,
+        true,
+    );
+    c.resolve_anomaly(&admin, &report_id);
+    c.resolve_anomaly(&admin, &report_id);
+}
+
+#[test]
+fn test_scan_campaign_pauses_critical_campaign() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (c, admin, oracle) = setup(&env);
+    let stubs = setup_actions(&env, &c, &admin);
+    let analytics = MockAnalyticsClient::new(&env, &env.register(MockAnalytics, ()));
+    c.set_analytics_aggregator(&admin, &analytics.address);
+    c.set_baseline(&oracle, &1u64, &1000u64, &20u64, &300u32);
+    env.ledger().with_mut(|li| li.timestamp = 10 * 3600);
+
+    analytics.set_stats(&1u64, &5u64, &1000u64, &300u64);
+    let report_id = c.scan_campaign(&1u64, &5u64).unwrap();
+    assert!(c.get_report(&report_id).unwrap().auto_action_taken);
+    assert!(stubs.lifecycle.is_paused(&1u64));
+    // No publisher on scan reports, so nothing to flag or hold
+    assert_eq!(stubs.fraud.flags(), 0);
+    assert!(!stubs.vault.is_held(&7u64));
+}
+
+#[test]
+fn test_scan_campaign_huge_click_count() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (c, analytics) = setup_scan(&env);
+    // clicks * 10_000 would overflow u64
+    analytics.set_stats(&1u64, &5u64, &1000u64, &10_000_000_000_000_000u64);
+
+    let report_id = c.scan_campaign(&1u64, &5u64).unwrap();
+    let report = c.get_report(&report_id).unwrap();
+    assert!(matches!(report.anomaly_type, AnomalyType::ClickFarming));
+}
+
+// ─── adaptive baselines ──────────────────────────────────────────────────────
+
+fn set_warmup(c: &AnomalyDetectorContractClient, admin: &Address, warmup_hours: u32) {
+    c.set_ewma_config(
+        admin,
+        &EwmaConfig {
+            alpha_bps: 1_000,
+            z_threshold_milli: 3_000,
+            warmup_hours,
+        },
+    );
+}
+
+#[test]
+fn test_ewma_update() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (c, _, oracle) = setup(&env);
+    let publisher = Address::generate(&env);
+    env.ledger().with_mut(|li| li.timestamp = 10 * 3600);
+
+    c.observe_publisher_hour(&oracle, &publisher, &0u64, &1000u64, &20u64);
+    c.observe_publisher_hour(&oracle, &publisher, &1u64, &2000u64, &20u64);
+
+    let baseline = c
+        .get_adaptive_baseline(&BaselineSubject::Publisher(publisher))
+        .unwrap();
+    assert_eq!(baseline.samples, 2);
+    assert_eq!(baseline.last_hour, 1);
+    // mean 1000 + 0.1 * 1000, variance 0.9 * 0.1 * 1000^2 (both scaled)
+    assert_eq!(baseline.impressions.mean, 1_100_000);
+    assert_eq!(baseline.impressions.variance, 90_000_000_000);
+    assert_eq!(baseline.clicks.variance, 0);
+}
+
+#[test]
+fn test_publisher_spike_after_warmup() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (c, admin, oracle) = setup(&env);
+    set_warmup(&c, &admin, 3);
+    let publisher = Address::generate(&env);
+    env.ledger().with_mut(|li| li.timestamp = 10 * 3600);
+
+    // Even an extreme hour is not flagged while warming up
+    assert_eq!(
+        c.observe_publisher_hour(&oracle, &publisher, &0u64, &1000u64, &20u64),
+        None
+    );
+    assert_eq!(
+        c.observe_publisher_hour(&oracle, &publisher, &1u64, &1000u64, &20u64),
+        None
+    );
+    assert_eq!(
+        c.observe_publisher_hour(&oracle, &publisher, &2u64, &9000u64, &20u64),
+        None
+    );
+    assert_eq!(
+        c.observe_publisher_hour(&oracle, &publisher, &3u64, &1100u64, &20u64),
+        None
+    );
+
+    let report_id = c
+        .observe_publisher_hour(&oracle, &publisher, &4u64, &20_000u64, &400u64)
+        .unwrap();
+    let report = c.get_report(&report_id).unwrap();
+    assert!(matches!(
+        report.anomaly_type,
+        AnomalyType::SuddenTrafficSpike
+    ));
+    assert!(matches!(report.severity, AnomalySeverity::Critical));
+    assert_eq!(report.campaign_id, 0);
+    assert_eq!(report.publisher, Some(publisher));
+}
+
+#[test]
+fn test_publisher_click_outlier() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (c, admin, oracle) = setup(&env);
+    set_warmup(&c, &admin, 2);
+    let publisher = Address::generate(&env);
+    env.ledger().with_mut(|li| li.timestamp = 10 * 3600);
+
+    c.observe_publisher_hour(&oracle, &publisher, &0u64, &1000u64, &20u64);
+    c.observe_publisher_hour(&oracle, &publisher, &1u64, &1000u64, &20u64);
+    // Clicks 8.9 deviations above the mean with steady impressions
+    let report_id = c
+        .observe_publisher_hour(&oracle, &publisher, &2u64, &1000u64, &60u64)
+        .unwrap();
+    let report = c.get_report(&report_id).unwrap();
+    assert!(matches!(report.anomaly_type, AnomalyType::ClickFarming));
+    assert!(matches!(report.severity, AnomalySeverity::High));
+}
+
+#[test]
+#[should_panic(expected = "stale hour")]
+fn test_observe_publisher_stale_hour() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (c, _, oracle) = setup(&env);
+    let publisher = Address::generate(&env);
+    env.ledger().with_mut(|li| li.timestamp = 10 * 3600);
+    c.observe_publisher_hour(&oracle, &publisher, &3u64, &1000u64, &20u64);
+    c.observe_publisher_hour(&oracle, &publisher, &3u64, &1000u64, &20u64);
+}
+
+#[test]
+fn test_scan_campaign_uses_adaptive_baseline() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (c, admin, _) = setup(&env);
+    set_warmup(&c, &admin, 2);
+    let analytics = MockAnalyticsClient::new(&env, &env.register(MockAnalytics, ()));
+    c.set_analytics_aggregator(&admin, &analytics.address);
+    env.ledger().with_mut(|li| li.timestamp = 10 * 3600);
+
+    analytics.set_stats(&1u64, &1u64, &1000u64, &20u64);
+    analytics.set_stats(&1u64, &2u64, &1010u64, &21u64);
+    analytics.set_stats(&1u64, &3u64, &1050u64, &21u64);
+    analytics.set_stats(&1u64, &4u64, &3000u64, &60u64);
+    assert_eq!(c.scan_campaign(&1u64, &1u64), None);
+    assert_eq!(c.scan_campaign(&1u64, &2u64), None);
+    // Within normal variation once warmed up
+    assert_eq!(c.scan_campaign(&1u64, &3u64), None);
+
+    let report_id = c.scan_campaign(&1u64, &4u64).unwrap();
+    let report = c.get_report(&report_id).unwrap();
+    assert!(matches!(
+        report.anomaly_type,
+        AnomalyType::SuddenTrafficSpike
+    ));
+    assert_eq!(
+        c.get_adaptive_baseline(&BaselineSubject::Campaign(1))
+            .unwrap()
+            .samples,
+        4
+    );
+}
+
+#[test]
+#[should_panic(expected = "invalid alpha")]
+fn test_set_ewma_config_invalid_alpha() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (c, admin, _) = setup(&env);
+    c.set_ewma_config(
+        &admin,
+        &EwmaConfig {
+            alpha_bps: 0,
+            z_threshold_milli: 3_000,
+            warmup_hours: 24,
+        },
+    );
+}
@@ -5,9 +5,22 @@
 //! - ("lifecycle", "transit"): [campaign_id: u64]
 //! - ("campaign", "pause"): [campaign_id: u64, actor: Address]
 //! - ("campaign", "resume"): [campaign_id: u64, actor: Address]
+//! - ("lifecycle", "rule"): [from: LifecycleState, to: LifecycleState]
+//! - ("lifecycle", "schedule"): [campaign_id: u64, start_ledger: u32]
+//! - ("review", "approved"): [campaign_id: u64, moderator: Address]
+//! - ("review", "rejected"): [campaign_id: u64, moderator: Address]
+//! - ("hook", "failed"): [hook: Address, campaign_id: u64]
+//!
+//! Hooks: contracts registered with `register_hook` are called with
+//! `on_lifecycle_change(campaign_id: u64, from: LifecycleState, to: LifecycleState)`
+//! after every state change. A hook that fails is reported via ("hook", "failed")
+//! and does not roll back the transition.
 
 #![no_std]
-use soroban_sdk::{contract, contractimpl, contracttype, symbol_short, Address, Env, String};
+use soroban_sdk::{
+    contract, contractimpl, contracttype, symbol_short, vec, Address, Env, IntoVal, Map, String,
+    Symbol, Vec,
+};
 
 #[contracttype]
 #[derive(Clone, PartialEq)]
@@ -23,6 +36,24 @@ pub enum LifecycleState {
     Rejected,
 }
 
+/// Who may perform a state transition. A rule lists the roles allowed to
+/// take a given edge; an actor passes if it holds any of them.
+#[contracttype]
+#[derive(Clone, PartialEq)]
+pub enum Role {
+    Advertiser,
+    Admin,
+    Moderator,
+    FraudContract,
+}
+
+#[contracttype]
+#[derive(Clone)]
+pub struct ScheduledActivation {
+    pub start_ledger: u32,
+    pub scheduled_by: Address,
+}
+
 #[contracttype]
 #[derive(Clone)]
 pub struct CampaignLifecycle {
@@ -60,6 +91,16 @@ pub enum DataKey {
     Lifecycle(u64),
     TransitionCount(u64),
     Transition(u64, u32), // campaign_id, transition_index
+    TransitionRule(LifecycleState, LifecycleState),
+    Moderator(Address),
+    ScheduledActivation(u64),
+    AdRegistry,
+    ReviewCount,
+    ReviewSlot(u32),     // queue position -> campaign_id
+    ReviewPosition(u64), // campaign_id -> queue position
+    Hooks,
+    AnomalyDetector,
+    FraudHolds(u64), // campaign_id -> pauses outstanding per fraud authority
 }
 
 const INSTANCE_LIFETIME_THRESHOLD: u32 = 17_280;
@@ -71,6 +112,8 @@ const PERSISTENT_BUMP_AMOUNT: u32 = 1_051_200;
 const MAX_EXTENSIONS: u32 = 10;
 /// Maximum total duration multiplier relative to original_end_ledger.
 const MAX_DURATION_MULTIPLIER: u32 = 3;
+/// Maximum number of hook contracts notified on each transition.
+const MAX_HOOKS: u32 = 8;
 
 #[contract]
 pub struct CampaignLifecycleContract;
@@ -105,30 +148,179 @@ impl CampaignLifecycleContract {
             .set(&DataKey::FraudContract, &fraud_contract);
     }
 
+    /// Let anomaly-detector pause and resume campaigns alongside the fraud contract
+    pub fn set_anomaly_detector(env: Env, admin: Address, anomaly_detector: Address) {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        admin.require_auth();
+        let stored_admin: Address = env.storage().instance().get(&DataKey::Admin).unwrap();
+        if admin != stored_admin {
+            panic!("unauthorized");
+        }
+        env.storage()
+            .instance()
+            .set(&DataKey::AnomalyDetector, &anomaly_detector);
+    }
+
+    pub fn set_ad_registry(env: Env, admin: Address, ad_registry: Address) {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        admin.require_auth();
+        let stored_admin: Address = env.storage().instance().get(&DataKey::Admin).unwrap();
+        if admin != stored_admin {
+            panic!("unauthorized");
+        }
+        env.storage()
+            .instance()
+            .set(&DataKey::AdRegistry, &ad_registry);
+    }
+
+    pub fn register_hook(env: Env, admin: Address, hook: Address) {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        admin.require_auth();
+        let stored_admin: Address = env.storage().instance().get(&DataKey::Admin).unwrap();
+        if admin != stored_admin {
+            panic!("unauthorized");
+        }
+        let mut hooks: Vec<Address> = env
+            .storage()
+            .instance()
+            .get(&DataKey::Hooks)
+            .unwrap_or(Vec::new(&env));
+        if hooks.contains(&hook) {
+            panic!("hook already registered");
+        }
+        if hooks.len() >= MAX_HOOKS {
+            panic!("too many hooks");
+        }
+        hooks.push_back(hook);
+        env.storage().instance().set(&DataKey::Hooks, &hooks);
+    }
+
+    pub fn unregister_hook(env: Env, admin: Address, hook: Address) {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        admin.require_auth();
+        let stored_admin: Address = env.storage().instance().get(&DataKey::Admin).unwrap();
+        if admin != stored_admin {
+            panic!("unauthorized");
+        }
+        let mut hooks: Vec<Address> = env
+            .storage()
+            .instance()
+            .get(&DataKey::Hooks)
+            .unwrap_or(Vec::new(&env));
+        let idx = hooks.first_index_of(&hook).expect("hook not registered");
+        hooks.remove(idx);
+        env.storage().instance().set(&DataKey::Hooks, &hooks);
+    }
+
+    pub fn get_hooks(env: Env) -> Vec<Address> {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        env.storage()
+            .instance()
+            .get(&DataKey::Hooks)
+            .unwrap_or(Vec::new(&env))
+    }
+
+    /// Pause a campaign on behalf of a fraud authority. Each call takes a
+    /// hold that `resume_after_fraud` from the same authority releases; a
+    /// campaign already paused under a hold just gains another one.
     pub fn pause_for_fraud(env: Env, fraud_contract: Address, campaign_id: u64) {
         env.storage()
             .instance()
             .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
         fraud_contract.require_auth();
 
-        let stored_fraud_contract: Address = env
+        if !Self::_is_fraud_authority(&env, &fraud_contract) {
+            panic!("unauthorized fraud contract");
+        }
+
+        let lifecycle: CampaignLifecycle = env
             .storage()
+            .persistent()
+            .get(&DataKey::Lifecycle(campaign_id))
+            .expect("lifecycle not found");
+        let mut holds = Self::_load_fraud_holds(&env, campaign_id);
+        let already_held = lifecycle.state == LifecycleState::Paused && !holds.is_empty();
+        if !already_held {
+            Self::_authorize_transition(&env, &lifecycle, &fraud_contract, &LifecycleState::Paused);
+        }
+        holds.set(
+            fraud_contract.clone(),
+            holds.get(fraud_contract.clone()).unwrap_or(0) + 1,
+        );
+        if !already_held {
+            Self::_apply_transition(
+                &env,
+                lifecycle,
+                fraud_contract,
+                LifecycleState::Paused,
+                String::from_str(&env, "paused for fraud detection"),
+            );
+        }
+        Self::_save_fraud_holds(&env, campaign_id, &holds);
+    }
+
+    /// Release one hold taken by `pause_for_fraud`. The campaign is only
+    /// reactivated once no fraud authority holds it any more.
+    pub fn resume_after_fraud(env: Env, fraud_contract: Address, campaign_id: u64) {
+        env.storage()
             .instance()
-            .get(&DataKey::FraudContract)
-            .expect("fraud contract not set");
-        if fraud_contract != stored_fraud_contract {
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        fraud_contract.require_auth();
+
+        if !Self::_is_fraud_authority(&env, &fraud_contract) {
             panic!("unauthorized fraud contract");
         }
 
-        Self::transition(
-            env.clone(),
+        let lifecycle: CampaignLifecycle = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Lifecycle(campaign_id))
+            .expect("lifecycle not found");
+        if lifecycle.state != LifecycleState::Paused {
+            panic!("campaign not paused");
+        }
+        let mut holds = Self::_load_fraud_holds(&env, campaign_id);
+        let held = holds.get(fraud_contract.clone()).unwrap_or(0);
+        if held == 0 {
+            panic!("no fraud hold");
+        }
+        if held > 1 {
+            holds.set(fraud_contract.clone(), held - 1);
+        } else {
+            holds.remove(fraud_contract.clone());
+        }
+        if !holds.is_empty() {
+            Self::_save_fraud_holds(&env, campaign_id, &holds);
+            return;
+        }
+
+        Self::_authorize_transition(&env, &lifecycle, &fraud_contract, &LifecycleState::Active);
+        Self::_apply_transition(
+            &env,
+            lifecycle,
             fraud_contract,
-            campaign_id,
-            LifecycleState::Paused,
-            String::from_str(&env, "paused for fraud detection"),
+            LifecycleState::Active,
+            String::from_str(&env, "resumed after fraud review"),
         );
     }
 
+    pub fn get_fraud_holds(env: Env, campaign_id: u64) -> Map<Address, u32> {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        Self::_load_fraud_holds(&env, campaign_id)
+    }
+
     pub fn register_campaign(env: Env, advertiser: Address, campaign_id: u64, end_ledger: u32) {
         env.storage()
             .instance()
@@ -180,29 +372,40 @@ impl CampaignLifecycleContract {
             .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
         actor.require_auth();
 
-        let mut lifecycle: CampaignLifecycle = env
+        let lifecycle: CampaignLifecycle = env
             .storage()
             .persistent()
             .get(&DataKey::Lifecycle(campaign_id))
             .expect("lifecycle not found");
 
-        let admin: Address = env.storage().instance().get(&DataKey::Admin).unwrap();
-        let fraud_contract: Option<Address> = env.storage().instance().get(&DataKey::FraudContract);
-
-        // Only advertiser, admin or authorized fraud contract can transition
-        if actor != lifecycle.advertiser && actor != admin {
-            if let Some(fraud_addr) = fraud_contract {
-                if actor != fraud_addr {
-                    panic!("unauthorized");
-                }
-            } else {
-                panic!("unauthorized");
+        // A fraud-held campaign is resumed by its holders through
+        // `resume_after_fraud`; only the admin may override them, with a reason.
+        if lifecycle.state == LifecycleState::Paused
+            && new_state == LifecycleState::Active
+            && !Self::_load_fraud_holds(&env, campaign_id).is_empty()
+        {
+            let admin: Address = env.storage().instance().get(&DataKey::Admin).unwrap();
+            if actor != admin {
+                panic!("campaign under fraud hold");
+            }
+            if reason.is_empty() {
+                panic!("reason required");
             }
         }
 
-        // Validate state transition
+        Self::_authorize_transition(&env, &lifecycle, &actor, &new_state);
+        Self::_apply_transition(&env, lifecycle, actor, new_state, reason);
+    }
+
+    fn _apply_transition(
+        env: &Env,
+        mut lifecycle: CampaignLifecycle,
+        actor: Address,
+        new_state: LifecycleState,
+        reason: String,
+    ) {
+        let campaign_id = lifecycle.campaign_id;
         let old_state = lifecycle.state.clone();
-        Self::_validate_transition(&old_state, &new_state);
 
         // Apply state
         let now = env.ledger().timestamp();
@@ -232,6 +435,23 @@ impl CampaignLifecycleContract {
             }
             _ => {}
         }
+        // Leaving Paused drops outstanding fraud holds: the last release, an
+        // admin override, cancellation or expiry.
+        if old_state == LifecycleState::Paused && new_state != LifecycleState::Paused {
+            env.storage()
+                .persistent()
+                .remove(&DataKey::FraudHolds(campaign_id));
+        }
+        if new_state != LifecycleState::Draft && new_state != LifecycleState::PendingReview {
+            env.storage()
+                .persistent()
+                .remove(&DataKey::ScheduledActivation(campaign_id));
+        }
+        if new_state == LifecycleState::PendingReview {
+            Self::_enqueue_review(env, campaign_id);
+        } else if old_state == LifecycleState::PendingReview {
+            Self::_dequeue_review(env, campaign_id);
+        }
         lifecycle.state = new_state.clone();
 
         let _ttl_key = DataKey::Lifecycle(campaign_id);
@@ -249,8 +469,8 @@ impl CampaignLifecycleContract {
             .get(&DataKey::TransitionCount(campaign_id))
             .unwrap_or(0);
         let transition = StateTransition {
-            from_state: old_state,
-            to_state: new_state,
+            from_state: old_state.clone(),
+            to_state: new_state.clone(),
             actor,
             reason,
             timestamp: now,
@@ -274,6 +494,36 @@ impl CampaignLifecycleContract {
             (symbol_short!("lifecycle"), symbol_short!("transit")),
             campaign_id,
         );
+
+        Self::_notify_hooks(env, campaign_id, &old_state, &new_state);
+    }
+
+    fn _notify_hooks(env: &Env, campaign_id: u64, from: &LifecycleState, to: &LifecycleState) {
+        let hooks: Vec<Address> = env
+            .storage()
+            .instance()
+            .get(&DataKey::Hooks)
+            .unwrap_or(Vec::new(env));
+        for hook in hooks.iter() {
+            let result = env.try_invoke_contract::<(), soroban_sdk::Error>(
+                &hook,
+                &Symbol::new(env, "on_lifecycle_change"),
+                Vec::from_array(
+                    env,
+                    [
+                        campaign_id.into_val(env),
+                        from.into_val(env),
+                        to.into_val(env),
+                    ],
+                ),
+            );
+            if !matches!(result, Ok(Ok(()))) {
+                env.events().publish(
+                    (symbol_short!("hook"), symbol_short!("failed")),
+                    (hook, campaign_id),
+                );
+            }
+        }
     }
 
     pub fn extend_campaign(env: Env, advertiser: Address, campaign_id: u64, extra_ledgers: u32) {
@@ -328,6 +578,290 @@ impl CampaignLifecycleContract {
         );
     }
 
+    pub fn set_transition_rule(
+        env: Env,
+        admin: Address,
+        from: LifecycleState,
+        to: LifecycleState,
+        roles: Vec<Role>,
+    ) {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        admin.require_auth();
+        let stored_admin: Address = env.storage().instance().get(&DataKey::Admin).unwrap();
+        if admin != stored_admin {
+            panic!("unauthorized");
+        }
+        // An empty role list disables the edge entirely.
+        env.storage()
+            .instance()
+            .set(&DataKey::TransitionRule(from.clone(), to.clone()), &roles);
+
+        env.events().publish(
+            (symbol_short!("lifecycle"), symbol_short!("rule")),
+            (from, to),
+        );
+    }
+
+    pub fn clear_transition_rule(
+        env: Env,
+        admin: Address,
+        from: LifecycleState,
+        to: LifecycleState,
+    ) {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        admin.require_auth();
+        let stored_admin: Address = env.storage().instance().get(&DataKey::Admin).unwrap();
+        if admin != stored_admin {
+            panic!("unauthorized");
+        }
+        env.storage()
+            .instance()
+            .remove(&DataKey::TransitionRule(from.clone(), to.clone()));
+
+        env.events().publish(
+            (symbol_short!("lifecycle"), symbol_short!("rule")),
+            (from, to),
+        );
+    }
+
+    pub fn get_transition_rule(env: Env, from: LifecycleState, to: LifecycleState) -> Vec<Role> {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        Self::_effective_rule(&env, &from, &to)
+    }
+
+    pub fn add_moderator(env: Env, admin: Address, moderator: Address) {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        admin.require_auth();
+        let stored_admin: Address = env.storage().instance().get(&DataKey::Admin).unwrap();
+        if admin != stored_admin {
+            panic!("unauthorized");
+        }
+        env.storage()
+            .instance()
+            .set(&DataKey::Moderator(moderator), &true);
+    }
+
+    pub fn remove_moderator(env: Env, admin: Address, moderator: Address) {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        admin.require_auth();
+        let stored_admin: Address = env.storage().instance().get(&DataKey::Admin).unwrap();
+        if admin != stored_admin {
+            panic!("unauthorized");
+        }
+        env.storage()
+            .instance()
+            .remove(&DataKey::Moderator(moderator));
+    }
+
+    pub fn is_moderator(env: Env, account: Address) -> bool {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        env.storage()
+            .instance()
+            .get(&DataKey::Moderator(account))
+            .unwrap_or(false)
+    }
+
+    /// Schedules a campaign still in `Draft` or `PendingReview` to go `Active`
+    /// at `start_ledger`. The activation is applied by `tick` on behalf of
+    /// `actor`, so `actor` must be allowed to take the current-state →
+    /// `Active` edge both now and when it fires. `Draft` → `Active` has no
+    /// default rule; the admin has to enable it before a draft can be
+    /// scheduled.
+    pub fn schedule_activation(env: Env, actor: Address, campaign_id: u64, start_ledger: u32) {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        actor.require_auth();
+
+        let lifecycle: CampaignLifecycle = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Lifecycle(campaign_id))
+            .expect("lifecycle not found");
+        if lifecycle.state != LifecycleState::Draft
+            && lifecycle.state != LifecycleState::PendingReview
+        {
+            panic!("campaign already started");
+        }
+        if start_ledger <= env.ledger().sequence() || start_ledger >= lifecycle.current_end_ledger {
+            panic!("invalid start ledger");
+        }
+        let roles = Self::_effective_rule(&env, &lifecycle.state, &LifecycleState::Active);
+        if roles.is_empty() {
+            panic!("invalid state transition");
+        }
+        if !Self::_actor_has_role(&env, &lifecycle, &actor, &roles) {
+            panic!("unauthorized");
+        }
+
+        let scheduled = ScheduledActivation {
+            start_ledger,
+            scheduled_by: actor,
+        };
+        let _ttl_key = DataKey::ScheduledActivation(campaign_id);
+        env.storage().persistent().set(&_ttl_key, &scheduled);
+        env.storage().persistent().extend_ttl(
+            &_ttl_key,
+            PERSISTENT_LIFETIME_THRESHOLD,
+            PERSISTENT_BUMP_AMOUNT,
+        );
+
+        env.events().publish(
+            (symbol_short!("lifecycle"), symbol_short!("schedule")),
+            (campaign_id, start_ledger),
+        );
+    }
+
+    pub fn get_scheduled_activation(env: Env, campaign_id: u64) -> Option<ScheduledActivation> {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        env.storage()
+            .persistent()
+            .get(&DataKey::ScheduledActivation(campaign_id))
+    }
+
+    /// Applies any time-based transition that is due and returns the
+    /// resulting state. Anyone may call it: a campaign that is `Active` or
+    /// `Paused` past `current_end_ledger` becomes `Expired`, and a due
+    /// scheduled activation moves the campaign to `Active`. Expiry is taken
+    /// by the contract itself, so it needs no role, but it only happens while
+    /// the effective rule leaves the edge to `Expired` enabled.
+    pub fn tick(env: Env, campaign_id: u64) -> LifecycleState {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+
+        let lifecycle: CampaignLifecycle = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Lifecycle(campaign_id))
+            .expect("lifecycle not found");
+        let ledger = env.ledger().sequence();
+
+        if (lifecycle.state == LifecycleState::Active || lifecycle.state == LifecycleState::Paused)
+            && ledger > lifecycle.current_end_ledger
+            && !Self::_effective_rule(&env, &lifecycle.state, &LifecycleState::Expired).is_empty()
+        {
+            Self::_apply_transition(
+                &env,
+                lifecycle,
+                env.current_contract_address(),
+                LifecycleState::Expired,
+                String::from_str(&env, "end ledger passed"),
+            );
+            return LifecycleState::Expired;
+        }
+
+        let scheduled: Option<ScheduledActivation> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::ScheduledActivation(campaign_id));
+        if let Some(scheduled) = scheduled {
+            if ledger >= scheduled.start_ledger {
+                let roles = Self::_effective_rule(&env, &lifecycle.state, &LifecycleState::Active);
+                if Self::_actor_has_role(&env, &lifecycle, &scheduled.scheduled_by, &roles)
+                    && Self::_creatives_approved(&env, campaign_id)
+                {
+                    Self::_apply_transition(
+                        &env,
+                        lifecycle,
+                        scheduled.scheduled_by,
+                        LifecycleState::Active,
+                        String::from_str(&env, "scheduled activation"),
+                    );
+                    return LifecycleState::Active;
+                }
+            }
+        }
+
+        lifecycle.state
+    }
+
+    pub fn approve_campaign(env: Env, moderator: Address, campaign_id: u64, reason: String) {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        moderator.require_auth();
+        let lifecycle = Self::_load_for_review(&env, &moderator, campaign_id);
+
+        Self::_authorize_transition(&env, &lifecycle, &moderator, &LifecycleState::Active);
+        Self::_apply_transition(
+            &env,
+            lifecycle,
+            moderator.clone(),
+            LifecycleState::Active,
+            reason,
+        );
+
+        env.events().publish(
+            (symbol_short!("review"), symbol_short!("approved")),
+            (campaign_id, moderator),
+        );
+    }
+
+    pub fn reject_campaign(env: Env, moderator: Address, campaign_id: u64, reason: String) {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        moderator.require_auth();
+        if reason.is_empty() {
+            panic!("reason required");
+        }
+        let lifecycle = Self::_load_for_review(&env, &moderator, campaign_id);
+
+        Self::_authorize_transition(&env, &lifecycle, &moderator, &LifecycleState::Rejected);
+        Self::_apply_transition(
+            &env,
+            lifecycle,
+            moderator.clone(),
+            LifecycleState::Rejected,
+            reason,
+        );
+
+        env.events().publish(
+            (symbol_short!("review"), symbol_short!("rejected")),
+            (campaign_id, moderator),
+        );
+    }
+
+    /// Campaigns awaiting review, at most `limit` starting at queue position
+    /// `start`. Removing a campaign moves the last entry into its slot, so
+    /// the order is not stable across reviews.
+    pub fn get_pending_reviews(env: Env, start: u32, limit: u32) -> Vec<u64> {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        let count = Self::_review_count(&env);
+        let end = start.saturating_add(limit).min(count);
+        let mut result = Vec::new(&env);
+        for i in start..end {
+            if let Some(campaign_id) = env.storage().persistent().get(&DataKey::ReviewSlot(i)) {
+                result.push_back(campaign_id);
+            }
+        }
+        result
+    }
+
+    pub fn get_pending_review_count(env: Env) -> u32 {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        Self::_review_count(&env)
+    }
+
     pub fn get_lifecycle(env: Env, campaign_id: u64) -> Option<CampaignLifecycle> {
         env.storage()
             .instance()
@@ -356,7 +890,9 @@ impl CampaignLifecycleContract {
             .unwrap_or(0)
     }
 
-    fn _validate_transition(from: &LifecycleState, to: &LifecycleState) {
+    /// Built-in edges used when the admin has not configured a rule for a
+    /// given (from, to) pair.
+    fn _default_rule(env: &Env, from: &LifecycleState, to: &LifecycleState) -> Vec<Role> {
         let valid = match from {
             LifecycleState::Draft => matches!(
                 to,
@@ -373,14 +909,216 @@ impl CampaignLifecycleContract {
                     | LifecycleState::Cancelled
                     | LifecycleState::Expired
             ),
-            LifecycleState::Paused => {
-                matches!(to, LifecycleState::Active | LifecycleState::Cancelled)
-            }
+            LifecycleState::Paused => matches!(
+                to,
+                LifecycleState::Active | LifecycleState::Cancelled | LifecycleState::Expired
+            ),
             _ => false,
         };
         if !valid {
+            Vec::new(env)
+        } else if *from == LifecycleState::PendingReview && *to != LifecycleState::Cancelled {
+            // Review outcomes are for reviewers; the advertiser may only
+            // withdraw the submission.
+            vec![env, Role::Admin, Role::Moderator]
+        } else {
+            vec![env, Role::Advertiser, Role::Admin, Role::FraudContract]
+        }
+    }
+
+    fn _effective_rule(env: &Env, from: &LifecycleState, to: &LifecycleState) -> Vec<Role> {
+        env.storage()
+            .instance()
+            .get(&DataKey::TransitionRule(from.clone(), to.clone()))
+            .unwrap_or_else(|| Self::_default_rule(env, from, to))
+    }
+
+    fn _actor_has_role(
+        env: &Env,
+        lifecycle: &CampaignLifecycle,
+        actor: &Address,
+        roles: &Vec<Role>,
+    ) -> bool {
+        roles.iter().any(|role| match role {
+            Role::Advertiser => *actor == lifecycle.advertiser,
+            Role::Admin => {
+                let admin: Address = env.storage().instance().get(&DataKey::Admin).unwrap();
+                *actor == admin
+            }
+            Role::Moderator => env
+                .storage()
+                .instance()
+                .get(&DataKey::Moderator(actor.clone()))
+                .unwrap_or(false),
+            Role::FraudContract => Self::_is_fraud_authority(env, actor),
+        })
+    }
+
+    /// The fraud contract or the anomaly detector, whichever is configured
+    fn _is_fraud_authority(env: &Env, actor: &Address) -> bool {
+        let matches = |key: DataKey| {
+            env.storage()
+                .instance()
+                .get::<DataKey, Address>(&key)
+                .map(|addr| addr == *actor)
+                .unwrap_or(false)
+        };
+        matches(DataKey::FraudContract) || matches(DataKey::AnomalyDetector)
+    }
+
+    fn _authorize_transition(
+        env: &Env,
+        lifecycle: &CampaignLifecycle,
+        actor: &Address,
+        to: &LifecycleState,
+    ) {
+        let roles = Self::_effective_rule(env, &lifecycle.state, to);
+        if roles.is_empty() {
             panic!("invalid state transition");
         }
+        if !Self::_actor_has_role(env, lifecycle, actor, &roles) {
+            panic!("unauthorized");
+        }
+        if Self::_is_first_activation(&lifecycle.state, to)
+            && !Self::_creatives_approved(env, lifecycle.campaign_id)
+        {
+            panic!("creatives not approved");
+        }
+    }
+
+    fn _load_for_review(env: &Env, moderator: &Address, campaign_id: u64) -> CampaignLifecycle {
+        let admin: Address = env.storage().instance().get(&DataKey::Admin).unwrap();
+        let is_moderator: bool = env
+            .storage()
+            .instance()
+            .get(&DataKey::Moderator(moderator.clone()))
+            .unwrap_or(false);
+        if *moderator != admin && !is_moderator {
+            panic!("not a moderator");
+        }
+        let lifecycle: CampaignLifecycle = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Lifecycle(campaign_id))
+            .expect("lifecycle not found");
+        if lifecycle.state != LifecycleState::PendingReview {
+            panic!("campaign not pending review");
+        }
+        lifecycle
+    }
+
+    fn _is_first_activation(from: &LifecycleState, to: &LifecycleState) -> bool {
+        *to == LifecycleState::Active
+            && (*from == LifecycleState::Draft || *from == LifecycleState::PendingReview)
+    }
+
+    /// Asks ad-registry whether every creative of the campaign is approved.
+    /// Without a configured registry there is nothing to gate on.
+    fn _creatives_approved(env: &Env, campaign_id: u64) -> bool {
+        match env
+            .storage()
+            .instance()
+            .get::<DataKey, Address>(&DataKey::AdRegistry)
+        {
+            Some(registry) => env.invoke_contract(
+                &registry,
+                &Symbol::new(env, "campaign_approved"),
+                Vec::from_array(env, [campaign_id.into_val(env)]),
+            ),
+            None => true,
+        }
+    }
+
+    fn _load_fraud_holds(env: &Env, campaign_id: u64) -> Map<Address, u32> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::FraudHolds(campaign_id))
+            .unwrap_or(Map::new(env))
+    }
+
+    fn _save_fraud_holds(env: &Env, campaign_id: u64, holds: &Map<Address, u32>) {
+        let _ttl_key = DataKey::FraudHolds(campaign_id);
+        env.storage().persistent().set(&_ttl_key, holds);
+        env.storage().persistent().extend_ttl(
+            &_ttl_key,
+            PERSISTENT_LIFETIME_THRESHOLD,
+            PERSISTENT_BUMP_AMOUNT,
+        );
+    }
+
+    fn _review_count(env: &Env) -> u32 {
+        env.storage()
+            .persistent()
+            .get(&DataKey::ReviewCount)
+            .unwrap_or(0)
+    }
+
+    fn _set_review_count(env: &Env, count: u32) {
+        let _ttl_key = DataKey::ReviewCount;
+        env.storage().persistent().set(&_ttl_key, &count);
+        env.storage().persistent().extend_ttl(
+            &_ttl_key,
+            PERSISTENT_LIFETIME_THRESHOLD,
+            PERSISTENT_BUMP_AMOUNT,
+        );
+    }
+
+    fn _set_review_slot(env: &Env, position: u32, campaign_id: u64) {
+        let _ttl_key = DataKey::ReviewSlot(position);
+        env.storage().persistent().set(&_ttl_key, &campaign_id);
+        env.storage().persistent().extend_ttl(
+            &_ttl_key,
+            PERSISTENT_LIFETIME_THRESHOLD,
+            PERSISTENT_BUMP_AMOUNT,
+        );
+        let _ttl_key = DataKey::ReviewPosition(campaign_id);
+        env.storage().persistent().set(&_ttl_key, &position);
+        env.storage().persistent().extend_ttl(
+            &_ttl_key,
+            PERSISTENT_LIFETIME_THRESHOLD,
+            PERSISTENT_BUMP_AMOUNT,
+        );
+    }
+
+    fn _enqueue_review(env: &Env, campaign_id: u64) {
+        if env
+            .storage()
+            .persistent()
+            .has(&DataKey::ReviewPosition(campaign_id))
+        {
+            return;
+        }
+        let count = Self::_review_count(env);
+        Self::_set_review_slot(env, count, campaign_id);
+        Self::_set_review_count(env, count + 1);
+    }
+
+    /// Swap-removes the campaign so the queue stays dense.
+    fn _dequeue_review(env: &Env, campaign_id: u64) {
+        let position: u32 = match env
+            .storage()
+            .persistent()
+            .get(&DataKey::ReviewPosition(campaign_id))
+        {
+            Some(p) => p,
+            None => return,
+        };
+        let last = Self::_review_count(env) - 1;
+        if position != last {
+            let moved: u64 = env
+                .storage()
+                .persistent()
+                .get(&DataKey::ReviewSlot(last))
+                .unwrap();
+            Self::_set_review_slot(env, position, moved);
+        }
+        env.storage()
+            .persistent()
+            .remove(&DataKey::ReviewSlot(last));
+        env.storage()
+            .persistent()
+            .remove(&DataKey::ReviewPosition(campaign_id));
+        Self::_set_review_count(env, last);
     }
 
     pub fn propose_admin(env: Env, current_admin: Address, new_admin: Address) {
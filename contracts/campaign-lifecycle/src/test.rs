@@ -1,6 +1,69 @@
 #![cfg(test)]
 use super::*;
-use soroban_sdk::{testutils::Address as _, Address, Env, String};
+use soroban_sdk::{
+    contract, contractimpl,
+    testutils::{Address as _, Ledger},
+    vec, Address, Env, String,
+};
+
+// Stand-in for ad-registry's creative approval check.
+#[contract]
+pub struct MockAdRegistry;
+#[contractimpl]
+impl MockAdRegistry {
+    pub fn set_approved(env: Env, campaign_id: u64, approved: bool) {
+        env.storage().instance().set(&campaign_id, &approved);
+    }
+
+    pub fn campaign_approved(env: Env, campaign_id: u64) -> bool {
+        env.storage().instance().get(&campaign_id).unwrap_or(false)
+    }
+}
+
+// Hook that remembers the last change it was told about.
+#[contract]
+pub struct RecordingHook;
+#[contractimpl]
+impl RecordingHook {
+    pub fn on_lifecycle_change(
+        env: Env,
+        campaign_id: u64,
+        from: LifecycleState,
+        to: LifecycleState,
+    ) {
+        let calls: u32 = env.storage().instance().get(&0u32).unwrap_or(0);
+        env.storage().instance().set(&0u32, &(calls + 1));
+        env.storage()
+            .instance()
+            .set(&1u32, &(campaign_id, from, to));
+    }
+
+    pub fn calls(env: Env) -> u32 {
+        env.storage().instance().get(&0u32).unwrap_or(0)
+    }
+
+    pub fn last(env: Env) -> (u64, LifecycleState, LifecycleState) {
+        env.storage().instance().get(&1u32).unwrap()
+    }
+}
+
+mod failing_hook {
+    use super::*;
+
+    #[contract]
+    pub struct FailingHook;
+    #[contractimpl]
+    impl FailingHook {
+        pub fn on_lifecycle_change(
+            _env: Env,
+            _campaign_id: u64,
+            _from: LifecycleState,
+            _to: LifecycleState,
+        ) {
+            panic!("hook broken");
+        }
+    }
+}
 
 // ─── helpers ─────────────────────────────────────────────────────────────────
 
@@ -361,6 +424,164 @@ fn test_pause_for_fraud_wrong_contract() {
     client.pause_for_fraud(&wrong_contract, &1u64);
 }
 
+#[test]
+fn test_resume_after_fraud() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, admin) = setup(&env);
+    let advertiser = Address::generate(&env);
+    let fraud_contract = Address::generate(&env);
+
+    client.set_fraud_contract(&admin, &fraud_contract);
+    client.register_campaign(&advertiser, &1u64, &10_000u32);
+    client.transition(
+        &advertiser,
+        &1u64,
+        &LifecycleState::PendingReview,
+        &make_reason(&env),
+    );
+    client.transition(&admin, &1u64, &LifecycleState::Active, &make_reason(&env));
+
+    client.pause_for_fraud(&fraud_contract, &1u64);
+    assert!(matches!(
+        client.get_lifecycle(&1u64).unwrap().state,
+        LifecycleState::Paused
+    ));
+    client.resume_after_fraud(&fraud_contract, &1u64);
+    assert!(matches!(
+        client.get_lifecycle(&1u64).unwrap().state,
+        LifecycleState::Active
+    ));
+}
+
+#[test]
+#[should_panic(expected = "campaign not paused")]
+fn test_resume_after_fraud_not_paused() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, admin) = setup(&env);
+    let advertiser = Address::generate(&env);
+    let fraud_contract = Address::generate(&env);
+
+    client.set_fraud_contract(&admin, &fraud_contract);
+    client.register_campaign(&advertiser, &1u64, &10_000u32);
+    client.resume_after_fraud(&fraud_contract, &1u64);
+}
+
+#[test]
+fn test_resume_waits_for_every_fraud_hold() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, admin) = setup(&env);
+    let advertiser = Address::generate(&env);
+    let fraud_contract = Address::generate(&env);
+    let detector = Address::generate(&env);
+
+    client.set_fraud_contract(&admin, &fraud_contract);
+    client.set_anomaly_detector(&admin, &detector);
+    activate_campaign(&env, &client, &admin, &advertiser, 1, 10_000);
+
+    client.pause_for_fraud(&fraud_contract, &1u64);
+    client.pause_for_fraud(&fraud_contract, &1u64);
+    client.pause_for_fraud(&detector, &1u64);
+    assert_eq!(
+        client.get_fraud_holds(&1u64).get(fraud_contract.clone()),
+        Some(2)
+    );
+    assert_eq!(client.get_lifecycle(&1u64).unwrap().pause_count, 1);
+
+    client.resume_after_fraud(&fraud_contract, &1u64);
+    client.resume_after_fraud(&fraud_contract, &1u64);
+    assert!(matches!(
+        client.get_lifecycle(&1u64).unwrap().state,
+        LifecycleState::Paused
+    ));
+
+    client.resume_after_fraud(&detector, &1u64);
+    assert!(matches!(
+        client.get_lifecycle(&1u64).unwrap().state,
+        LifecycleState::Active
+    ));
+    assert!(client.get_fraud_holds(&1u64).is_empty());
+}
+
+#[test]
+#[should_panic(expected = "no fraud hold")]
+fn test_resume_after_fraud_by_other_authority() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, admin) = setup(&env);
+    let advertiser = Address::generate(&env);
+    let fraud_contract = Address::generate(&env);
+    let detector = Address::generate(&env);
+
+    client.set_fraud_contract(&admin, &fraud_contract);
+    client.set_anomaly_detector(&admin, &detector);
+    activate_campaign(&env, &client, &admin, &advertiser, 1, 10_000);
+
+    client.pause_for_fraud(&detector, &1u64);
+    client.resume_after_fraud(&fraud_contract, &1u64);
+}
+
+#[test]
+#[should_panic(expected = "invalid state transition")]
+fn test_pause_for_fraud_on_manually_paused_campaign() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, admin) = setup(&env);
+    let advertiser = Address::generate(&env);
+    let fraud_contract = Address::generate(&env);
+
+    client.set_fraud_contract(&admin, &fraud_contract);
+    activate_campaign(&env, &client, &admin, &advertiser, 1, 10_000);
+    client.transition(
+        &advertiser,
+        &1u64,
+        &LifecycleState::Paused,
+        &make_reason(&env),
+    );
+    client.pause_for_fraud(&fraud_contract, &1u64);
+}
+
+#[test]
+#[should_panic(expected = "campaign under fraud hold")]
+fn test_advertiser_cannot_resume_fraud_held_campaign() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, admin) = setup(&env);
+    let advertiser = Address::generate(&env);
+    let fraud_contract = Address::generate(&env);
+
+    client.set_fraud_contract(&admin, &fraud_contract);
+    activate_campaign(&env, &client, &admin, &advertiser, 1, 10_000);
+    client.pause_for_fraud(&fraud_contract, &1u64);
+    client.transition(
+        &advertiser,
+        &1u64,
+        &LifecycleState::Active,
+        &make_reason(&env),
+    );
+}
+
+#[test]
+fn test_admin_overrides_fraud_hold() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, admin) = setup(&env);
+    let advertiser = Address::generate(&env);
+    let fraud_contract = Address::generate(&env);
+
+    client.set_fraud_contract(&admin, &fraud_contract);
+    activate_campaign(&env, &client, &admin, &advertiser, 1, 10_000);
+    client.pause_for_fraud(&fraud_contract, &1u64);
+    client.transition(&admin, &1u64, &LifecycleState::Active, &make_reason(&env));
+    assert!(matches!(
+        client.get_lifecycle(&1u64).unwrap().state,
+        LifecycleState::Active
+    ));
+    assert!(client.get_fraud_holds(&1u64).is_empty());
+}
+
 // ─── extend_campaign ─────────────────────────────────────────────────────────
 
 /// Helper: register a campaign and activate it (Draft → PendingReview → Active).
@@ -579,3 +800,675 @@ fn test_transition_recorded() {
     assert!(matches!(t.to_state, LifecycleState::PendingReview));
     assert_eq!(t.actor, advertiser);
 }
+
+// ─── configurable transition rules ───────────────────────────────────────────
+
+fn submit_for_review(
+    env: &Env,
+    client: &CampaignLifecycleContractClient,
+    advertiser: &Address,
+    campaign_id: u64,
+    end_ledger: u32,
+) {
+    client.register_campaign(advertiser, &campaign_id, &end_ledger);
+    client.transition(
+        advertiser,
+        &campaign_id,
+        &LifecycleState::PendingReview,
+        &make_reason(env),
+    );
+}
+
+#[test]
+fn test_default_rule() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, _) = setup(&env);
+
+    let roles = client.get_transition_rule(&LifecycleState::Active, &LifecycleState::Paused);
+    assert_eq!(roles.len(), 3);
+    assert!(client
+        .get_transition_rule(&LifecycleState::Draft, &LifecycleState::Active)
+        .is_empty());
+}
+
+#[test]
+fn test_moderator_only_rule() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, admin) = setup(&env);
+    let advertiser = Address::generate(&env);
+    let moderator = Address::generate(&env);
+
+    client.add_moderator(&admin, &moderator);
+    client.set_transition_rule(
+        &admin,
+        &LifecycleState::PendingReview,
+        &LifecycleState::Active,
+        &vec![&env, Role::Moderator],
+    );
+    submit_for_review(&env, &client, &advertiser, 1, 10_000);
+
+    client.transition(
+        &moderator,
+        &1u64,
+        &LifecycleState::Active,
+        &make_reason(&env),
+    );
+    let lc = client.get_lifecycle(&1u64).unwrap();
+    assert!(matches!(lc.state, LifecycleState::Active));
+}
+
+#[test]
+#[should_panic(expected = "unauthorized")]
+fn test_rule_excludes_advertiser() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, admin) = setup(&env);
+    let advertiser = Address::generate(&env);
+
+    client.set_transition_rule(
+        &admin,
+        &LifecycleState::PendingReview,
+        &LifecycleState::Active,
+        &vec![&env, Role::Admin, Role::Moderator],
+    );
+    submit_for_review(&env, &client, &advertiser, 1, 10_000);
+    client.transition(
+        &advertiser,
+        &1u64,
+        &LifecycleState::Active,
+        &make_reason(&env),
+    );
+}
+
+#[test]
+#[should_panic(expected = "invalid state transition")]
+fn test_empty_rule_disables_edge() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, admin) = setup(&env);
+    let advertiser = Address::generate(&env);
+
+    client.set_transition_rule(
+        &admin,
+        &LifecycleState::Draft,
+        &LifecycleState::Cancelled,
+        &Vec::new(&env),
+    );
+    client.register_campaign(&advertiser, &1u64, &10_000u32);
+    client.transition(
+        &advertiser,
+        &1u64,
+        &LifecycleState::Cancelled,
+        &make_reason(&env),
+    );
+}
+
+#[test]
+fn test_clear_rule_restores_default() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, admin) = setup(&env);
+    let advertiser = Address::generate(&env);
+
+    client.set_transition_rule(
+        &admin,
+        &LifecycleState::Draft,
+        &LifecycleState::Cancelled,
+        &Vec::new(&env),
+    );
+    client.clear_transition_rule(&admin, &LifecycleState::Draft, &LifecycleState::Cancelled);
+
+    client.register_campaign(&advertiser, &1u64, &10_000u32);
+    client.transition(
+        &advertiser,
+        &1u64,
+        &LifecycleState::Cancelled,
+        &make_reason(&env),
+    );
+    let lc = client.get_lifecycle(&1u64).unwrap();
+    assert!(matches!(lc.state, LifecycleState::Cancelled));
+}
+
+#[test]
+#[should_panic(expected = "unauthorized")]
+fn test_set_transition_rule_by_stranger() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, _) = setup(&env);
+    let stranger = Address::generate(&env);
+
+    client.set_transition_rule(
+        &stranger,
+        &LifecycleState::Draft,
+        &LifecycleState::Active,
+        &vec![&env, Role::Advertiser],
+    );
+}
+
+#[test]
+fn test_pause_for_fraud_direct() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, admin) = setup(&env);
+    let advertiser = Address::generate(&env);
+    let fraud_contract = Address::generate(&env);
+
+    client.set_fraud_contract(&admin, &fraud_contract);
+    activate_campaign(&env, &client, &admin, &advertiser, 1, 10_000);
+    client.pause_for_fraud(&fraud_contract, &1u64);
+
+    let lc = client.get_lifecycle(&1u64).unwrap();
+    assert!(matches!(lc.state, LifecycleState::Paused));
+    let t = client.get_transition(&1u64, &2u32).unwrap();
+    assert_eq!(t.actor, fraud_contract);
+}
+
+// ─── scheduled transitions ───────────────────────────────────────────────────
+
+#[test]
+fn test_tick_expires_after_end_ledger() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, admin) = setup(&env);
+    let advertiser = Address::generate(&env);
+
+    activate_campaign(&env, &client, &admin, &advertiser, 1, 1_000);
+
+    env.ledger().with_mut(|li| li.sequence_number = 1_000);
+    assert!(matches!(client.tick(&1u64), LifecycleState::Active));
+
+    env.ledger().with_mut(|li| li.sequence_number = 1_001);
+    assert!(matches!(client.tick(&1u64), LifecycleState::Expired));
+    let lc = client.get_lifecycle(&1u64).unwrap();
+    assert!(matches!(lc.state, LifecycleState::Expired));
+    let t = client.get_transition(&1u64, &2u32).unwrap();
+    assert!(matches!(t.to_state, LifecycleState::Expired));
+}
+
+#[test]
+fn test_tick_expires_paused_campaign() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, admin) = setup(&env);
+    let advertiser = Address::generate(&env);
+
+    activate_campaign(&env, &client, &admin, &advertiser, 1, 1_000);
+    client.transition(
+        &advertiser,
+        &1u64,
+        &LifecycleState::Paused,
+        &make_reason(&env),
+    );
+
+    env.ledger().with_mut(|li| li.sequence_number = 2_000);
+    assert!(matches!(client.tick(&1u64), LifecycleState::Expired));
+}
+
+#[test]
+fn test_tick_respects_disabled_expiry_rule() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, admin) = setup(&env);
+    let advertiser = Address::generate(&env);
+
+    activate_campaign(&env, &client, &admin, &advertiser, 1, 1_000);
+    client.set_transition_rule(
+        &admin,
+        &LifecycleState::Active,
+        &LifecycleState::Expired,
+        &Vec::new(&env),
+    );
+
+    env.ledger().with_mut(|li| li.sequence_number = 2_000);
+    assert!(matches!(client.tick(&1u64), LifecycleState::Active));
+
+    client.clear_transition_rule(&admin, &LifecycleState::Active, &LifecycleState::Expired);
+    assert!(matches!(client.tick(&1u64), LifecycleState::Expired));
+}
+
+#[test]
+fn test_scheduled_activation() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, admin) = setup(&env);
+    let advertiser = Address::generate(&env);
+
+    submit_for_review(&env, &client, &advertiser, 1, 10_000);
+    client.schedule_activation(&admin, &1u64, &500u32);
+    assert_eq!(
+        client.get_scheduled_activation(&1u64).unwrap().start_ledger,
+        500
+    );
+
+    env.ledger().with_mut(|li| li.sequence_number = 499);
+    assert!(matches!(client.tick(&1u64), LifecycleState::PendingReview));
+
+    env.ledger().with_mut(|li| li.sequence_number = 500);
+    assert!(matches!(client.tick(&1u64), LifecycleState::Active));
+    let lc = client.get_lifecycle(&1u64).unwrap();
+    assert!(lc.activated_at.is_some());
+    assert!(client.get_scheduled_activation(&1u64).is_none());
+}
+
+#[test]
+#[should_panic(expected = "invalid state transition")]
+fn test_schedule_activation_from_draft_without_rule() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, _) = setup(&env);
+    let advertiser = Address::generate(&env);
+
+    client.register_campaign(&advertiser, &1u64, &10_000u32);
+    // Draft → Active is not an edge by default
+    client.schedule_activation(&advertiser, &1u64, &500u32);
+}
+
+#[test]
+fn test_scheduled_activation_from_draft() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, admin) = setup(&env);
+    let advertiser = Address::generate(&env);
+
+    client.set_transition_rule(
+        &admin,
+        &LifecycleState::Draft,
+        &LifecycleState::Active,
+        &vec![&env, Role::Advertiser],
+    );
+    client.register_campaign(&advertiser, &1u64, &10_000u32);
+    client.schedule_activation(&advertiser, &1u64, &500u32);
+
+    env.ledger().with_mut(|li| li.sequence_number = 499);
+    assert!(matches!(client.tick(&1u64), LifecycleState::Draft));
+
+    env.ledger().with_mut(|li| li.sequence_number = 650);
+    assert!(matches!(client.tick(&1u64), LifecycleState::Active));
+    let t = client.get_transition(&1u64, &0u32).unwrap();
+    assert!(matches!(t.from_state, LifecycleState::Draft));
+    assert_eq!(t.actor, advertiser);
+}
+
+#[test]
+#[should_panic(expected = "unauthorized")]
+fn test_schedule_activation_by_stranger() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, _) = setup(&env);
+    let advertiser = Address::generate(&env);
+
+    submit_for_review(&env, &client, &advertiser, 1, 10_000);
+    client.schedule_activation(&Address::generate(&env), &1u64, &500u32);
+}
+
+#[test]
+#[should_panic(expected = "invalid start ledger")]
+fn test_schedule_activation_in_past() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, _) = setup(&env);
+    let advertiser = Address::generate(&env);
+
+    env.ledger().with_mut(|li| li.sequence_number = 100);
+    client.register_campaign(&advertiser, &1u64, &10_000u32);
+    client.schedule_activation(&advertiser, &1u64, &100u32);
+}
+
+#[test]
+fn test_cancel_clears_scheduled_activation() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, admin) = setup(&env);
+    let advertiser = Address::generate(&env);
+
+    submit_for_review(&env, &client, &advertiser, 1, 10_000);
+    client.schedule_activation(&admin, &1u64, &500u32);
+    client.transition(
+        &advertiser,
+        &1u64,
+        &LifecycleState::Cancelled,
+        &make_reason(&env),
+    );
+
+    assert!(client.get_scheduled_activation(&1u64).is_none());
+    env.ledger().with_mut(|li| li.sequence_number = 500);
+    assert!(matches!(client.tick(&1u64), LifecycleState::Cancelled));
+}
+
+// ─── moderation queue ────────────────────────────────────────────────────────
+
+fn setup_review(
+    env: &Env,
+) -> (
+    CampaignLifecycleContractClient<'_>,
+    Address,
+    Address,
+    MockAdRegistryClient<'_>,
+) {
+    let (client, admin) = setup(env);
+    let moderator = Address::generate(env);
+    let registry_id = env.register(MockAdRegistry, ());
+    let registry = MockAdRegistryClient::new(env, &registry_id);
+
+    client.add_moderator(&admin, &moderator);
+    client.set_ad_registry(&admin, &registry_id);
+    (client, admin, moderator, registry)
+}
+
+#[test]
+fn test_pending_reviews_queue() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, _, moderator, registry) = setup_review(&env);
+    let advertiser = Address::generate(&env);
+
+    submit_for_review(&env, &client, &advertiser, 1, 10_000);
+    submit_for_review(&env, &client, &advertiser, 2, 10_000);
+    assert_eq!(client.get_pending_reviews(&0, &10), vec![&env, 1u64, 2u64]);
+
+    registry.set_approved(&1u64, &true);
+    client.approve_campaign(&moderator, &1u64, &make_reason(&env));
+    assert_eq!(client.get_pending_reviews(&0, &10), vec![&env, 2u64]);
+
+    client.reject_campaign(
+        &moderator,
+        &2u64,
+        &String::from_str(&env, "misleading claims"),
+    );
+    assert_eq!(client.get_pending_review_count(), 0);
+    assert_eq!(client.get_pending_reviews(&0, &10).len(), 0);
+}
+
+#[test]
+fn test_pending_reviews_paged() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, _, moderator, _) = setup_review(&env);
+    let advertiser = Address::generate(&env);
+
+    for campaign_id in 1..=5u64 {
+        submit_for_review(&env, &client, &advertiser, campaign_id, 10_000);
+    }
+    assert_eq!(client.get_pending_review_count(), 5);
+    assert_eq!(client.get_pending_reviews(&0, &2), vec![&env, 1u64, 2u64]);
+    assert_eq!(client.get_pending_reviews(&4, &2), vec![&env, 5u64]);
+    assert_eq!(client.get_pending_reviews(&9, &2).len(), 0);
+
+    // the last entry fills the gap left by a reviewed campaign
+    client.reject_campaign(&moderator, &2u64, &String::from_str(&env, "spam"));
+    assert_eq!(
+        client.get_pending_reviews(&0, &10),
+        vec![&env, 1u64, 5u64, 3u64, 4u64]
+    );
+}
+
+#[test]
+#[should_panic(expected = "unauthorized")]
+fn test_advertiser_cannot_approve_own_campaign() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, _, _, registry) = setup_review(&env);
+    let advertiser = Address::generate(&env);
+
+    submit_for_review(&env, &client, &advertiser, 1, 10_000);
+    registry.set_approved(&1u64, &true);
+    client.transition(
+        &advertiser,
+        &1u64,
+        &LifecycleState::Active,
+        &make_reason(&env),
+    );
+}
+
+#[test]
+fn test_approve_campaign_records_reason() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, _, moderator, registry) = setup_review(&env);
+    let advertiser = Address::generate(&env);
+
+    submit_for_review(&env, &client, &advertiser, 1, 10_000);
+    registry.set_approved(&1u64, &true);
+    client.approve_campaign(&moderator, &1u64, &String::from_str(&env, "creatives ok"));
+
+    let lc = client.get_lifecycle(&1u64).unwrap();
+    assert!(matches!(lc.state, LifecycleState::Active));
+    let t = client.get_transition(&1u64, &1u32).unwrap();
+    assert_eq!(t.actor, moderator);
+    assert_eq!(t.reason, String::from_str(&env, "creatives ok"));
+}
+
+#[test]
+fn test_reject_campaign_records_reason() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, _, moderator, _) = setup_review(&env);
+    let advertiser = Address::generate(&env);
+
+    submit_for_review(&env, &client, &advertiser, 1, 10_000);
+    client.reject_campaign(
+        &moderator,
+        &1u64,
+        &String::from_str(&env, "prohibited product"),
+    );
+
+    let lc = client.get_lifecycle(&1u64).unwrap();
+    assert!(matches!(lc.state, LifecycleState::Rejected));
+    let t = client.get_transition(&1u64, &1u32).unwrap();
+    assert_eq!(t.reason, String::from_str(&env, "prohibited product"));
+}
+
+#[test]
+#[should_panic(expected = "creatives not approved")]
+fn test_approve_with_pending_creatives() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, _, moderator, _) = setup_review(&env);
+    let advertiser = Address::generate(&env);
+
+    submit_for_review(&env, &client, &advertiser, 1, 10_000);
+    client.approve_campaign(&moderator, &1u64, &make_reason(&env));
+}
+
+#[test]
+#[should_panic(expected = "creatives not approved")]
+fn test_transition_to_active_with_pending_creatives() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, admin, _, _) = setup_review(&env);
+    let advertiser = Address::generate(&env);
+
+    submit_for_review(&env, &client, &advertiser, 1, 10_000);
+    client.transition(&admin, &1u64, &LifecycleState::Active, &make_reason(&env));
+}
+
+#[test]
+fn test_scheduled_activation_waits_for_creatives() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, admin, _, registry) = setup_review(&env);
+    let advertiser = Address::generate(&env);
+
+    submit_for_review(&env, &client, &advertiser, 1, 10_000);
+    client.schedule_activation(&admin, &1u64, &500u32);
+    env.ledger().with_mut(|li| li.sequence_number = 500);
+    assert!(matches!(client.tick(&1u64), LifecycleState::PendingReview));
+
+    registry.set_approved(&1u64, &true);
+    assert!(matches!(client.tick(&1u64), LifecycleState::Active));
+}
+
+#[test]
+#[should_panic(expected = "not a moderator")]
+fn test_approve_campaign_by_stranger() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, _, _, registry) = setup_review(&env);
+    let advertiser = Address::generate(&env);
+
+    submit_for_review(&env, &client, &advertiser, 1, 10_000);
+    registry.set_approved(&1u64, &true);
+    client.approve_campaign(&Address::generate(&env), &1u64, &make_reason(&env));
+}
+
+#[test]
+#[should_panic(expected = "campaign not pending review")]
+fn test_approve_campaign_not_in_review() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, _, moderator, _) = setup_review(&env);
+    let advertiser = Address::generate(&env);
+
+    client.register_campaign(&advertiser, &1u64, &10_000u32);
+    client.approve_campaign(&moderator, &1u64, &make_reason(&env));
+}
+
+#[test]
+#[should_panic(expected = "reason required")]
+fn test_reject_campaign_requires_reason() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, _, moderator, _) = setup_review(&env);
+    let advertiser = Address::generate(&env);
+
+    submit_for_review(&env, &client, &advertiser, 1, 10_000);
+    client.reject_campaign(&moderator, &1u64, &String::from_str(&env, ""));
+}
+
+// ─── lifecycle hooks ─────────────────────────────────────────────────────────
+
+#[test]
+fn test_hook_notified_on_transition() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, admin) = setup(&env);
+    let advertiser = Address::generate(&env);
+    let hook_id = env.register(RecordingHook, ());
+    let hook = RecordingHookClient::new(&env, &hook_id);
+
+    client.register_hook(&admin, &hook_id);
+    submit_for_review(&env, &client, &advertiser, 7, 10_000);
+
+    assert_eq!(hook.calls(), 1);
+    let (campaign_id, from, to) = hook.last();
+    assert_eq!(campaign_id, 7);
+    assert!(matches!(from, LifecycleState::Draft));
+    assert!(matches!(to, LifecycleState::PendingReview));
+}
+
+#[test]
+fn test_failing_hook_does_not_block_fraud_pause() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, admin) = setup(&env);
+    let advertiser = Address::generate(&env);
+    let fraud_contract = Address::generate(&env);
+    let failing_id = env.register(failing_hook::FailingHook, ());
+    let hook_id = env.register(RecordingHook, ());
+    let hook = RecordingHookClient::new(&env, &hook_id);
+
+    client.set_fraud_contract(&admin, &fraud_contract);
+    activate_campaign(&env, &client, &admin, &advertiser, 1, 10_000);
+    client.register_hook(&admin, &failing_id);
+    client.register_hook(&admin, &hook_id);
+
+    client.pause_for_fraud(&fraud_contract, &1u64);
+
+    let lc = client.get_lifecycle(&1u64).unwrap();
+    assert!(matches!(lc.state, LifecycleState::Paused));
+    // the hook after the broken one still ran
+    assert_eq!(hook.calls(), 1);
+    assert!(matches!(hook.last().2, LifecycleState::Paused));
+}
+
+#[test]
+fn test_hook_notified_on_tick() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, admin) = setup(&env);
+    let advertiser = Address::generate(&env);
+    let hook_id = env.register(RecordingHook, ());
+    let hook = RecordingHookClient::new(&env, &hook_id);
+
+    activate_campaign(&env, &client, &admin, &advertiser, 1, 1_000);
+    client.register_hook(&admin, &hook_id);
+    env.ledger().with_mut(|li| li.sequence_number = 1_001);
+    client.tick(&1u64);
+
+    let (_, from, to) = hook.last();
+    assert!(matches!(from, LifecycleState::Active));
+    assert!(matches!(to, LifecycleState::Expired));
+}
+
+#[test]
+fn test_unregister_hook() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, admin) = setup(&env);
+    let advertiser = Address::generate(&env);
+    let hook_id = env.register(RecordingHook, ());
+    let hook = RecordingHookClient::new(&env, &hook_id);
+
+    client.register_hook(&admin, &hook_id);
+    assert_eq!(client.get_hooks().len(), 1);
+    client.unregister_hook(&admin, &hook_id);
+    assert_eq!(client.get_hooks().len(), 0);
+
+    submit_for_review(&env, &client, &advertiser, 1, 10_000);
+    assert_eq!(hook.calls(), 0);
+}
+
+#[test]
+#[should_panic(expected = "hook already registered")]
+fn test_register_hook_twice() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, admin) = setup(&env);
+    let hook = Address::generate(&env);
+
+    client.register_hook(&admin, &hook);
+    client.register_hook(&admin, &hook);
+}
+
+#[test]
+#[should_panic(expected = "unauthorized")]
+fn test_register_hook_by_stranger() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, _) = setup(&env);
+    let stranger = Address::generate(&env);
+
+    client.register_hook(&stranger, &Address::generate(&env));
+}
+
+#[test]
+fn test_anomaly_detector_pauses_and_resumes() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, admin) = setup(&env);
+    let advertiser = Address::generate(&env);
+    let detector = Address::generate(&env);
+
+    client.set_anomaly_detector(&admin, &detector);
+    client.register_campaign(&advertiser, &1u64, &10_000u32);
+    client.transition(
+        &advertiser,
+        &1u64,
+        &LifecycleState::PendingReview,
+        &make_reason(&env),
+    );
+    client.transition(&admin, &1u64, &LifecycleState::Active, &make_reason(&env));
+
+    client.pause_for_fraud(&detector, &1u64);
+    assert!(matches!(
+        client.get_lifecycle(&1u64).unwrap().state,
+        LifecycleState::Paused
+    ));
+    client.resume_after_fraud(&detector, &1u64);
+    assert!(matches!(
+        client.get_lifecycle(&1u64).unwrap().state,
+        LifecycleState::Active
+    ));
+}
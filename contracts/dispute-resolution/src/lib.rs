@@ -4,7 +4,7 @@
 #![no_std]
 use soroban_sdk::{
     contract, contractimpl, contracttype, symbol_short, token, Address, Env, IntoVal, String,
-    Symbol, Vec as SdkVec,
+    Symbol, Vec,
 };
 
 #[contracttype]
@@ -27,6 +27,7 @@ pub enum DisputeOutcome {
     Respondent,
     Split,
     NoAction,
+    Frivolous,
 }
 
 #[contracttype]
@@ -48,6 +49,45 @@ pub struct Dispute {
     pub arbitrator: Option<Address>,
 }
 
+#[contracttype]
+#[derive(Clone)]
+pub struct FeeSchedule {
+    pub base_fee: i128,
+    pub claim_bps: u32, // added on top of base_fee, scaled by claim_amount
+    pub max_fee: i128,  // 0 = uncapped
+    pub frivolous_respondent_bps: u32, // share of a forfeited fee paid to the respondent
+}
+
+#[contracttype]
+#[derive(Clone)]
+pub struct ArbitrationConfig {
+    pub stake_token: Address,
+    pub min_stake: i128,
+    pub panel_size: u32,
+    pub ruling_period: u64, // seconds a panel has to rule
+    pub slash_bps: u32,
+    pub ruling_reward: i128, // paid from the reward pool per majority ruling
+}
+
+#[contracttype]
+#[derive(Clone)]
+pub struct ArbitratorInfo {
+    pub stake: i128,
+    pub active_panels: u32,
+    pub rulings: u32,
+    pub majority_rulings: u32,
+    pub slashed_total: i128,
+    pub pending_rewards: i128,
+}
+
+#[contracttype]
+#[derive(Clone)]
+pub struct Panel {
+    pub members: Vec<Address>,
+    pub deadline: u64,
+    pub votes_cast: u32,
+}
+
 #[contracttype]
 #[derive(Clone)]
 pub enum DataKey {
@@ -61,6 +101,14 @@ pub enum DataKey {
     Dispute(u64),
     DisputeEscrow(u64),
     ArbitratorApproved(Address),
+    ArbitrationConfig,
+    RewardPool,
+    Arbitrator(Address),
+    DisputePanel(u64),
+    PanelVote(u64, Address),
+    FeeSchedule,
+    FeePool,
+    DisputeFee(u64),
 }
 
 const INSTANCE_LIFETIME_THRESHOLD: u32 = 17_280;
@@ -127,11 +175,7 @@ impl DisputeResolutionContract {
         }
 
         // Collect filing fee
-        let fee: i128 = env
-            .storage()
-            .instance()
-            .get(&DataKey::FilingFee)
-            .unwrap_or(0);
+        let fee = Self::compute_filing_fee(&env, claim_amount);
         let token_addr: Address = env
             .storage()
             .instance()
@@ -176,6 +220,15 @@ impl DisputeResolutionContract {
             PERSISTENT_LIFETIME_THRESHOLD,
             PERSISTENT_BUMP_AMOUNT,
         );
+        // Kept beside the dispute so records written before fee schedules
+        // existed still decode.
+        let _ttl_key = DataKey::DisputeFee(dispute_id);
+        env.storage().persistent().set(&_ttl_key, &fee);
+        env.storage().persistent().extend_ttl(
+            &_ttl_key,
+            PERSISTENT_LIFETIME_THRESHOLD,
+            PERSISTENT_BUMP_AMOUNT,
+        );
         env.storage()
             .instance()
             .set(&DataKey::DisputeCounter, &dispute_id);
@@ -213,6 +266,11 @@ impl DisputeResolutionContract {
             .persistent()
             .get(&DataKey::Dispute(dispute_id))
             .expect("dispute not found");
+        // Manual assignment is the fallback for deployments without staked
+        // panels; once arbitration is configured every dispute goes to a panel.
+        if env.storage().instance().has(&DataKey::ArbitrationConfig) {
+            panic!("staked panels enabled");
+        }
 
         dispute.arbitrator = Some(arbitrator);
         dispute.status = DisputeStatus::UnderReview;
@@ -237,7 +295,7 @@ impl DisputeResolutionContract {
             .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
         arbitrator.require_auth();
 
-        let mut dispute: Dispute = env
+        let dispute: Dispute = env
             .storage()
             .persistent()
             .get(&DataKey::Dispute(dispute_id))
@@ -255,87 +313,221 @@ impl DisputeResolutionContract {
             panic!("already resolved");
         }
 
-        let (claimant_amount, respondent_amount) = match outcome {
-            DisputeOutcome::Claimant => (dispute.claim_amount, 0),
-            DisputeOutcome::Respondent => (0, dispute.claim_amount),
-            DisputeOutcome::Split => {
-                let claimant_part = dispute.claim_amount / 2;
-                (claimant_part, dispute.claim_amount - claimant_part)
-            }
-            DisputeOutcome::NoAction | DisputeOutcome::Pending => (0, 0),
-        };
+        Self::settle(&env, dispute_id, dispute, outcome, notes);
+    }
 
-        let used_escrow = if claimant_amount > 0 || respondent_amount > 0 {
-            Self::try_settle_linked_escrow(
-                &env,
-                dispute_id,
-                &dispute,
-                claimant_amount,
-                respondent_amount,
-            )
-        } else {
-            false
-        };
+    // ─── Staked arbitrator panels ────────────────────────────────────────────
 
-        if !used_escrow {
-            let token_client = token::Client::new(&env, &dispute.token);
-            if claimant_amount > 0 {
-                token_client.transfer(
-                    &env.current_contract_address(),
-                    &dispute.claimant,
-                    &claimant_amount,
-                );
-            }
-            if respondent_amount > 0 {
-                token_client.transfer(
-                    &env.current_contract_address(),
-                    &dispute.respondent,
-                    &respondent_amount,
-                );
+    pub fn set_arbitration_config(env: Env, admin: Address, config: ArbitrationConfig) {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        admin.require_auth();
+        let stored_admin: Address = env.storage().instance().get(&DataKey::Admin).unwrap();
+        if admin != stored_admin {
+            panic!("unauthorized");
+        }
+        if config.min_stake <= 0 || config.panel_size == 0 || config.ruling_reward < 0 {
+            panic!("invalid config");
+        }
+        if config.slash_bps > 10_000 {
+            panic!("invalid slash bps");
+        }
+        env.storage()
+            .instance()
+            .set(&DataKey::ArbitrationConfig, &config);
+    }
+
+    pub fn stake_arbitrator(env: Env, arbitrator: Address, amount: i128) {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        arbitrator.require_auth();
+        if amount <= 0 {
+            panic!("invalid amount");
+        }
+        let config = Self::load_config(&env);
+
+        token::Client::new(&env, &config.stake_token).transfer(
+            &arbitrator,
+            &env.current_contract_address(),
+            &amount,
+        );
+
+        let mut info = Self::load_arbitrator(&env, &arbitrator);
+        info.stake += amount;
+        Self::save_arbitrator(&env, &arbitrator, &info);
+
+        if info.stake >= config.min_stake {
+            let mut pool = Self::load_pool(&env);
+            if !pool.contains(&arbitrator) {
+                pool.push_back(arbitrator.clone());
+                Self::save_pool(&env, &pool);
             }
         }
 
-        let fee: i128 = env
+        env.events().publish(
+            (symbol_short!("arbiter"), symbol_short!("staked")),
+            (arbitrator, amount),
+        );
+    }
+
+    pub fn unstake_arbitrator(env: Env, arbitrator: Address, amount: i128) {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        arbitrator.require_auth();
+        if amount <= 0 {
+            panic!("invalid amount");
+        }
+        let config = Self::load_config(&env);
+        let mut info = Self::load_arbitrator(&env, &arbitrator);
+        if info.active_panels > 0 {
+            panic!("arbitrator on active panel");
+        }
+        if amount > info.stake {
+            panic!("insufficient stake");
+        }
+
+        info.stake -= amount;
+        Self::save_arbitrator(&env, &arbitrator, &info);
+        if info.stake < config.min_stake {
+            Self::remove_from_pool(&env, &arbitrator);
+        }
+
+        token::Client::new(&env, &config.stake_token).transfer(
+            &env.current_contract_address(),
+            &arbitrator,
+            &amount,
+        );
+
+        env.events().publish(
+            (symbol_short!("arbiter"), symbol_short!("unstaked")),
+            (arbitrator, amount),
+        );
+    }
+
+    pub fn fund_reward_pool(env: Env, funder: Address, amount: i128) {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        funder.require_auth();
+        if amount <= 0 {
+            panic!("invalid amount");
+        }
+        let config = Self::load_config(&env);
+        token::Client::new(&env, &config.stake_token).transfer(
+            &funder,
+            &env.current_contract_address(),
+            &amount,
+        );
+        let pool: i128 = env
             .storage()
             .instance()
-            .get(&DataKey::FilingFee)
+            .get(&DataKey::RewardPool)
             .unwrap_or(0);
-        if fee > 0 {
-            let token_client = token::Client::new(&env, &dispute.token);
-            match outcome {
-                DisputeOutcome::Claimant => {
-                    token_client.transfer(&env.current_contract_address(), &dispute.claimant, &fee);
-                }
-                DisputeOutcome::Respondent => {
-                    token_client.transfer(&env.current_contract_address(), &dispute.respondent, &fee);
-                }
-                DisputeOutcome::Split => {
-                    let claimant_fee = fee / 2;
-                    let respondent_fee = fee - claimant_fee;
-                    if claimant_fee > 0 {
-                        token_client.transfer(
-                            &env.current_contract_address(),
-                            &dispute.claimant,
-                            &claimant_fee,
-                        );
-                    }
-                    if respondent_fee > 0 {
-                        token_client.transfer(
-                            &env.current_contract_address(),
-                            &dispute.respondent,
-                            &respondent_fee,
-                        );
-                    }
+        env.storage()
+            .instance()
+            .set(&DataKey::RewardPool, &(pool + amount));
+    }
+
+    pub fn claim_arbitrator_rewards(env: Env, arbitrator: Address) -> i128 {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        arbitrator.require_auth();
+        let config = Self::load_config(&env);
+        let mut info = Self::load_arbitrator(&env, &arbitrator);
+        let amount = info.pending_rewards;
+        if amount <= 0 {
+            panic!("no rewards");
+        }
+        info.pending_rewards = 0;
+        Self::save_arbitrator(&env, &arbitrator, &info);
+
+        token::Client::new(&env, &config.stake_token).transfer(
+            &env.current_contract_address(),
+            &arbitrator,
+            &amount,
+        );
+        amount
+    }
+
+    /// Draws a panel for a filed dispute from the staked pool. Each seat is
+    /// filled by a stake-weighted draw over the remaining candidates, so the
+    /// same arbitrator can't be picked twice and the parties are never eligible.
+    pub fn select_panel(env: Env, dispute_id: u64) -> Vec<Address> {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        let config = Self::load_config(&env);
+        let mut dispute: Dispute = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Dispute(dispute_id))
+            .expect("dispute not found");
+        if dispute.status != DisputeStatus::Filed || dispute.arbitrator.is_some() {
+            panic!("dispute not open for panel");
+        }
+
+        let mut candidates: Vec<Address> = Vec::new(&env);
+        let mut weights: Vec<i128> = Vec::new(&env);
+        for addr in Self::load_pool(&env).iter() {
+            if addr == dispute.claimant || addr == dispute.respondent {
+                continue;
+            }
+            let info = Self::load_arbitrator(&env, &addr);
+            if info.stake >= config.min_stake {
+                candidates.push_back(addr);
+                weights.push_back(info.stake);
+            }
+        }
+        if candidates.len() < config.panel_size {
+            panic!("insufficient arbitrators");
+        }
+
+        let mut members: Vec<Address> = Vec::new(&env);
+        for _ in 0..config.panel_size {
+            let mut total: i128 = 0;
+            for w in weights.iter() {
+                total += w;
+            }
+            // Stakes are i128, so draw over the full u128 range rather than
+            // assuming the total fits in a u64.
+            let draw = ((env.prng().gen::<u64>() as u128) << 64) | env.prng().gen::<u64>() as u128;
+            let mut pick = (draw % total as u128) as i128;
+            let mut idx: u32 = 0;
+            for (i, w) in weights.iter().enumerate() {
+                if pick < w {
+                    idx = i as u32;
+                    break;
                 }
-                DisputeOutcome::NoAction | DisputeOutcome::Pending => {}
+                pick -= w;
             }
+            let chosen = candidates.get(idx).unwrap();
+            candidates.remove(idx);
+            weights.remove(idx);
+
+            let mut info = Self::load_arbitrator(&env, &chosen);
+            info.active_panels += 1;
+            Self::save_arbitrator(&env, &chosen, &info);
+            members.push_back(chosen);
         }
 
-        dispute.outcome = outcome;
-        dispute.resolution_notes = notes;
-        dispute.status = DisputeStatus::Resolved;
-        dispute.resolved_at = Some(env.ledger().timestamp());
+        let panel = Panel {
+            members: members.clone(),
+            deadline: env.ledger().timestamp() + config.ruling_period,
+            votes_cast: 0,
+        };
+        let _ttl_key = DataKey::DisputePanel(dispute_id);
+        env.storage().persistent().set(&_ttl_key, &panel);
+        env.storage().persistent().extend_ttl(
+            &_ttl_key,
+            PERSISTENT_LIFETIME_THRESHOLD,
+            PERSISTENT_BUMP_AMOUNT,
+        );
 
+        dispute.status = DisputeStatus::Deliberating;
         let _ttl_key = DataKey::Dispute(dispute_id);
         env.storage().persistent().set(&_ttl_key, &dispute);
         env.storage().persistent().extend_ttl(
@@ -345,9 +537,202 @@ impl DisputeResolutionContract {
         );
 
         env.events().publish(
-            (symbol_short!("dispute"), symbol_short!("resolved")),
+            (symbol_short!("dispute"), symbol_short!("panel")),
+            (dispute_id, members.clone()),
+        );
+
+        members
+    }
+
+    pub fn cast_ruling(env: Env, arbitrator: Address, dispute_id: u64, outcome: DisputeOutcome) {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        arbitrator.require_auth();
+        if outcome == DisputeOutcome::Pending {
+            panic!("invalid outcome");
+        }
+
+        let mut panel: Panel = env
+            .storage()
+            .persistent()
+            .get(&DataKey::DisputePanel(dispute_id))
+            .expect("panel not found");
+        if !panel.members.contains(&arbitrator) {
+            panic!("not panel member");
+        }
+        if env.ledger().timestamp() > panel.deadline {
+            panic!("ruling period ended");
+        }
+        let vote_key = DataKey::PanelVote(dispute_id, arbitrator.clone());
+        if env.storage().persistent().has(&vote_key) {
+            panic!("already ruled");
+        }
+
+        env.storage().persistent().set(&vote_key, &outcome);
+        env.storage().persistent().extend_ttl(
+            &vote_key,
+            PERSISTENT_LIFETIME_THRESHOLD,
+            PERSISTENT_BUMP_AMOUNT,
+        );
+
+        panel.votes_cast += 1;
+        let _ttl_key = DataKey::DisputePanel(dispute_id);
+        env.storage().persistent().set(&_ttl_key, &panel);
+        env.storage().persistent().extend_ttl(
+            &_ttl_key,
+            PERSISTENT_LIFETIME_THRESHOLD,
+            PERSISTENT_BUMP_AMOUNT,
+        );
+
+        env.events().publish(
+            (symbol_short!("dispute"), symbol_short!("ruling")),
+            (dispute_id, arbitrator),
+        );
+    }
+
+    /// Closes a panel once every member has ruled or the ruling period has
+    /// passed. The plurality outcome settles the dispute (a tie settles as
+    /// `NoAction`); members who ruled against it or never ruled are slashed,
+    /// and the slashed stake is shared among the majority on top of the
+    /// per-ruling reward. If nobody ruled, the dispute returns to `Filed`.
+    pub fn finalize_panel(env: Env, dispute_id: u64) -> DisputeOutcome {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        let config = Self::load_config(&env);
+        let panel: Panel = env
+            .storage()
+            .persistent()
+            .get(&DataKey::DisputePanel(dispute_id))
+            .expect("panel not found");
+        let mut dispute: Dispute = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Dispute(dispute_id))
+            .expect("dispute not found");
+        if dispute.status != DisputeStatus::Deliberating {
+            panic!("panel already finalized");
+        }
+        if panel.votes_cast < panel.members.len() && env.ledger().timestamp() <= panel.deadline {
+            panic!("ruling period active");
+        }
+
+        // Tally: Claimant, Respondent, Split, Frivolous, NoAction
+        let mut counts = [0u32; 5];
+        for member in panel.members.iter() {
+            if let Some(vote) = env
+                .storage()
+                .persistent()
+                .get::<DataKey, DisputeOutcome>(&DataKey::PanelVote(dispute_id, member))
+            {
+                counts[Self::outcome_index(&vote)] += 1;
+            }
+        }
+        let mut best = 0usize;
+        let mut tied = false;
+        for (i, &count) in counts.iter().enumerate().skip(1) {
+            if count > counts[best] {
+                best = i;
+                tied = false;
+            } else if count == counts[best] {
+                tied = true;
+            }
+        }
+        let majority: Option<DisputeOutcome> = if panel.votes_cast == 0 || tied {
+            None
+        } else {
+            Some(Self::outcome_from_index(best))
+        };
+
+        // Slash dissenters and absentees, collecting the slashed stake.
+        let mut slashed_pot: i128 = 0;
+        let mut winners: Vec<Address> = Vec::new(&env);
+        for member in panel.members.iter() {
+            let vote: Option<DisputeOutcome> = env
+                .storage()
+                .persistent()
+                .get(&DataKey::PanelVote(dispute_id, member.clone()));
+            let mut info = Self::load_arbitrator(&env, &member);
+            info.active_panels = info.active_panels.saturating_sub(1);
+
+            let penalize = match (&vote, &majority) {
+                (None, _) => true,
+                (Some(v), Some(m)) => v != m,
+                (Some(_), None) => false,
+            };
+            if vote.is_some() {
+                info.rulings += 1;
+            }
+            if penalize {
+                let penalty = info.stake * config.slash_bps as i128 / 10_000;
+                info.stake -= penalty;
+                info.slashed_total += penalty;
+                slashed_pot += penalty;
+                if info.stake < config.min_stake {
+                    Self::remove_from_pool(&env, &member);
+                }
+                env.events().publish(
+                    (symbol_short!("arbiter"), symbol_short!("slashed")),
+                    (member.clone(), dispute_id, penalty),
+                );
+            } else if majority.is_some() {
+                info.majority_rulings += 1;
+                winners.push_back(member.clone());
+            }
+            Self::save_arbitrator(&env, &member, &info);
+        }
+
+        let mut reward_pool: i128 = env
+            .storage()
+            .instance()
+            .get(&DataKey::RewardPool)
+            .unwrap_or(0);
+        if winners.is_empty() {
+            reward_pool += slashed_pot;
+        } else {
+            let share = slashed_pot / winners.len() as i128;
+            reward_pool += slashed_pot - share * winners.len() as i128;
+            for winner in winners.iter() {
+                let fee = config.ruling_reward.min(reward_pool);
+                reward_pool -= fee;
+                let mut info = Self::load_arbitrator(&env, &winner);
+                info.pending_rewards += share + fee;
+                Self::save_arbitrator(&env, &winner, &info);
+                env.events().publish(
+                    (symbol_short!("arbiter"), symbol_short!("rewarded")),
+                    (winner, dispute_id, share + fee),
+                );
+            }
+        }
+        env.storage()
+            .instance()
+            .set(&DataKey::RewardPool, &reward_pool);
+
+        if panel.votes_cast == 0 {
+            env.storage()
+                .persistent()
+                .remove(&DataKey::DisputePanel(dispute_id));
+            dispute.status = DisputeStatus::Filed;
+            let _ttl_key = DataKey::Dispute(dispute_id);
+            env.storage().persistent().set(&_ttl_key, &dispute);
+            env.storage().persistent().extend_ttl(
+                &_ttl_key,
+                PERSISTENT_LIFETIME_THRESHOLD,
+                PERSISTENT_BUMP_AMOUNT,
+            );
+            return DisputeOutcome::Pending;
+        }
+
+        let outcome = majority.unwrap_or(DisputeOutcome::NoAction);
+        Self::settle(
+            &env,
             dispute_id,
+            dispute,
+            outcome.clone(),
+            String::from_str(&env, "panel ruling"),
         );
+        outcome
     }
 
     pub fn get_dispute(env: Env, dispute_id: u64) -> Option<Dispute> {
@@ -359,6 +744,13 @@ impl DisputeResolutionContract {
             .get(&DataKey::Dispute(dispute_id))
     }
 
+    pub fn get_dispute_fee(env: Env, dispute_id: u64) -> i128 {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        Self::load_dispute_fee(&env, dispute_id)
+    }
+
     pub fn get_dispute_count(env: Env) -> u64 {
         env.storage()
             .instance()
@@ -369,6 +761,139 @@ impl DisputeResolutionContract {
             .unwrap_or(0)
     }
 
+    pub fn get_arbitrator(env: Env, arbitrator: Address) -> Option<ArbitratorInfo> {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        env.storage()
+            .persistent()
+            .get(&DataKey::Arbitrator(arbitrator))
+    }
+
+    pub fn get_arbitrator_pool(env: Env) -> Vec<Address> {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        Self::load_pool(&env)
+    }
+
+    pub fn get_panel(env: Env, dispute_id: u64) -> Option<Panel> {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        env.storage()
+            .persistent()
+            .get(&DataKey::DisputePanel(dispute_id))
+    }
+
+    pub fn get_panel_vote(
+        env: Env,
+        dispute_id: u64,
+        arbitrator: Address,
+    ) -> Option<DisputeOutcome> {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        env.storage()
+            .persistent()
+            .get(&DataKey::PanelVote(dispute_id, arbitrator))
+    }
+
+    pub fn get_arbitration_config(env: Env) -> Option<ArbitrationConfig> {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        env.storage().instance().get(&DataKey::ArbitrationConfig)
+    }
+
+    pub fn get_reward_pool(env: Env) -> i128 {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        env.storage()
+            .instance()
+            .get(&DataKey::RewardPool)
+            .unwrap_or(0)
+    }
+
+    pub fn set_fee_schedule(env: Env, admin: Address, schedule: FeeSchedule) {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        admin.require_auth();
+        let stored_admin: Address = env.storage().instance().get(&DataKey::Admin).unwrap();
+        if admin != stored_admin {
+            panic!("unauthorized");
+        }
+        if schedule.base_fee < 0 || schedule.max_fee < 0 {
+            panic!("invalid fee");
+        }
+        if schedule.claim_bps > 10_000 || schedule.frivolous_respondent_bps > 10_000 {
+            panic!("invalid bps");
+        }
+        env.storage()
+            .instance()
+            .set(&DataKey::FeeSchedule, &schedule);
+    }
+
+    pub fn withdraw_fees(env: Env, admin: Address, to: Address, amount: i128) {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        admin.require_auth();
+        let stored_admin: Address = env.storage().instance().get(&DataKey::Admin).unwrap();
+        if admin != stored_admin {
+            panic!("unauthorized");
+        }
+        if amount <= 0 {
+            panic!("invalid amount");
+        }
+        let pool: i128 = env.storage().instance().get(&DataKey::FeePool).unwrap_or(0);
+        if amount > pool {
+            panic!("insufficient fee pool");
+        }
+        env.storage()
+            .instance()
+            .set(&DataKey::FeePool, &(pool - amount));
+
+        let token_addr: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::TokenAddress)
+            .unwrap();
+        token::Client::new(&env, &token_addr).transfer(
+            &env.current_contract_address(),
+            &to,
+            &amount,
+        );
+
+        env.events().publish(
+            (symbol_short!("fees"), symbol_short!("withdrawn")),
+            (to, amount),
+        );
+    }
+
+    pub fn get_fee_schedule(env: Env) -> Option<FeeSchedule> {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        env.storage().instance().get(&DataKey::FeeSchedule)
+    }
+
+    pub fn quote_filing_fee(env: Env, claim_amount: i128) -> i128 {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        Self::compute_filing_fee(&env, claim_amount)
+    }
+
+    pub fn get_fee_pool(env: Env) -> i128 {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        env.storage().instance().get(&DataKey::FeePool).unwrap_or(0)
+    }
+
     pub fn set_escrow_contract(env: Env, admin: Address, escrow_contract: Address) {
         env.storage()
             .instance()
@@ -392,7 +917,11 @@ impl DisputeResolutionContract {
         if admin != stored_admin {
             panic!("unauthorized");
         }
-        if !env.storage().persistent().has(&DataKey::Dispute(dispute_id)) {
+        if !env
+            .storage()
+            .persistent()
+            .has(&DataKey::Dispute(dispute_id))
+        {
             panic!("dispute not found");
         }
         let _ttl_key = DataKey::DisputeEscrow(dispute_id);
@@ -418,6 +947,252 @@ impl DisputeResolutionContract {
         pulsar_common_admin::accept_admin(&env, &DataKey::Admin, &DataKey::PendingAdmin, new_admin);
     }
 
+    fn load_config(env: &Env) -> ArbitrationConfig {
+        env.storage()
+            .instance()
+            .get(&DataKey::ArbitrationConfig)
+            .expect("arbitration not configured")
+    }
+
+    fn load_arbitrator(env: &Env, arbitrator: &Address) -> ArbitratorInfo {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Arbitrator(arbitrator.clone()))
+            .unwrap_or(ArbitratorInfo {
+                stake: 0,
+                active_panels: 0,
+                rulings: 0,
+                majority_rulings: 0,
+                slashed_total: 0,
+                pending_rewards: 0,
+            })
+    }
+
+    fn save_arbitrator(env: &Env, arbitrator: &Address, info: &ArbitratorInfo) {
+        let _ttl_key = DataKey::Arbitrator(arbitrator.clone());
+        env.storage().persistent().set(&_ttl_key, info);
+        env.storage().persistent().extend_ttl(
+            &_ttl_key,
+            PERSISTENT_LIFETIME_THRESHOLD,
+            PERSISTENT_BUMP_AMOUNT,
+        );
+    }
+
+    fn load_pool(env: &Env) -> Vec<Address> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::ArbitratorPool)
+            .unwrap_or(Vec::new(env))
+    }
+
+    fn save_pool(env: &Env, pool: &Vec<Address>) {
+        let _ttl_key = DataKey::ArbitratorPool;
+        env.storage().persistent().set(&_ttl_key, pool);
+        env.storage().persistent().extend_ttl(
+            &_ttl_key,
+            PERSISTENT_LIFETIME_THRESHOLD,
+            PERSISTENT_BUMP_AMOUNT,
+        );
+    }
+
+    fn remove_from_pool(env: &Env, arbitrator: &Address) {
+        let mut pool = Self::load_pool(env);
+        if let Some(idx) = pool.first_index_of(arbitrator) {
+            pool.remove(idx);
+            Self::save_pool(env, &pool);
+        }
+    }
+
+    fn outcome_index(outcome: &DisputeOutcome) -> usize {
+        match outcome {
+            DisputeOutcome::Claimant => 0,
+            DisputeOutcome::Respondent => 1,
+            DisputeOutcome::Split => 2,
+            DisputeOutcome::Frivolous => 3,
+            DisputeOutcome::NoAction | DisputeOutcome::Pending => 4,
+        }
+    }
+
+    fn outcome_from_index(idx: usize) -> DisputeOutcome {
+        match idx {
+            0 => DisputeOutcome::Claimant,
+            1 => DisputeOutcome::Respondent,
+            2 => DisputeOutcome::Split,
+            3 => DisputeOutcome::Frivolous,
+            _ => DisputeOutcome::NoAction,
+        }
+    }
+
+    fn settle(
+        env: &Env,
+        dispute_id: u64,
+        mut dispute: Dispute,
+        outcome: DisputeOutcome,
+        notes: String,
+    ) {
+        let (claimant_amount, respondent_amount) = match outcome {
+            DisputeOutcome::Claimant => (dispute.claim_amount, 0),
+            DisputeOutcome::Respondent => (0, dispute.claim_amount),
+            DisputeOutcome::Split => {
+                let claimant_part = dispute.claim_amount / 2;
+                (claimant_part, dispute.claim_amount - claimant_part)
+            }
+            // No decision (including a tied panel) and a frivolous claim both
+            // leave the claim with the claimant: the locked amount goes back
+            // and only the filing fee is settled.
+            DisputeOutcome::NoAction | DisputeOutcome::Pending => (0, 0),
+            DisputeOutcome::Frivolous => (0, 0),
+        };
+
+        let used_escrow = if claimant_amount > 0 || respondent_amount > 0 {
+            Self::try_settle_linked_escrow(
+                env,
+                dispute_id,
+                &dispute,
+                claimant_amount,
+                respondent_amount,
+            )
+        } else {
+            false
+        };
+
+        if matches!(
+            outcome,
+            DisputeOutcome::Frivolous | DisputeOutcome::NoAction | DisputeOutcome::Pending
+        ) {
+            token::Client::new(env, &dispute.token).transfer(
+                &env.current_contract_address(),
+                &dispute.claimant,
+                &dispute.claim_amount,
+            );
+        }
+
+        if !used_escrow {
+            let token_client = token::Client::new(env, &dispute.token);
+            if claimant_amount > 0 {
+                token_client.transfer(
+                    &env.current_contract_address(),
+                    &dispute.claimant,
+                    &claimant_amount,
+                );
+            }
+            if respondent_amount > 0 {
+                token_client.transfer(
+                    &env.current_contract_address(),
+                    &dispute.respondent,
+                    &respondent_amount,
+                );
+            }
+        }
+
+        Self::settle_filing_fee(env, dispute_id, &dispute, &outcome);
+
+        dispute.outcome = outcome;
+        dispute.resolution_notes = notes;
+        dispute.status = DisputeStatus::Resolved;
+        dispute.resolved_at = Some(env.ledger().timestamp());
+
+        let _ttl_key = DataKey::Dispute(dispute_id);
+        env.storage().persistent().set(&_ttl_key, &dispute);
+        env.storage().persistent().extend_ttl(
+            &_ttl_key,
+            PERSISTENT_LIFETIME_THRESHOLD,
+            PERSISTENT_BUMP_AMOUNT,
+        );
+
+        env.events().publish(
+            (symbol_short!("dispute"), symbol_short!("resolved")),
+            dispute_id,
+        );
+    }
+
+    fn compute_filing_fee(env: &Env, claim_amount: i128) -> i128 {
+        let schedule: Option<FeeSchedule> = env.storage().instance().get(&DataKey::FeeSchedule);
+        match schedule {
+            Some(s) => {
+                let fee = s.base_fee + claim_amount * s.claim_bps as i128 / 10_000;
+                if s.max_fee > 0 {
+                    fee.min(s.max_fee)
+                } else {
+                    fee
+                }
+            }
+            None => env
+                .storage()
+                .instance()
+                .get(&DataKey::FilingFee)
+                .unwrap_or(0),
+        }
+    }
+
+    /// Disputes filed before per-dispute fees were recorded paid the flat
+    /// `FilingFee`.
+    fn load_dispute_fee(env: &Env, dispute_id: u64) -> i128 {
+        env.storage()
+            .persistent()
+            .get(&DataKey::DisputeFee(dispute_id))
+            .unwrap_or_else(|| {
+                env.storage()
+                    .instance()
+                    .get(&DataKey::FilingFee)
+                    .unwrap_or(0)
+            })
+    }
+
+    /// Loser pays: the fee goes to whichever party wins and is split on a
+    /// split ruling. With no decision (a tied panel or `NoAction`) nobody
+    /// lost, so the claimant gets the fee back. Only a frivolous claim
+    /// forfeits it: the respondent receives its configured share and the rest
+    /// accrues to the fee pool.
+    fn settle_filing_fee(env: &Env, dispute_id: u64, dispute: &Dispute, outcome: &DisputeOutcome) {
+        let fee = Self::load_dispute_fee(env, dispute_id);
+        if fee <= 0 {
+            return;
+        }
+        let (claimant_share, respondent_share) = match outcome {
+            DisputeOutcome::Claimant => (fee, 0),
+            DisputeOutcome::Respondent => (0, fee),
+            DisputeOutcome::Split => {
+                let claimant_fee = fee / 2;
+                (claimant_fee, fee - claimant_fee)
+            }
+            DisputeOutcome::Frivolous => {
+                let bps: u32 = env
+                    .storage()
+                    .instance()
+                    .get::<DataKey, FeeSchedule>(&DataKey::FeeSchedule)
+                    .map(|s| s.frivolous_respondent_bps)
+                    .unwrap_or(0);
+                (0, fee * bps as i128 / 10_000)
+            }
+            DisputeOutcome::NoAction | DisputeOutcome::Pending => (fee, 0),
+        };
+
+        let token_client = token::Client::new(env, &dispute.token);
+        if claimant_share > 0 {
+            token_client.transfer(
+                &env.current_contract_address(),
+                &dispute.claimant,
+                &claimant_share,
+            );
+        }
+        if respondent_share > 0 {
+            token_client.transfer(
+                &env.current_contract_address(),
+                &dispute.respondent,
+                &respondent_share,
+            );
+        }
+
+        let retained = fee - claimant_share - respondent_share;
+        if retained > 0 {
+            let pool: i128 = env.storage().instance().get(&DataKey::FeePool).unwrap_or(0);
+            env.storage()
+                .instance()
+                .set(&DataKey::FeePool, &(pool + retained));
+        }
+    }
+
     fn try_settle_linked_escrow(
         env: &Env,
         dispute_id: u64,
@@ -425,15 +1200,12 @@ impl DisputeResolutionContract {
         claimant_amount: i128,
         respondent_amount: i128,
     ) -> bool {
-        let escrow_contract: Address = if let Some(addr) = env
-            .storage()
-            .instance()
-            .get(&DataKey::EscrowContract)
-        {
-            addr
-        } else {
-            return false;
-        };
+        let escrow_contract: Address =
+            if let Some(addr) = env.storage().instance().get(&DataKey::EscrowContract) {
+                addr
+            } else {
+                return false;
+            };
         let escrow_id: u64 = if let Some(id) = env
             .storage()
             .persistent()
@@ -447,7 +1219,7 @@ impl DisputeResolutionContract {
         env.invoke_contract::<()>(
             &escrow_contract,
             &Symbol::new(env, "settle_dispute"),
-            SdkVec::from_array(
+            Vec::from_array(
                 env,
                 [
                     env.current_contract_address().into_val(env),
@@ -3,7 +3,7 @@ use super::*;
 use soroban_sdk::{
     testutils::{Address as _, Ledger},
     token::StellarAssetClient,
-    Address, Env, String,
+    Address, Env, String, Vec,
 };
 
 // ─── helpers ─────────────────────────────────────────────────────────────────
@@ -357,3 +357,562 @@ fn test_get_dispute_count_initial() {
 
     assert_eq!(client.get_dispute_count(), 0);
 }
+
+// ─── staked arbitrator panels ────────────────────────────────────────────────
+
+fn setup_panel<'a>(
+    env: &'a Env,
+    panel_size: u32,
+    stakes: &[i128],
+) -> (
+    DisputeResolutionContractClient<'a>,
+    Address,
+    Address,
+    Address,
+    Vec<Address>,
+) {
+    let (client, admin, _, token_addr) = setup(env);
+    let gov_admin = Address::generate(env);
+    let stake_token = deploy_token(env, &gov_admin);
+
+    client.set_arbitration_config(
+        &admin,
+        &ArbitrationConfig {
+            stake_token: stake_token.clone(),
+            min_stake: 1_000,
+            panel_size,
+            ruling_period: 3_600,
+            slash_bps: 1_000,
+            ruling_reward: 50,
+        },
+    );
+
+    let mut arbitrators = Vec::new(env);
+    for stake in stakes {
+        let arb = Address::generate(env);
+        mint(env, &stake_token, &arb, *stake);
+        client.stake_arbitrator(&arb, stake);
+        arbitrators.push_back(arb);
+    }
+
+    (client, admin, token_addr, stake_token, arbitrators)
+}
+
+fn file(env: &Env, client: &DisputeResolutionContractClient<'_>, token_addr: &Address) -> u64 {
+    let claimant = Address::generate(env);
+    let respondent = Address::generate(env);
+    mint(env, token_addr, &claimant, 1_000_000);
+    client.file_dispute(
+        &claimant,
+        &respondent,
+        &1u64,
+        &50_000i128,
+        &make_desc(env),
+        &make_evidence(env),
+    )
+}
+
+#[test]
+fn test_stake_joins_pool() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, _, _, _, arbs) = setup_panel(&env, 1, &[1_000, 500]);
+
+    let pool = client.get_arbitrator_pool();
+    assert_eq!(pool.len(), 1);
+    assert!(pool.contains(arbs.get(0).unwrap()));
+    assert_eq!(
+        client.get_arbitrator(&arbs.get(1).unwrap()).unwrap().stake,
+        500
+    );
+}
+
+#[test]
+fn test_unstake_below_min_leaves_pool() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, _, _, stake_token, arbs) = setup_panel(&env, 1, &[2_000]);
+
+    client.unstake_arbitrator(&arbs.get(0).unwrap(), &1_500);
+
+    assert_eq!(client.get_arbitrator_pool().len(), 0);
+    assert_eq!(
+        client.get_arbitrator(&arbs.get(0).unwrap()).unwrap().stake,
+        500
+    );
+    assert_eq!(
+        token::Client::new(&env, &stake_token).balance(&arbs.get(0).unwrap()),
+        1_500
+    );
+}
+
+#[test]
+#[should_panic(expected = "arbitration not configured")]
+fn test_stake_without_config() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, _, _, _) = setup(&env);
+    client.stake_arbitrator(&Address::generate(&env), &1_000);
+}
+
+#[test]
+fn test_select_panel_draws_from_pool() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, _, token_addr, _, arbs) = setup_panel(&env, 3, &[1_000, 5_000, 20_000, 3_000]);
+    let dispute_id = file(&env, &client, &token_addr);
+
+    let members = client.select_panel(&dispute_id);
+    assert_eq!(members.len(), 3);
+    for m in members.iter() {
+        assert!(arbs.contains(&m));
+        assert_eq!(client.get_arbitrator(&m).unwrap().active_panels, 1);
+    }
+    // no duplicates
+    assert!(members.get(0).unwrap() != members.get(1).unwrap());
+    assert!(members.get(1).unwrap() != members.get(2).unwrap());
+    assert!(members.get(0).unwrap() != members.get(2).unwrap());
+
+    let dispute = client.get_dispute(&dispute_id).unwrap();
+    assert!(matches!(dispute.status, DisputeStatus::Deliberating));
+}
+
+#[test]
+fn test_select_panel_with_stake_above_u64() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let big = u64::MAX as i128;
+    let (client, _, token_addr, _, arbs) = setup_panel(&env, 2, &[big, big, 1_000]);
+    let dispute_id = file(&env, &client, &token_addr);
+
+    let members = client.select_panel(&dispute_id);
+    assert_eq!(members.len(), 2);
+    for m in members.iter() {
+        assert!(arbs.contains(&m));
+    }
+}
+
+#[test]
+#[should_panic(expected = "insufficient arbitrators")]
+fn test_select_panel_insufficient_pool() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, _, token_addr, _, _) = setup_panel(&env, 3, &[1_000, 1_000]);
+    let dispute_id = file(&env, &client, &token_addr);
+    client.select_panel(&dispute_id);
+}
+
+#[test]
+#[should_panic(expected = "arbitrator on active panel")]
+fn test_unstake_while_on_panel() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, _, token_addr, _, arbs) = setup_panel(&env, 1, &[1_000]);
+    let dispute_id = file(&env, &client, &token_addr);
+    client.select_panel(&dispute_id);
+    client.unstake_arbitrator(&arbs.get(0).unwrap(), &1_000);
+}
+
+#[test]
+fn test_panel_majority_rewards_and_slashes_dissent() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, _, token_addr, stake_token, _) = setup_panel(&env, 3, &[10_000, 10_000, 10_000]);
+    let funder = Address::generate(&env);
+    mint(&env, &stake_token, &funder, 1_000);
+    client.fund_reward_pool(&funder, &1_000);
+
+    let dispute_id = file(&env, &client, &token_addr);
+    let members = client.select_panel(&dispute_id);
+    let a = members.get(0).unwrap();
+    let b = members.get(1).unwrap();
+    let c = members.get(2).unwrap();
+
+    client.cast_ruling(&a, &dispute_id, &DisputeOutcome::Claimant);
+    client.cast_ruling(&b, &dispute_id, &DisputeOutcome::Claimant);
+    client.cast_ruling(&c, &dispute_id, &DisputeOutcome::Respondent);
+
+    let outcome = client.finalize_panel(&dispute_id);
+    assert!(matches!(outcome, DisputeOutcome::Claimant));
+
+    let dispute = client.get_dispute(&dispute_id).unwrap();
+    assert!(matches!(dispute.status, DisputeStatus::Resolved));
+    assert!(matches!(dispute.outcome, DisputeOutcome::Claimant));
+
+    // dissenter loses 10% of stake, split between the majority
+    let info_c = client.get_arbitrator(&c).unwrap();
+    assert_eq!(info_c.stake, 9_000);
+    assert_eq!(info_c.slashed_total, 1_000);
+    assert_eq!(info_c.active_panels, 0);
+
+    let info_a = client.get_arbitrator(&a).unwrap();
+    assert_eq!(info_a.stake, 10_000);
+    assert_eq!(info_a.majority_rulings, 1);
+    assert_eq!(info_a.pending_rewards, 500 + 50);
+    assert_eq!(client.get_reward_pool(), 900);
+
+    assert_eq!(client.claim_arbitrator_rewards(&a), 550);
+    assert_eq!(token::Client::new(&env, &stake_token).balance(&a), 550);
+}
+
+#[test]
+fn test_panel_missed_deadline_is_slashed() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, _, token_addr, _, _) = setup_panel(&env, 2, &[10_000, 10_000]);
+
+    let dispute_id = file(&env, &client, &token_addr);
+    let members = client.select_panel(&dispute_id);
+    let voter = members.get(0).unwrap();
+    let absent = members.get(1).unwrap();
+
+    client.cast_ruling(&voter, &dispute_id, &DisputeOutcome::Split);
+    env.ledger().with_mut(|li| li.timestamp += 3_601);
+
+    let outcome = client.finalize_panel(&dispute_id);
+    assert!(matches!(outcome, DisputeOutcome::Split));
+    assert_eq!(client.get_arbitrator(&absent).unwrap().stake, 9_000);
+    assert_eq!(
+        client.get_arbitrator(&voter).unwrap().pending_rewards,
+        1_000
+    );
+}
+
+#[test]
+fn test_panel_without_rulings_returns_to_filed() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, _, token_addr, _, arbs) = setup_panel(&env, 1, &[10_000, 10_000]);
+
+    let dispute_id = file(&env, &client, &token_addr);
+    client.select_panel(&dispute_id);
+    env.ledger().with_mut(|li| li.timestamp += 3_601);
+
+    let outcome = client.finalize_panel(&dispute_id);
+    assert!(matches!(outcome, DisputeOutcome::Pending));
+    assert!(client.get_panel(&dispute_id).is_none());
+    let dispute = client.get_dispute(&dispute_id).unwrap();
+    assert!(matches!(dispute.status, DisputeStatus::Filed));
+
+    let slashed: i128 = arbs
+        .iter()
+        .map(|a| client.get_arbitrator(&a).unwrap().slashed_total)
+        .sum();
+    assert_eq!(slashed, 1_000);
+    assert_eq!(client.get_reward_pool(), 1_000);
+
+    // a fresh panel can be drawn
+    assert_eq!(client.select_panel(&dispute_id).len(), 1);
+}
+
+#[test]
+fn test_tied_panel_returns_claim() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, _, token_addr, _, _) = setup_panel(&env, 2, &[10_000, 10_000]);
+
+    let dispute_id = file(&env, &client, &token_addr);
+    let claimant = client.get_dispute(&dispute_id).unwrap().claimant;
+    let members = client.select_panel(&dispute_id);
+    client.cast_ruling(
+        &members.get(0).unwrap(),
+        &dispute_id,
+        &DisputeOutcome::Claimant,
+    );
+    client.cast_ruling(
+        &members.get(1).unwrap(),
+        &dispute_id,
+        &DisputeOutcome::Respondent,
+    );
+
+    let outcome = client.finalize_panel(&dispute_id);
+    assert!(matches!(outcome, DisputeOutcome::NoAction));
+
+    // a tie decides nothing: the locked claim and the fee both come back
+    let token = token::Client::new(&env, &token_addr);
+    assert_eq!(token.balance(&claimant), 1_000_000);
+    assert_eq!(token.balance(&client.address), 0);
+    assert_eq!(client.get_fee_pool(), 0);
+}
+
+#[test]
+#[should_panic(expected = "ruling period active")]
+fn test_finalize_panel_too_early() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, _, token_addr, _, _) = setup_panel(&env, 2, &[10_000, 10_000]);
+    let dispute_id = file(&env, &client, &token_addr);
+    let members = client.select_panel(&dispute_id);
+    client.cast_ruling(
+        &members.get(0).unwrap(),
+        &dispute_id,
+        &DisputeOutcome::Claimant,
+    );
+    client.finalize_panel(&dispute_id);
+}
+
+#[test]
+#[should_panic(expected = "not panel member")]
+fn test_cast_ruling_by_outsider() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, _, token_addr, _, _) = setup_panel(&env, 1, &[10_000]);
+    let dispute_id = file(&env, &client, &token_addr);
+    client.select_panel(&dispute_id);
+    client.cast_ruling(
+        &Address::generate(&env),
+        &dispute_id,
+        &DisputeOutcome::Claimant,
+    );
+}
+
+#[test]
+#[should_panic(expected = "staked panels enabled")]
+fn test_assign_arbitrator_with_staked_panels() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, admin, token_addr, _, _) = setup_panel(&env, 1, &[10_000]);
+    let arbitrator = Address::generate(&env);
+    let dispute_id = file(&env, &client, &token_addr);
+    client.authorize_arbitrator(&admin, &arbitrator);
+    client.assign_arbitrator(&admin, &dispute_id, &arbitrator);
+}
+
+#[test]
+#[should_panic(expected = "dispute not open for panel")]
+fn test_select_panel_after_manual_assignment() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, admin, _, token_addr) = setup(&env);
+    let arbitrator = Address::generate(&env);
+    let dispute_id = file(&env, &client, &token_addr);
+    client.authorize_arbitrator(&admin, &arbitrator);
+    client.assign_arbitrator(&admin, &dispute_id, &arbitrator);
+
+    let gov_admin = Address::generate(&env);
+    client.set_arbitration_config(
+        &admin,
+        &ArbitrationConfig {
+            stake_token: deploy_token(&env, &gov_admin),
+            min_stake: 1_000,
+            panel_size: 1,
+            ruling_period: 3_600,
+            slash_bps: 1_000,
+            ruling_reward: 0,
+        },
+    );
+    client.select_panel(&dispute_id);
+}
+
+// ─── filing fees ─────────────────────────────────────────────────────────────
+
+fn resolve_with(
+    env: &Env,
+    client: &DisputeResolutionContractClient<'_>,
+    admin: &Address,
+    token_addr: &Address,
+    outcome: DisputeOutcome,
+) -> (Address, Address) {
+    let claimant = Address::generate(env);
+    let respondent = Address::generate(env);
+    let arbitrator = Address::generate(env);
+    mint(env, token_addr, &claimant, 100_000);
+
+    let dispute_id = client.file_dispute(
+        &claimant,
+        &respondent,
+        &1u64,
+        &50_000i128,
+        &make_desc(env),
+        &make_evidence(env),
+    );
+    client.authorize_arbitrator(admin, &arbitrator);
+    client.assign_arbitrator(admin, &dispute_id, &arbitrator);
+    client.resolve_dispute(
+        &arbitrator,
+        &dispute_id,
+        &outcome,
+        &String::from_str(env, "ruling"),
+    );
+    (claimant, respondent)
+}
+
+fn set_schedule(client: &DisputeResolutionContractClient<'_>, admin: &Address) {
+    client.set_fee_schedule(
+        admin,
+        &FeeSchedule {
+            base_fee: 500,
+            claim_bps: 100,
+            max_fee: 2_000,
+            frivolous_respondent_bps: 5_000,
+        },
+    );
+}
+
+#[test]
+fn test_fee_schedule_scales_with_claim() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, admin, _, _) = setup(&env);
+
+    assert_eq!(client.quote_filing_fee(&50_000), 1_000);
+    set_schedule(&client, &admin);
+    assert_eq!(client.quote_filing_fee(&50_000), 1_000);
+    assert_eq!(client.quote_filing_fee(&10_000), 600);
+    assert_eq!(client.quote_filing_fee(&1_000_000), 2_000);
+}
+
+#[test]
+fn test_filing_fee_recorded_on_dispute() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, admin, _, token_addr) = setup(&env);
+    set_schedule(&client, &admin);
+
+    let claimant = Address::generate(&env);
+    mint(&env, &token_addr, &claimant, 100_000);
+    let dispute_id = client.file_dispute(
+        &claimant,
+        &Address::generate(&env),
+        &1u64,
+        &10_000i128,
+        &make_desc(&env),
+        &make_evidence(&env),
+    );
+
+    assert_eq!(client.get_dispute_fee(&dispute_id), 600);
+    assert_eq!(
+        token::Client::new(&env, &token_addr).balance(&claimant),
+        89_400
+    );
+}
+
+#[test]
+fn test_winning_claimant_gets_fee_back() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, admin, _, token_addr) = setup(&env);
+
+    let (claimant, _) = resolve_with(&env, &client, &admin, &token_addr, DisputeOutcome::Claimant);
+
+    assert_eq!(
+        token::Client::new(&env, &token_addr).balance(&claimant),
+        100_000
+    );
+    assert_eq!(client.get_fee_pool(), 0);
+}
+
+#[test]
+fn test_winning_respondent_gets_fee() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, admin, _, token_addr) = setup(&env);
+
+    let (claimant, respondent) = resolve_with(
+        &env,
+        &client,
+        &admin,
+        &token_addr,
+        DisputeOutcome::Respondent,
+    );
+
+    let token = token::Client::new(&env, &token_addr);
+    assert_eq!(token.balance(&claimant), 49_000);
+    assert_eq!(token.balance(&respondent), 51_000);
+    assert_eq!(client.get_fee_pool(), 0);
+}
+
+#[test]
+fn test_no_action_refunds_fee() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, admin, _, token_addr) = setup(&env);
+
+    let (claimant, respondent) =
+        resolve_with(&env, &client, &admin, &token_addr, DisputeOutcome::NoAction);
+
+    let token = token::Client::new(&env, &token_addr);
+    assert_eq!(token.balance(&claimant), 100_000);
+    assert_eq!(token.balance(&respondent), 0);
+    assert_eq!(client.get_fee_pool(), 0);
+}
+
+#[test]
+fn test_frivolous_claim_forfeits_fee() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, admin, _, token_addr) = setup(&env);
+    set_schedule(&client, &admin);
+
+    let (claimant, respondent) = resolve_with(
+        &env,
+        &client,
+        &admin,
+        &token_addr,
+        DisputeOutcome::Frivolous,
+    );
+
+    // fee on 50_000 is 1_000: claim returned, fee split respondent / pool
+    let token = token::Client::new(&env, &token_addr);
+    assert_eq!(token.balance(&claimant), 99_000);
+    assert_eq!(token.balance(&respondent), 500);
+    assert_eq!(client.get_fee_pool(), 500);
+}
+
+#[test]
+fn test_withdraw_fees() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, admin, _, token_addr) = setup(&env);
+    resolve_with(
+        &env,
+        &client,
+        &admin,
+        &token_addr,
+        DisputeOutcome::Frivolous,
+    );
+
+    let treasury = Address::generate(&env);
+    client.withdraw_fees(&admin, &treasury, &600);
+
+    assert_eq!(
+        token::Client::new(&env, &token_addr).balance(&treasury),
+        600
+    );
+    assert_eq!(client.get_fee_pool(), 400);
+}
+
+#[test]
+#[should_panic(expected = "insufficient fee pool")]
+fn test_withdraw_fees_exceeds_pool() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, admin, _, token_addr) = setup(&env);
+    resolve_with(
+        &env,
+        &client,
+        &admin,
+        &token_addr,
+        DisputeOutcome::Frivolous,
+    );
+
+    client.withdraw_fees(&admin, &Address::generate(&env), &1_001);
+}
+
+#[test]
+#[should_panic(expected = "unauthorized")]
+fn test_withdraw_fees_by_stranger() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, admin, _, token_addr) = setup(&env);
+    resolve_with(
+        &env,
+        &client,
+        &admin,
+        &token_addr,
+        DisputeOutcome::Frivolous,
+    );
+
+    let stranger = Address::generate(&env);
+    client.withdraw_fees(&stranger, &stranger, &100);
+}
@@ -6,9 +6,13 @@
 //! - ("escrow", "release"): [escrow_id: u64, amount: i128]
 //! - ("escrow", "release_partial"): [escrow_id: u64, amount: i128]
 //! - ("escrow", "refund"): [escrow_id: u64, amount: i128]
+//! - ("escrow", "held"): [escrow_id: u64]
+//! - ("escrow", "unheld"): [escrow_id: u64]
 
 #![no_std]
-use soroban_sdk::{contract, contractimpl, contracttype, symbol_short, token, Address, Env, Vec};
+use soroban_sdk::{
+    contract, contractimpl, contracttype, symbol_short, token, Address, Env, Map, Vec,
+};
 
 // ============================================================
 // Data Types
@@ -80,6 +84,10 @@ pub enum DataKey {
     ApprovalCount(u64),
     RequiredApprover(u64, Address),
     Performance(u64),
+    BeneficiaryEscrows(Address),
+    FraudHold(u64),
+    FraudHolders(u64), // escrow_id -> holds outstanding per fraud authority
+    AnomalyDetector,
 }
 
 // ============================================================
@@ -132,6 +140,21 @@ impl EscrowVaultContract {
             .set(&DataKey::FraudContract, &fraud_contract);
     }
 
+    /// Let anomaly-detector place and lift fraud holds alongside the fraud contract
+    pub fn set_anomaly_detector(env: Env, admin: Address, anomaly_detector: Address) {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        admin.require_auth();
+        let stored_admin: Address = env.storage().instance().get(&DataKey::Admin).unwrap();
+        if admin != stored_admin {
+            panic!("unauthorized");
+        }
+        env.storage()
+            .instance()
+            .set(&DataKey::AnomalyDetector, &anomaly_detector);
+    }
+
     pub fn set_dispute_contract(env: Env, admin: Address, dispute_contract: Address) {
         env.storage()
             .instance()
@@ -152,12 +175,7 @@ impl EscrowVaultContract {
             .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
         fraud_contract.require_auth();
 
-        let stored_fraud: Address = env
-            .storage()
-            .instance()
-            .get(&DataKey::FraudContract)
-            .expect("fraud contract not set");
-        if fraud_contract != stored_fraud {
+        if !Self::_is_fraud_authority(&env, &fraud_contract) {
             panic!("unauthorized fraud contract");
         }
 
@@ -167,6 +185,31 @@ impl EscrowVaultContract {
             .get(&DataKey::Escrow(escrow_id))
             .expect("escrow not found");
 
+        if escrow.state == EscrowState::Released || escrow.state == EscrowState::Refunded {
+            panic!("already settled");
+        }
+        let mut holders = Self::_load_fraud_holders(&env, escrow_id);
+        if escrow.state == EscrowState::Disputed {
+            // Already held by another authority (or this one again): stack
+            holders.set(
+                fraud_contract.clone(),
+                holders.get(fraud_contract.clone()).unwrap_or(0) + 1,
+            );
+            Self::_save_fraud_holders(&env, escrow_id, &holders);
+            return;
+        }
+        holders.set(fraud_contract.clone(), 1);
+        Self::_save_fraud_holders(&env, escrow_id, &holders);
+
+        // Remember the pre-hold state so release_fraud_hold can restore it
+        let _ttl_key = DataKey::FraudHold(escrow_id);
+        env.storage().persistent().set(&_ttl_key, &escrow.state);
+        env.storage().persistent().extend_ttl(
+            &_ttl_key,
+            PERSISTENT_LIFETIME_THRESHOLD,
+            PERSISTENT_BUMP_AMOUNT,
+        );
+
         escrow.state = EscrowState::Disputed;
 
         let _ttl_key = DataKey::Escrow(escrow_id);
@@ -176,6 +219,70 @@ impl EscrowVaultContract {
             PERSISTENT_LIFETIME_THRESHOLD,
             PERSISTENT_BUMP_AMOUNT,
         );
+
+        env.events().publish((symbol_short!("escrow"), symbol_short!("held")), escrow_id);
+    }
+
+    /// Lift one fraud hold placed by `fraud_contract`. The prior state is
+    /// only restored once every authority holding the escrow has released it.
+    pub fn release_fraud_hold(env: Env, fraud_contract: Address, escrow_id: u64) {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        fraud_contract.require_auth();
+
+        if !Self::_is_fraud_authority(&env, &fraud_contract) {
+            panic!("unauthorized fraud contract");
+        }
+
+        let mut holders = Self::_load_fraud_holders(&env, escrow_id);
+        let held = holders.get(fraud_contract.clone()).unwrap_or(0);
+        // Holds placed before holder tracking have a prior state but no holders
+        let untracked = holders.is_empty()
+            && env.storage().persistent().has(&DataKey::FraudHold(escrow_id));
+        if held == 0 && !untracked {
+            panic!("no fraud hold");
+        }
+        if held > 1 {
+            holders.set(fraud_contract.clone(), held - 1);
+        } else {
+            holders.remove(fraud_contract.clone());
+        }
+        if !holders.is_empty() {
+            Self::_save_fraud_holders(&env, escrow_id, &holders);
+            return;
+        }
+        env.storage()
+            .persistent()
+            .remove(&DataKey::FraudHolders(escrow_id));
+
+        let prior_state: EscrowState = env
+            .storage()
+            .persistent()
+            .get(&DataKey::FraudHold(escrow_id))
+            .expect("no fraud hold");
+        env.storage().persistent().remove(&DataKey::FraudHold(escrow_id));
+
+        let mut escrow: Escrow = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Escrow(escrow_id))
+            .expect("escrow not found");
+        // A dispute settlement may already have moved the escrow on
+        if escrow.state != EscrowState::Disputed {
+            return;
+        }
+        escrow.state = prior_state;
+
+        let _ttl_key = DataKey::Escrow(escrow_id);
+        env.storage().persistent().set(&_ttl_key, &escrow);
+        env.storage().persistent().extend_ttl(
+            &_ttl_key,
+            PERSISTENT_LIFETIME_THRESHOLD,
+            PERSISTENT_BUMP_AMOUNT,
+        );
+
+        env.events().publish((symbol_short!("escrow"), symbol_short!("unheld")), escrow_id);
     }
 
     /// Create a new escrow
@@ -251,6 +358,20 @@ impl EscrowVaultContract {
             PERSISTENT_BUMP_AMOUNT,
         );
 
+        let _ttl_key = DataKey::BeneficiaryEscrows(escrow.beneficiary.clone());
+        let mut beneficiary_escrows: Vec<u64> = env
+            .storage()
+            .persistent()
+            .get(&_ttl_key)
+            .unwrap_or(Vec::new(&env));
+        beneficiary_escrows.push_back(escrow_id);
+        env.storage().persistent().set(&_ttl_key, &beneficiary_escrows);
+        env.storage().persistent().extend_ttl(
+            &_ttl_key,
+            PERSISTENT_LIFETIME_THRESHOLD,
+            PERSISTENT_BUMP_AMOUNT,
+        );
+
         // Register required approvers
         for approver in required_approvers.iter() {
             let _ttl_key = DataKey::RequiredApprover(escrow_id, approver.clone());
@@ -644,6 +765,35 @@ impl EscrowVaultContract {
         env.storage().persistent().get(&DataKey::Escrow(escrow_id))
     }
 
+    pub fn get_beneficiary_escrows(env: Env, beneficiary: Address) -> Vec<u64> {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        env.storage()
+            .persistent()
+            .get(&DataKey::BeneficiaryEscrows(beneficiary))
+            .unwrap_or(Vec::new(&env))
+    }
+
+    /// Whether `beneficiary` is paid from an escrow funding `campaign_id`
+    pub fn serves_campaign(env: Env, beneficiary: Address, campaign_id: u64) -> bool {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        let escrow_ids: Vec<u64> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::BeneficiaryEscrows(beneficiary))
+            .unwrap_or(Vec::new(&env));
+        escrow_ids.iter().any(|escrow_id| {
+            env.storage()
+                .persistent()
+                .get::<DataKey, Escrow>(&DataKey::Escrow(escrow_id))
+                .map(|escrow| escrow.campaign_id == campaign_id)
+                .unwrap_or(false)
+        })
+    }
+
     pub fn get_performance(env: Env, escrow_id: u64) -> Option<PerformanceMetrics> {
         env.storage()
             .instance()
@@ -706,6 +856,35 @@ impl EscrowVaultContract {
     // Internal Helpers
     // ============================================================
 
+    /// The fraud contract or the anomaly detector, whichever is configured
+    fn _is_fraud_authority(env: &Env, actor: &Address) -> bool {
+        let matches = |key: DataKey| {
+            env.storage()
+                .instance()
+                .get::<DataKey, Address>(&key)
+                .map(|addr| addr == *actor)
+                .unwrap_or(false)
+        };
+        matches(DataKey::FraudContract) || matches(DataKey::AnomalyDetector)
+    }
+
+    fn _load_fraud_holders(env: &Env, escrow_id: u64) -> Map<Address, u32> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::FraudHolders(escrow_id))
+            .unwrap_or(Map::new(env))
+    }
+
+    fn _save_fraud_holders(env: &Env, escrow_id: u64, holders: &Map<Address, u32>) {
+        let _ttl_key = DataKey::FraudHolders(escrow_id);
+        env.storage().persistent().set(&_ttl_key, holders);
+        env.storage().persistent().extend_ttl(
+            &_ttl_key,
+            PERSISTENT_LIFETIME_THRESHOLD,
+            PERSISTENT_BUMP_AMOUNT,
+        );
+    }
+
     fn _check_can_release(env: &Env, escrow: &Escrow, escrow_id: u64) {
         if escrow.state == EscrowState::Disputed {
             panic!("escrow is disputed due to fraud");
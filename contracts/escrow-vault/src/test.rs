@@ -740,6 +740,77 @@ fn test_release_disputed_escrow_fails() {
     client.release_escrow(&depositor, &escrow_id); // should panic
 }
 
+#[test]
+fn test_release_fraud_hold_restores_state() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, admin, token_addr, _) = setup(&env);
+    let fraud_contract = Address::generate(&env);
+    client.set_fraud_contract(&admin, &fraud_contract);
+
+    let depositor = Address::generate(&env);
+    let beneficiary = Address::generate(&env);
+    mint(&env, &admin, &token_addr, &depositor, 1_000_000);
+    let escrow_id = client.create_escrow(
+        &depositor,
+        &1u64,
+        &beneficiary,
+        &100_000i128,
+        &0u64,
+        &0u32,
+        &999_999u64,
+        &vec![&env],
+    );
+    let second_id = client.create_escrow(
+        &depositor,
+        &2u64,
+        &beneficiary,
+        &100_000i128,
+        &0u64,
+        &0u32,
+        &999_999u64,
+        &vec![&env],
+    );
+    assert_eq!(client.get_beneficiary_escrows(&beneficiary), vec![&env, escrow_id, second_id]);
+    assert!(client.serves_campaign(&beneficiary, &2u64));
+    assert!(!client.serves_campaign(&beneficiary, &3u64));
+    assert!(!client.serves_campaign(&depositor, &1u64));
+
+    client.hold_for_fraud(&fraud_contract, &escrow_id);
+    // Holding twice keeps the original pre-hold state, and needs two releases
+    client.hold_for_fraud(&fraud_contract, &escrow_id);
+    client.release_fraud_hold(&fraud_contract, &escrow_id);
+    assert!(matches!(client.get_escrow(&escrow_id).unwrap().state, EscrowState::Disputed));
+    client.release_fraud_hold(&fraud_contract, &escrow_id);
+
+    let escrow = client.get_escrow(&escrow_id).unwrap();
+    assert!(matches!(escrow.state, EscrowState::Locked));
+}
+
+#[test]
+#[should_panic(expected = "no fraud hold")]
+fn test_release_fraud_hold_without_hold() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, admin, token_addr, _) = setup(&env);
+    let fraud_contract = Address::generate(&env);
+    client.set_fraud_contract(&admin, &fraud_contract);
+
+    let depositor = Address::generate(&env);
+    mint(&env, &admin, &token_addr, &depositor, 1_000_000);
+    let escrow_id = client.create_escrow(
+        &depositor,
+        &1u64,
+        &Address::generate(&env),
+        &100_000i128,
+        &0u64,
+        &0u32,
+        &999_999u64,
+        &vec![&env],
+    );
+    client.release_fraud_hold(&fraud_contract, &escrow_id);
+}
+
 // ─── can_release ─────────────────────────────────────────────────────────────
 
 #[test]
@@ -813,3 +884,64 @@ fn test_accept_admin_unauthorized() {
     c.propose_admin(&admin, &new_admin);
     c.accept_admin(&stranger);
 }
+
+#[test]
+fn test_anomaly_detector_holds_escrow() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, admin, token_addr, _) = setup(&env);
+    let detector = Address::generate(&env);
+    client.set_anomaly_detector(&admin, &detector);
+
+    let depositor = Address::generate(&env);
+    mint(&env, &admin, &token_addr, &depositor, 1_000_000);
+    let escrow_id = client.create_escrow(
+        &depositor,
+        &1u64,
+        &Address::generate(&env),
+        &100_000i128,
+        &0u64,
+        &0u32,
+        &999_999u64,
+        &vec![&env],
+    );
+
+    client.hold_for_fraud(&detector, &escrow_id);
+    assert!(matches!(client.get_escrow(&escrow_id).unwrap().state, EscrowState::Disputed));
+    client.release_fraud_hold(&detector, &escrow_id);
+    assert!(matches!(client.get_escrow(&escrow_id).unwrap().state, EscrowState::Locked));
+}
+
+#[test]
+fn test_fraud_hold_released_by_last_holder() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, admin, token_addr, _) = setup(&env);
+    let fraud_contract = Address::generate(&env);
+    let detector = Address::generate(&env);
+    client.set_fraud_contract(&admin, &fraud_contract);
+    client.set_anomaly_detector(&admin, &detector);
+
+    let depositor = Address::generate(&env);
+    mint(&env, &admin, &token_addr, &depositor, 1_000_000);
+    let escrow_id = client.create_escrow(
+        &depositor,
+        &1u64,
+        &Address::generate(&env),
+        &100_000i128,
+        &0u64,
+        &0u32,
+        &999_999u64,
+        &vec![&env],
+    );
+
+    client.hold_for_fraud(&fraud_contract, &escrow_id);
+    client.hold_for_fraud(&detector, &escrow_id);
+    client.release_fraud_hold(&fraud_contract, &escrow_id);
+    assert!(matches!(client.get_escrow(&escrow_id).unwrap().state, EscrowState::Disputed));
+    // The fraud contract has nothing left to release
+    assert!(client.try_release_fraud_hold(&fraud_contract, &escrow_id).is_err());
+
+    client.release_fraud_hold(&detector, &escrow_id);
+    assert!(matches!(client.get_escrow(&escrow_id).unwrap().state, EscrowState::Locked));
+}
@@ -1,6 +1,10 @@
 #![cfg(test)]
 use super::*;
-use soroban_sdk::{testutils::{Address as _, Ledger}, Address, BytesN, Env};
+use ed25519_dalek::{Signer, SigningKey};
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    Address, BytesN, Env, Vec,
+};
 
 // ─── helpers ─────────────────────────────────────────────────────────────────
 
@@ -14,6 +18,41 @@ fn setup(env: &Env) -> (FraudPreventionContractClient<'_>, Address) {
     (client, admin)
 }
 
+fn register_key(
+    env: &Env,
+    client: &FraudPreventionContractClient,
+    admin: &Address,
+    signer: &SigningKey,
+    owner: &Address,
+    kind: AttesterKind,
+) -> BytesN<32> {
+    let key = BytesN::from_array(env, &signer.verifying_key().to_bytes());
+    client.register_attestation_key(admin, owner, &key, &kind);
+    key
+}
+
+#[allow(clippy::too_many_arguments)]
+fn sign_proof(
+    env: &Env,
+    client: &FraudPreventionContractClient,
+    signer: &SigningKey,
+    key: &BytesN<32>,
+    campaign_id: u64,
+    publisher: &Address,
+    viewer: &Address,
+    nonce: u64,
+) -> ViewProof {
+    let timestamp = env.ledger().timestamp();
+    let payload = client.view_proof_payload(&campaign_id, publisher, viewer, &timestamp, &nonce);
+    let signature = signer.sign(&payload.to_alloc_vec());
+    ViewProof {
+        key: key.clone(),
+        timestamp,
+        nonce,
+        signature: BytesN::from_array(env, &signature.to_bytes()),
+    }
+}
+
 // ─── initialize ──────────────────────────────────────────────────────────────
 
 #[test]
@@ -183,6 +222,36 @@ fn test_flag_suspicious_admin() {
     assert!(!status.suspended);
 }
 
+#[test]
+fn test_anomaly_detector_flags_and_retracts() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, admin) = setup(&env);
+
+    let detector = Address::generate(&env);
+    let publisher = Address::generate(&env);
+    client.set_signal_contracts(&admin, &Address::generate(&env), &detector);
+
+    client.flag_suspicious(&detector, &publisher);
+    client.flag_suspicious(&detector, &publisher);
+    client.retract_flag(&detector, &publisher);
+
+    let status = client.get_suspicious_status(&publisher).unwrap();
+    assert_eq!(status.suspicious_views, 1);
+    assert_eq!(status.total_flags, 1);
+}
+
+#[test]
+#[should_panic(expected = "unauthorized - only admin or oracle can flag publishers")]
+fn test_retract_flag_unauthorized() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, admin) = setup(&env);
+    let publisher = Address::generate(&env);
+    client.flag_suspicious(&admin, &publisher);
+    client.retract_flag(&Address::generate(&env), &publisher);
+}
+
 #[test]
 fn test_flag_suspicious_oracle() {
     let env = Env::default();
@@ -315,8 +384,17 @@ fn test_verify_view_with_proof() {
     let vault = Address::generate(&env);
     client.set_dependent_contracts(&admin, &lifecycle, &network, &vault);
 
-    // Threshold is 80, base_score=80 + proof_bonus=10 = 90 >= 80 → verified
-    let proof = BytesN::from_array(&env, &[1u8; 32]);
+    // Threshold is 80, base_score=90 + proof_bonus=10 = 100 >= 80 → verified
+    let signer = SigningKey::from_bytes(&[7u8; 32]);
+    let key = register_key(
+        &env,
+        &client,
+        &admin,
+        &signer,
+        &publisher,
+        AttesterKind::Publisher,
+    );
+    let proof = sign_proof(&env, &client, &signer, &key, 1, &publisher, &viewer, 1);
     let result = client.verify_view(&1u64, &publisher, &viewer, &Some(proof));
     assert!(result);
 
@@ -337,7 +415,7 @@ fn test_verify_view_without_proof() {
     let vault = Address::generate(&env);
     client.set_dependent_contracts(&admin, &lifecycle, &network, &vault);
 
-    // base_score = 80, no proof bonus → 80 >= 80 → verified
+    // base_score = 90, no penalties and no proof bonus → 90 >= 80 → verified
     let result = client.verify_view(&1u64, &publisher, &viewer, &None);
     assert!(result);
 }
@@ -435,3 +513,1163 @@ fn test_duplicate_view_prevention_same_campaign() {
     });
     assert!(client.verify_view(&1u64, &publisher1, &viewer1, &None));
 }
+
+// ─── view scoring ────────────────────────────────────────────────────────────
+
+#[contract]
+pub struct MockReputation;
+
+#[contractimpl]
+impl MockReputation {
+    pub fn set_score(env: Env, publisher: Address, score: u32) {
+        env.storage().instance().set(&publisher, &score);
+    }
+
+    pub fn get_reputation(env: Env, publisher: Address) -> Option<mocks::ReputationScore> {
+        let score: Option<u32> = env.storage().instance().get(&publisher);
+        score.map(|score| mocks::ReputationScore {
+            publisher,
+            score,
+            total_reviews: 0,
+            positive_reviews: 0,
+            negative_reviews: 0,
+            slashes: 0,
+            uptime_score: 100,
+            quality_score: 100,
+            last_slash_ledger: 0,
+            last_updated: 0,
+        })
+    }
+}
+
+#[contract]
+pub struct MockAnomalyDetector;
+
+#[contractimpl]
+impl MockAnomalyDetector {
+    pub fn set_flagged(env: Env, publisher: Address, flagged: bool) {
+        env.storage().instance().set(&publisher, &flagged);
+    }
+
+    pub fn is_publisher_flagged(env: Env, publisher: Address) -> bool {
+        env.storage().instance().get(&publisher).unwrap_or(false)
+    }
+}
+
+#[contract]
+pub struct RecordingLifecycle;
+
+#[contractimpl]
+impl RecordingLifecycle {
+    pub fn pause_for_fraud(env: Env, fraud_contract: Address, campaign_id: u64) {
+        let count: u32 = env.storage().instance().get(&campaign_id).unwrap_or(0);
+        env.storage().instance().set(&campaign_id, &(count + 1));
+        env.storage()
+            .instance()
+            .set(&(symbol_short!("holder"), campaign_id), &fraud_contract);
+    }
+
+    pub fn get_fraud_holds(env: Env, campaign_id: u64) -> Map<Address, u32> {
+        let mut holds = Map::new(&env);
+        let held = Self::pauses(env.clone(), campaign_id)
+            .saturating_sub(Self::resumes(env.clone(), campaign_id));
+        if let Some(holder) = env
+            .storage()
+            .instance()
+            .get::<_, Address>(&(symbol_short!("holder"), campaign_id))
+        {
+            if held > 0 {
+                holds.set(holder, held);
+            }
+        }
+        holds
+    }
+
+    pub fn pauses(env: Env, campaign_id: u64) -> u32 {
+        env.storage().instance().get(&campaign_id).unwrap_or(0)
+    }
+
+    pub fn resume_after_fraud(env: Env, _fraud_contract: Address, campaign_id: u64) {
+        let key = (symbol_short!("resume"), campaign_id);
+        let count: u32 = env.storage().instance().get(&key).unwrap_or(0);
+        env.storage().instance().set(&key, &(count + 1));
+    }
+
+    pub fn resumes(env: Env, campaign_id: u64) -> u32 {
+        env.storage()
+            .instance()
+            .get(&(symbol_short!("resume"), campaign_id))
+            .unwrap_or(0)
+    }
+}
+
+mod paused_lifecycle {
+    use soroban_sdk::{contract, contractimpl, Address, Env};
+
+    #[contract]
+    pub struct AlreadyPausedLifecycle;
+
+    #[contractimpl]
+    impl AlreadyPausedLifecycle {
+        pub fn pause_for_fraud(_env: Env, _fraud_contract: Address, _campaign_id: u64) {
+            panic!("invalid state transition");
+        }
+    }
+}
+
+fn setup_signals<'a>(
+    env: &'a Env,
+) -> (
+    FraudPreventionContractClient<'a>,
+    Address,
+    MockReputationClient<'a>,
+    MockAnomalyDetectorClient<'a>,
+    RecordingLifecycleClient<'a>,
+) {
+    let (client, admin) = setup(env);
+    let reputation = MockReputationClient::new(env, &env.register(MockReputation, ()));
+    let detector = MockAnomalyDetectorClient::new(env, &env.register(MockAnomalyDetector, ()));
+    let lifecycle = RecordingLifecycleClient::new(env, &env.register(RecordingLifecycle, ()));
+    let network = env.register(mocks::PublisherNetworkContract, ());
+    let vault = env.register(StubVault, ());
+    client.set_dependent_contracts(&admin, &lifecycle.address, &network, &vault);
+    client.set_signal_contracts(&admin, &reputation.address, &detector.address);
+    (client, admin, reputation, detector, lifecycle)
+}
+
+#[test]
+fn test_score_clean_view() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, _, _, _, _) = setup_signals(&env);
+    let publisher = Address::generate(&env);
+    let viewer = Address::generate(&env);
+
+    assert_eq!(
+        client.get_view_score(&1u64, &publisher, &viewer, &false),
+        90
+    );
+    assert_eq!(
+        client.get_view_score(&1u64, &publisher, &viewer, &true),
+        100
+    );
+}
+
+#[test]
+fn test_score_rate_pressure() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, _, _, _, _) = setup_signals(&env);
+    let viewer = Address::generate(&env);
+
+    for _ in 0..5 {
+        let publisher = Address::generate(&env);
+        assert!(client.verify_view(&1u64, &publisher, &viewer, &None));
+    }
+
+    // 5 of 10 hourly views used → 20 * 5 / 10 = 10 point penalty
+    let publisher = Address::generate(&env);
+    assert_eq!(
+        client.get_view_score(&1u64, &publisher, &viewer, &false),
+        80
+    );
+    assert!(client.verify_view(&1u64, &publisher, &viewer, &None));
+    assert_eq!(
+        client.get_view_score(&1u64, &publisher, &viewer, &false),
+        78
+    );
+    assert!(!client.verify_view(&1u64, &Address::generate(&env), &viewer, &None));
+}
+
+#[test]
+fn test_score_suspicious_history() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, admin, _, _, _) = setup_signals(&env);
+    let publisher = Address::generate(&env);
+    let viewer = Address::generate(&env);
+
+    for _ in 0..50 {
+        client.flag_suspicious(&admin, &publisher);
+    }
+    // 50 of 100 suspicious views → 40 * 50 / 100 = 20 point penalty
+    assert_eq!(
+        client.get_view_score(&1u64, &publisher, &viewer, &false),
+        70
+    );
+    assert!(!client.verify_view(&1u64, &publisher, &viewer, &None));
+
+    let stats = client.get_verification_stats(&1u64);
+    assert_eq!(stats.rejected_views, 1);
+    assert_eq!(stats.average_score, 70);
+    assert_eq!(client.get_total_verifications(), 0);
+
+    client.suspend_publisher(&admin, &publisher);
+    assert_eq!(
+        client.get_view_score(&1u64, &publisher, &Address::generate(&env), &false),
+        50
+    );
+}
+
+#[test]
+fn test_score_reputation() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, _, reputation, _, _) = setup_signals(&env);
+    let publisher = Address::generate(&env);
+    let viewer = Address::generate(&env);
+
+    // Unknown and above-neutral publishers are not penalised
+    assert_eq!(
+        client.get_view_score(&1u64, &publisher, &viewer, &false),
+        90
+    );
+    reputation.set_score(&publisher, &800u32);
+    assert_eq!(
+        client.get_view_score(&1u64, &publisher, &viewer, &false),
+        90
+    );
+
+    reputation.set_score(&publisher, &250u32);
+    assert_eq!(
+        client.get_view_score(&1u64, &publisher, &viewer, &false),
+        70
+    );
+    reputation.set_score(&publisher, &0u32);
+    assert_eq!(
+        client.get_view_score(&1u64, &publisher, &viewer, &false),
+        50
+    );
+}
+
+#[test]
+fn test_score_anomaly_flag_alone_does_not_pause() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, _, _, detector, lifecycle) = setup_signals(&env);
+    let publisher = Address::generate(&env);
+    let viewer = Address::generate(&env);
+
+    detector.set_flagged(&publisher, &true);
+    assert_eq!(
+        client.get_view_score(&1u64, &publisher, &viewer, &false),
+        60
+    );
+
+    assert!(!client.verify_view(&7u64, &publisher, &viewer, &None));
+    assert_eq!(lifecycle.pauses(&7u64), 0);
+
+    detector.set_flagged(&publisher, &false);
+    assert!(client.verify_view(&7u64, &publisher, &Address::generate(&env), &None));
+}
+
+#[test]
+fn test_score_anomaly_flag_with_low_reputation_pauses_campaign() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, _, reputation, detector, lifecycle) = setup_signals(&env);
+    let publisher = Address::generate(&env);
+    let viewer = Address::generate(&env);
+
+    detector.set_flagged(&publisher, &true);
+    reputation.set_score(&publisher, &250u32);
+    serve_campaign(&env, &client, &publisher, 7);
+    assert_eq!(
+        client.get_view_score(&1u64, &publisher, &viewer, &false),
+        40
+    );
+
+    assert!(!client.verify_view(&7u64, &publisher, &viewer, &None));
+    assert_eq!(lifecycle.pauses(&7u64), 1);
+}
+
+#[test]
+fn test_low_score_view_on_unserved_campaign_does_not_pause() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, _, reputation, detector, lifecycle) = setup_signals(&env);
+    let publisher = Address::generate(&env);
+
+    detector.set_flagged(&publisher, &true);
+    reputation.set_score(&publisher, &250u32);
+    assert!(!client.verify_view(&7u64, &publisher, &Address::generate(&env), &None));
+    assert_eq!(lifecycle.pauses(&7u64), 0);
+    assert_eq!(client.get_publisher_campaigns(&publisher).len(), 0);
+}
+
+#[test]
+fn test_verify_view_requires_publisher_auth() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, _) = setup(&env);
+    let publisher = Address::generate(&env);
+
+    // Anyone naming a publisher cannot report views on its behalf
+    env.set_auths(&[]);
+    assert!(client
+        .try_verify_view(&1u64, &publisher, &Address::generate(&env), &None)
+        .is_err());
+    assert_eq!(client.get_verification_stats(&1u64).total_views, 0);
+}
+
+#[test]
+fn test_low_score_views_take_a_single_pause_hold() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, _, reputation, detector, lifecycle) = setup_signals(&env);
+    let publisher = Address::generate(&env);
+
+    detector.set_flagged(&publisher, &true);
+    reputation.set_score(&publisher, &250u32);
+    serve_campaign(&env, &client, &publisher, 7);
+
+    assert!(!client.verify_view(&7u64, &publisher, &Address::generate(&env), &None));
+    assert!(!client.verify_view(&7u64, &publisher, &Address::generate(&env), &None));
+    assert_eq!(lifecycle.pauses(&7u64), 1);
+}
+
+#[test]
+fn test_low_score_view_recorded_when_campaign_already_paused() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, admin, reputation, detector, _) = setup_signals(&env);
+    let lifecycle = env.register(paused_lifecycle::AlreadyPausedLifecycle, ());
+    client.set_dependent_contracts(
+        &admin,
+        &lifecycle,
+        &Address::generate(&env),
+        &Address::generate(&env),
+    );
+
+    let publisher = Address::generate(&env);
+    detector.set_flagged(&publisher, &true);
+    reputation.set_score(&publisher, &0u32);
+    assert!(!client.verify_view(&7u64, &publisher, &Address::generate(&env), &None));
+    assert_eq!(client.get_verification_stats(&7u64).rejected_views, 1);
+}
+
+#[test]
+fn test_set_score_weights() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, admin, _, detector, _) = setup_signals(&env);
+    let publisher = Address::generate(&env);
+    let viewer = Address::generate(&env);
+    detector.set_flagged(&publisher, &true);
+
+    let mut weights = client.get_score_weights();
+    assert_eq!(weights.base_score, 90);
+    weights.anomaly_flag = 5;
+    client.set_score_weights(&admin, &weights);
+
+    assert_eq!(client.get_score_weights().anomaly_flag, 5);
+    assert_eq!(
+        client.get_view_score(&1u64, &publisher, &viewer, &false),
+        85
+    );
+}
+
+#[test]
+#[should_panic(expected = "unauthorized")]
+fn test_set_score_weights_unauthorized() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, _) = setup(&env);
+    let weights = client.get_score_weights();
+    client.set_score_weights(&Address::generate(&env), &weights);
+}
+
+#[test]
+#[should_panic(expected = "invalid weights")]
+fn test_set_score_weights_invalid() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, admin) = setup(&env);
+    let mut weights = client.get_score_weights();
+    weights.base_score = 101;
+    client.set_score_weights(&admin, &weights);
+}
+
+// ─── attested view proofs ────────────────────────────────────────────────────
+
+#[test]
+fn test_vendor_key_attests_any_publisher() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, admin) = setup(&env);
+    let vendor = Address::generate(&env);
+    let publisher = Address::generate(&env);
+    let viewer = Address::generate(&env);
+
+    let signer = SigningKey::from_bytes(&[9u8; 32]);
+    let key = register_key(
+        &env,
+        &client,
+        &admin,
+        &signer,
+        &vendor,
+        AttesterKind::Vendor,
+    );
+    let record = client.get_attestation_key(&key).unwrap();
+    assert_eq!(record.owner, vendor);
+    assert!(record.active);
+
+    let proof = sign_proof(&env, &client, &signer, &key, 3, &publisher, &viewer, 1);
+    assert!(client.verify_view(&3u64, &publisher, &viewer, &Some(proof)));
+    assert_eq!(client.get_verification_stats(&3u64).average_score, 100);
+}
+
+#[test]
+#[should_panic(expected = "key not valid for publisher")]
+fn test_publisher_key_cannot_attest_other_publisher() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, admin) = setup(&env);
+    let owner = Address::generate(&env);
+    let other = Address::generate(&env);
+    let viewer = Address::generate(&env);
+
+    let signer = SigningKey::from_bytes(&[7u8; 32]);
+    let key = register_key(
+        &env,
+        &client,
+        &admin,
+        &signer,
+        &owner,
+        AttesterKind::Publisher,
+    );
+    let proof = sign_proof(&env, &client, &signer, &key, 1, &other, &viewer, 1);
+    client.verify_view(&1u64, &other, &viewer, &Some(proof));
+}
+
+#[test]
+#[should_panic(expected = "unknown attestation key")]
+fn test_unregistered_key_rejected() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, _) = setup(&env);
+    let publisher = Address::generate(&env);
+    let viewer = Address::generate(&env);
+
+    let signer = SigningKey::from_bytes(&[7u8; 32]);
+    let key = BytesN::from_array(&env, &signer.verifying_key().to_bytes());
+    let proof = sign_proof(&env, &client, &signer, &key, 1, &publisher, &viewer, 1);
+    client.verify_view(&1u64, &publisher, &viewer, &Some(proof));
+}
+
+#[test]
+#[should_panic]
+fn test_forged_signature_rejected() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, admin) = setup(&env);
+    let publisher = Address::generate(&env);
+    let viewer = Address::generate(&env);
+
+    let signer = SigningKey::from_bytes(&[7u8; 32]);
+    let key = register_key(
+        &env,
+        &client,
+        &admin,
+        &signer,
+        &publisher,
+        AttesterKind::Publisher,
+    );
+    // Signed for campaign 1, submitted for campaign 2
+    let proof = sign_proof(&env, &client, &signer, &key, 1, &publisher, &viewer, 1);
+    client.verify_view(&2u64, &publisher, &viewer, &Some(proof));
+}
+
+#[test]
+#[should_panic(expected = "proof replayed")]
+fn test_replayed_nonce_rejected() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, admin) = setup(&env);
+    let publisher = Address::generate(&env);
+    let viewer = Address::generate(&env);
+
+    let signer = SigningKey::from_bytes(&[7u8; 32]);
+    let key = register_key(
+        &env,
+        &client,
+        &admin,
+        &signer,
+        &publisher,
+        AttesterKind::Publisher,
+    );
+    let proof = sign_proof(&env, &client, &signer, &key, 1, &publisher, &viewer, 5);
+    assert!(client.verify_view(&1u64, &publisher, &viewer, &Some(proof.clone())));
+
+    // Same proof for a new view id one second later
+    env.ledger().with_mut(|li| li.timestamp += 1);
+    client.verify_view(&1u64, &publisher, &viewer, &Some(proof));
+}
+
+#[test]
+#[should_panic(expected = "invalid proof timestamp")]
+fn test_stale_proof_rejected() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, admin) = setup(&env);
+    let publisher = Address::generate(&env);
+    let viewer = Address::generate(&env);
+
+    let signer = SigningKey::from_bytes(&[7u8; 32]);
+    let key = register_key(
+        &env,
+        &client,
+        &admin,
+        &signer,
+        &publisher,
+        AttesterKind::Publisher,
+    );
+    let proof = sign_proof(&env, &client, &signer, &key, 1, &publisher, &viewer, 1);
+    env.ledger().with_mut(|li| li.timestamp += 301);
+    client.verify_view(&1u64, &publisher, &viewer, &Some(proof));
+}
+
+#[test]
+fn test_rotate_attestation_key() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, admin) = setup(&env);
+    let publisher = Address::generate(&env);
+
+    let old_signer = SigningKey::from_bytes(&[7u8; 32]);
+    let new_signer = SigningKey::from_bytes(&[8u8; 32]);
+    let old_key = register_key(
+        &env,
+        &client,
+        &admin,
+        &old_signer,
+        &publisher,
+        AttesterKind::Publisher,
+    );
+    let new_key = BytesN::from_array(&env, &new_signer.verifying_key().to_bytes());
+
+    client.rotate_attestation_key(&publisher, &old_key, &new_key);
+    assert!(!client.get_attestation_key(&old_key).unwrap().active);
+    let rotated = client.get_attestation_key(&new_key).unwrap();
+    assert_eq!(rotated.owner, publisher);
+    assert_eq!(rotated.kind, AttesterKind::Publisher);
+
+    let viewer = Address::generate(&env);
+    let proof = sign_proof(
+        &env,
+        &client,
+        &new_signer,
+        &new_key,
+        1,
+        &publisher,
+        &viewer,
+        1,
+    );
+    assert!(client.verify_view(&1u64, &publisher, &viewer, &Some(proof)));
+}
+
+#[test]
+#[should_panic(expected = "attestation key inactive")]
+fn test_rotated_out_key_rejected() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, admin) = setup(&env);
+    let publisher = Address::generate(&env);
+    let viewer = Address::generate(&env);
+
+    let old_signer = SigningKey::from_bytes(&[7u8; 32]);
+    let old_key = register_key(
+        &env,
+        &client,
+        &admin,
+        &old_signer,
+        &publisher,
+        AttesterKind::Publisher,
+    );
+    let new_key = BytesN::from_array(&env, &[8u8; 32]);
+    client.rotate_attestation_key(&publisher, &old_key, &new_key);
+
+    let proof = sign_proof(
+        &env,
+        &client,
+        &old_signer,
+        &old_key,
+        1,
+        &publisher,
+        &viewer,
+        1,
+    );
+    client.verify_view(&1u64, &publisher, &viewer, &Some(proof));
+}
+
+#[test]
+#[should_panic(expected = "unauthorized")]
+fn test_rotate_attestation_key_stranger() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, admin) = setup(&env);
+    let publisher = Address::generate(&env);
+
+    let signer = SigningKey::from_bytes(&[7u8; 32]);
+    let key = register_key(
+        &env,
+        &client,
+        &admin,
+        &signer,
+        &publisher,
+        AttesterKind::Publisher,
+    );
+    client.rotate_attestation_key(
+        &Address::generate(&env),
+        &key,
+        &BytesN::from_array(&env, &[8u8; 32]),
+    );
+}
+
+#[test]
+fn test_revoke_attestation_key() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, admin) = setup(&env);
+    let vendor = Address::generate(&env);
+
+    let signer = SigningKey::from_bytes(&[9u8; 32]);
+    let key = register_key(
+        &env,
+        &client,
+        &admin,
+        &signer,
+        &vendor,
+        AttesterKind::Vendor,
+    );
+    client.revoke_attestation_key(&admin, &key);
+    assert!(!client.get_attestation_key(&key).unwrap().active);
+}
+
+#[test]
+#[should_panic(expected = "key already registered")]
+fn test_register_attestation_key_twice() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, admin) = setup(&env);
+    let vendor = Address::generate(&env);
+
+    let signer = SigningKey::from_bytes(&[9u8; 32]);
+    register_key(
+        &env,
+        &client,
+        &admin,
+        &signer,
+        &vendor,
+        AttesterKind::Vendor,
+    );
+    register_key(
+        &env,
+        &client,
+        &admin,
+        &signer,
+        &vendor,
+        AttesterKind::Vendor,
+    );
+}
+
+// ─── escalation ──────────────────────────────────────────────────────────────
+
+#[contract]
+pub struct StubNetwork;
+
+#[contractimpl]
+impl StubNetwork {
+    pub fn suspend_publisher(env: Env, _fraud_contract: Address, publisher: Address) {
+        env.storage().instance().set(&publisher, &true);
+    }
+
+    pub fn reinstate_publisher(env: Env, _fraud_contract: Address, publisher: Address) {
+        env.storage().instance().set(&publisher, &false);
+    }
+
+    pub fn suspended(env: Env, publisher: Address) -> bool {
+        env.storage().instance().get(&publisher).unwrap_or(false)
+    }
+}
+
+#[contract]
+pub struct StubVault;
+
+#[contractimpl]
+impl StubVault {
+    pub fn set_escrows(env: Env, beneficiary: Address, escrow_ids: Vec<u64>) {
+        env.storage().instance().set(&beneficiary, &escrow_ids);
+    }
+
+    pub fn get_beneficiary_escrows(env: Env, beneficiary: Address) -> Vec<u64> {
+        env.storage()
+            .instance()
+            .get(&beneficiary)
+            .unwrap_or(Vec::new(&env))
+    }
+
+    pub fn hold_for_fraud(env: Env, _fraud_contract: Address, escrow_id: u64) {
+        env.storage().instance().set(&escrow_id, &true);
+    }
+
+    pub fn release_fraud_hold(env: Env, _fraud_contract: Address, escrow_id: u64) {
+        env.storage().instance().set(&escrow_id, &false);
+    }
+
+    pub fn held(env: Env, escrow_id: u64) -> bool {
+        env.storage().instance().get(&escrow_id).unwrap_or(false)
+    }
+
+    pub fn set_serves(env: Env, beneficiary: Address, campaign_id: u64) {
+        env.storage()
+            .instance()
+            .set(&(symbol_short!("serves"), beneficiary, campaign_id), &true);
+    }
+
+    pub fn serves_campaign(env: Env, beneficiary: Address, campaign_id: u64) -> bool {
+        env.storage()
+            .instance()
+            .get(&(symbol_short!("serves"), beneficiary, campaign_id))
+            .unwrap_or(false)
+    }
+}
+
+/// Record in the configured StubVault that `publisher` serves `campaign_id`
+fn serve_campaign(
+    env: &Env,
+    client: &FraudPreventionContractClient,
+    publisher: &Address,
+    campaign_id: u64,
+) {
+    let vault: Address = env.as_contract(&client.address, || {
+        env.storage().instance().get(&DataKey::EscrowVault).unwrap()
+    });
+    StubVaultClient::new(env, &vault).set_serves(publisher, &campaign_id);
+}
+
+fn setup_escalation<'a>(
+    env: &'a Env,
+) -> (
+    FraudPreventionContractClient<'a>,
+    Address,
+    StubNetworkClient<'a>,
+    StubVaultClient<'a>,
+    RecordingLifecycleClient<'a>,
+    Address,
+) {
+    let (client, admin) = setup(env);
+    let network = StubNetworkClient::new(env, &env.register(StubNetwork, ()));
+    let vault = StubVaultClient::new(env, &env.register(StubVault, ()));
+    let lifecycle = RecordingLifecycleClient::new(env, &env.register(RecordingLifecycle, ()));
+    client.set_dependent_contracts(&admin, &lifecycle.address, &network.address, &vault.address);
+    client.set_suspicious_threshold(&admin, &2u64);
+
+    // Publisher serves campaigns 1 and 2 and benefits from escrows 10 and 11
+    let publisher = Address::generate(env);
+    vault.set_serves(&publisher, &1u64);
+    vault.set_serves(&publisher, &2u64);
+    client.verify_view(&1u64, &publisher, &Address::generate(env), &None);
+    client.verify_view(&2u64, &publisher, &Address::generate(env), &None);
+    vault.set_escrows(&publisher, &soroban_sdk::vec![env, 10u64, 11u64]);
+
+    (client, admin, network, vault, lifecycle, publisher)
+}
+
+#[test]
+fn test_threshold_crossing_escalates() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, admin, network, vault, lifecycle, publisher) = setup_escalation(&env);
+    assert_eq!(
+        client.get_publisher_campaigns(&publisher),
+        soroban_sdk::vec![&env, 1u64, 2u64]
+    );
+
+    client.flag_suspicious(&admin, &publisher);
+    client.flag_suspicious(&admin, &publisher);
+    assert!(client.get_escalation(&publisher).is_none());
+    assert!(!network.suspended(&publisher));
+
+    client.flag_suspicious(&admin, &publisher);
+    assert!(client.is_publisher_suspended(&publisher));
+    assert!(network.suspended(&publisher));
+    assert!(vault.held(&10u64) && vault.held(&11u64));
+    assert_eq!(lifecycle.pauses(&1u64), 1);
+    assert_eq!(lifecycle.pauses(&2u64), 1);
+
+    let escalation = client.get_escalation(&publisher).unwrap();
+    assert!(escalation.suspended_in_network);
+    assert_eq!(
+        escalation.held_escrows,
+        soroban_sdk::vec![&env, 10u64, 11u64]
+    );
+    assert_eq!(
+        escalation.paused_campaigns,
+        soroban_sdk::vec![&env, 1u64, 2u64]
+    );
+
+    // Further flags do not repeat the actions
+    client.flag_suspicious(&admin, &publisher);
+    assert_eq!(lifecycle.pauses(&1u64), 1);
+}
+
+#[test]
+fn test_clear_flag_reverses_escalation() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, admin, network, vault, lifecycle, publisher) = setup_escalation(&env);
+    client.suspend_publisher(&admin, &publisher);
+    assert!(network.suspended(&publisher));
+
+    client.clear_flag(&admin, &publisher);
+    assert!(!client.is_publisher_suspended(&publisher));
+    assert!(client.get_escalation(&publisher).is_none());
+    assert!(!network.suspended(&publisher));
+    assert!(!vault.held(&10u64) && !vault.held(&11u64));
+    assert_eq!(lifecycle.resumes(&1u64), 1);
+    assert_eq!(lifecycle.resumes(&2u64), 1);
+}
+
+#[test]
+fn test_escalation_policy_limits_actions() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, admin, network, vault, lifecycle, publisher) = setup_escalation(&env);
+    client.set_escalation_policy(
+        &admin,
+        &EscalationPolicy {
+            auto_suspend: true,
+            hold_escrows: false,
+            pause_campaigns: false,
+        },
+    );
+
+    for _ in 0..3 {
+        client.flag_suspicious(&admin, &publisher);
+    }
+    assert!(network.suspended(&publisher));
+    assert!(!vault.held(&10u64));
+    assert_eq!(lifecycle.pauses(&1u64), 0);
+}
+
+#[test]
+fn test_auto_suspend_disabled() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, admin, network, _, _, publisher) = setup_escalation(&env);
+    let mut policy = client.get_escalation_policy();
+    policy.auto_suspend = false;
+    client.set_escalation_policy(&admin, &policy);
+
+    for _ in 0..3 {
+        client.flag_suspicious(&admin, &publisher);
+    }
+    assert!(!client.is_publisher_suspended(&publisher));
+    assert!(!network.suspended(&publisher));
+    assert!(client.get_escalation(&publisher).is_none());
+}
+
+#[test]
+fn test_escalation_survives_failing_contract() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, admin, _, vault, lifecycle, publisher) = setup_escalation(&env);
+    // Network address without a deployed contract
+    client.set_dependent_contracts(
+        &admin,
+        &lifecycle.address,
+        &Address::generate(&env),
+        &vault.address,
+    );
+
+    client.suspend_publisher(&admin, &publisher);
+    let escalation = client.get_escalation(&publisher).unwrap();
+    assert!(!escalation.suspended_in_network);
+    assert!(vault.held(&10u64));
+    assert_eq!(lifecycle.pauses(&1u64), 1);
+}
+
+#[test]
+#[should_panic(expected = "unauthorized")]
+fn test_set_escalation_policy_unauthorized() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, _) = setup(&env);
+    let policy = client.get_escalation_policy();
+    client.set_escalation_policy(&Address::generate(&env), &policy);
+}
+
+// ─── viewer privacy ──────────────────────────────────────────────────────────
+
+#[contract]
+pub struct MockPrivacyLayer;
+
+#[contractimpl]
+impl MockPrivacyLayer {
+    pub fn set_consent(env: Env, user: Address, granted: bool) {
+        env.storage().instance().set(&user, &granted);
+    }
+
+    pub fn has_consent(env: Env, user: Address, consent_type: soroban_sdk::String) -> bool {
+        consent_type == soroban_sdk::String::from_str(&env, "analytics")
+            && env.storage().instance().get(&user).unwrap_or(false)
+    }
+}
+
+#[test]
+fn test_viewer_hash_rotates_per_campaign_and_epoch() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, admin) = setup(&env);
+    let viewer = Address::generate(&env);
+
+    let hash = client.get_viewer_hash(&1u64, &viewer);
+    assert_eq!(hash, client.get_viewer_hash(&1u64, &viewer));
+    assert_ne!(hash, client.get_viewer_hash(&2u64, &viewer));
+    assert_ne!(
+        hash,
+        client.get_viewer_hash(&1u64, &Address::generate(&env))
+    );
+
+    // Same epoch, one hour later
+    env.ledger().with_mut(|li| li.timestamp += 3600);
+    assert_eq!(hash, client.get_viewer_hash(&1u64, &viewer));
+
+    env.ledger().with_mut(|li| li.timestamp += 86_400);
+    assert_ne!(hash, client.get_viewer_hash(&1u64, &viewer));
+
+    let current = client.get_viewer_hash(&1u64, &viewer);
+    client.rotate_salt(&admin);
+    assert_ne!(current, client.get_viewer_hash(&1u64, &viewer));
+}
+
+#[test]
+#[should_panic(expected = "duplicate view")]
+fn test_dedup_uses_viewer_hash() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, _) = setup(&env);
+    let publisher = Address::generate(&env);
+    let viewer = Address::generate(&env);
+
+    client.verify_view(&1u64, &publisher, &viewer, &None);
+    client.verify_view(&1u64, &publisher, &viewer, &None);
+}
+
+#[test]
+#[should_panic(expected = "rate limit exceeded")]
+fn test_rate_limit_uses_viewer_hash() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, _) = setup(&env);
+    let viewer = Address::generate(&env);
+
+    for _ in 0..11 {
+        client.verify_view(&1u64, &Address::generate(&env), &viewer, &None);
+    }
+}
+
+#[test]
+#[should_panic(expected = "viewer rate limit exceeded")]
+fn test_viewer_rate_limit_spans_campaigns() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, _) = setup(&env);
+    let viewer = Address::generate(&env);
+
+    // Spreading views over campaigns doesn't reset the limit
+    for campaign_id in 0..10u64 {
+        client.verify_view(&campaign_id, &Address::generate(&env), &viewer, &None);
+    }
+    env.ledger().with_mut(|li| li.timestamp += 60);
+    client.verify_view(&10u64, &Address::generate(&env), &viewer, &None);
+}
+
+#[test]
+fn test_verify_view_with_consent() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, admin) = setup(&env);
+    let privacy = MockPrivacyLayerClient::new(&env, &env.register(MockPrivacyLayer, ()));
+    client.set_privacy_layer(&admin, &privacy.address);
+
+    let viewer = Address::generate(&env);
+    privacy.set_consent(&viewer, &true);
+    assert!(client.verify_view(&1u64, &Address::generate(&env), &viewer, &None));
+}
+
+#[test]
+#[should_panic(expected = "viewer consent required")]
+fn test_verify_view_without_consent() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, admin) = setup(&env);
+    let privacy = MockPrivacyLayerClient::new(&env, &env.register(MockPrivacyLayer, ()));
+    client.set_privacy_layer(&admin, &privacy.address);
+
+    client.verify_view(
+        &1u64,
+        &Address::generate(&env),
+        &Address::generate(&env),
+        &None,
+    );
+}
+
+#[test]
+#[should_panic(expected = "invalid salt epoch")]
+fn test_set_salt_epoch_too_short() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, admin) = setup(&env);
+    client.set_salt_epoch(&admin, &60u64);
+}
+
+// ─── sliding-window rate limits ──────────────────────────────────────────────
+
+#[test]
+#[should_panic(expected = "viewer rate limit exceeded")]
+fn test_viewer_limit_slides_across_bucket_edge() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, _) = setup(&env);
+    let viewer = Address::generate(&env);
+
+    env.ledger().with_mut(|li| li.timestamp = 3_599);
+    for _ in 0..10 {
+        client.verify_view(&1u64, &Address::generate(&env), &viewer, &None);
+    }
+    // A fixed hourly bucket would reset here
+    env.ledger().with_mut(|li| li.timestamp = 3_600);
+    client.verify_view(&1u64, &Address::generate(&env), &viewer, &None);
+}
+
+#[test]
+fn test_viewer_rate_key_scoped_to_bucket() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, _) = setup(&env);
+    let viewer = Address::generate(&env);
+
+    env.ledger().with_mut(|li| li.timestamp = 3_599);
+    client.verify_view(&1u64, &Address::generate(&env), &viewer, &None);
+
+    env.as_contract(&client.address, || {
+        let subject = RateSubject::Viewer(FraudPreventionContract::_viewer_rate_key(&env, &viewer));
+        // The stable viewer key never reaches storage
+        assert!(!env
+            .storage()
+            .temporary()
+            .has(&DataKey::RateBucket(subject.clone(), HOUR, 0)));
+        let this_hour = FraudPreventionContract::_bucket_key(&env, &subject, HOUR, 0);
+        assert!(env.storage().temporary().has(&this_hour));
+        let next_hour = FraudPreventionContract::_bucket_key(&env, &subject, HOUR, 1);
+        assert_ne!(this_hour.to_xdr(&env), next_hour.to_xdr(&env));
+    });
+}
+
+#[test]
+fn test_viewer_limit_decays_over_window() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, _) = setup(&env);
+    let publisher = Address::generate(&env);
+    let viewer = Address::generate(&env);
+
+    env.ledger().with_mut(|li| li.timestamp = 3_599);
+    for _ in 0..10 {
+        client.verify_view(&1u64, &Address::generate(&env), &viewer, &None);
+    }
+    assert!(client.get_rate_status(&1u64, &publisher, &viewer).limited);
+
+    // Half way through the next hour, half of the previous bucket still counts
+    env.ledger().with_mut(|li| li.timestamp = 5_400);
+    let status = client.get_rate_status(&1u64, &publisher, &viewer);
+    assert_eq!(status.viewer.hour, 5);
+    assert!(!status.limited);
+    client.verify_view(&1u64, &publisher, &viewer, &None);
+}
+
+#[test]
+#[should_panic(expected = "publisher rate limit exceeded")]
+fn test_publisher_minute_limit() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, admin) = setup(&env);
+    client.set_rate_limit(
+        &admin,
+        &RateScope::Publisher,
+        &RateLimit {
+            per_minute: 3,
+            per_hour: 0,
+            per_day: 0,
+        },
+    );
+    let publisher = Address::generate(&env);
+
+    for _ in 0..4 {
+        client.verify_view(&1u64, &publisher, &Address::generate(&env), &None);
+    }
+}
+
+#[test]
+fn test_publisher_campaign_day_limit() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, admin) = setup(&env);
+    client.set_rate_limit(
+        &admin,
+        &RateScope::PublisherCampaign,
+        &RateLimit {
+            per_minute: 0,
+            per_hour: 0,
+            per_day: 2,
+        },
+    );
+    let publisher = Address::generate(&env);
+    let viewer = Address::generate(&env);
+
+    client.verify_view(&1u64, &publisher, &Address::generate(&env), &None);
+    client.verify_view(&1u64, &publisher, &Address::generate(&env), &None);
+    let status = client.get_rate_status(&1u64, &publisher, &viewer);
+    assert_eq!(status.pair.day, 2);
+    assert_eq!(status.publisher.day, 0); // publisher scope has no limit, so is untracked
+    assert!(status.limited);
+
+    // Other campaigns of the same publisher are unaffected
+    assert!(!client.get_rate_status(&2u64, &publisher, &viewer).limited);
+    assert!(client.verify_view(&2u64, &publisher, &viewer, &None));
+    assert!(client
+        .try_verify_view(&1u64, &publisher, &Address::generate(&env), &None)
+        .is_err());
+}
+
+#[test]
+#[should_panic(expected = "unauthorized")]
+fn test_set_rate_limit_unauthorized() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, _) = setup(&env);
+    client.set_rate_limit(
+        &Address::generate(&env),
+        &RateScope::Viewer,
+        &RateLimit {
+            per_minute: 1,
+            per_hour: 1,
+            per_day: 1,
+        },
+    );
+}
+
+#[test]
+fn test_flag_suspicious_rejects_spoofed_caller() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, admin) = setup(&env);
+    let publisher = Address::generate(&env);
+
+    // Naming the admin as caller is not enough without the admin's signature
+    env.set_auths(&[]);
+    assert!(client.try_flag_suspicious(&admin, &publisher).is_err());
+    assert!(client.get_suspicious_status(&publisher).is_none());
+}
@@ -1,5 +1,10 @@
 //! PulsarTrack - Governance Core (Soroban)
 //! Core governance parameters, roles, and access control on Stellar.
+//!
+//! Once `set_governance` has linked a governance-dao, parameters can only be
+//! changed by the governor (the DAO's timelock, executing a passed proposal),
+//! and the DAO reports proposals opening and closing so `max_active_proposals`
+//! is enforced through `ActiveProposalCount`.
 
 #![no_std]
 use soroban_sdk::{contract, contractimpl, contracttype, symbol_short, Address, Env};
@@ -42,6 +47,8 @@ pub enum DataKey {
     RoleGrant(Address, Role),
     RoleCount(Role),
     ActiveProposalCount,
+    Dao,
+    Governor,
 }
 
 const INSTANCE_LIFETIME_THRESHOLD: u32 = 17_280;
@@ -169,7 +176,9 @@ impl GovernanceCoreContract {
         }
     }
 
-    pub fn update_params(env: Env, admin: Address, params: GovernanceParams) {
+    /// Link the governance-dao and the governor allowed to change parameters.
+    /// Can only be done once; afterwards the admin can no longer update params.
+    pub fn set_governance(env: Env, admin: Address, dao: Address, governor: Address) {
         env.storage()
             .instance()
             .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
@@ -178,9 +187,102 @@ impl GovernanceCoreContract {
         if admin != stored_admin {
             panic!("unauthorized");
         }
+        if env.storage().instance().has(&DataKey::Governor) {
+            panic!("governance already set");
+        }
+        env.storage().instance().set(&DataKey::Dao, &dao);
+        env.storage().instance().set(&DataKey::Governor, &governor);
+    }
+
+    /// Update parameters: the governor once governance is set, the admin before
+    pub fn update_params(env: Env, caller: Address, params: GovernanceParams) {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        caller.require_auth();
+        let authority: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Governor)
+            .unwrap_or_else(|| env.storage().instance().get(&DataKey::Admin).unwrap());
+        if caller != authority {
+            panic!("unauthorized");
+        }
+        if params.voting_period_ledgers == 0
+            || params.quorum_pct > 100
+            || params.pass_threshold_pct == 0
+            || params.pass_threshold_pct > 100
+        {
+            panic!("invalid params");
+        }
         env.storage()
             .instance()
             .set(&DataKey::GovernanceParams, &params);
+
+        env.events()
+            .publish((symbol_short!("params"), symbol_short!("updated")), caller);
+    }
+
+    /// Called by the DAO when a proposal opens for voting
+    pub fn open_proposal(env: Env, dao: Address) {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        Self::_require_dao(&env, &dao);
+
+        let params = Self::get_params(env.clone());
+        let count: u32 = env
+            .storage()
+            .instance()
+            .get(&DataKey::ActiveProposalCount)
+            .unwrap_or(0);
+        if count >= params.max_active_proposals {
+            panic!("too many active proposals");
+        }
+        env.storage()
+            .instance()
+            .set(&DataKey::ActiveProposalCount, &(count + 1));
+    }
+
+    /// Called by the DAO when a proposal stops being active
+    pub fn close_proposal(env: Env, dao: Address) {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        Self::_require_dao(&env, &dao);
+
+        let count: u32 = env
+            .storage()
+            .instance()
+            .get(&DataKey::ActiveProposalCount)
+            .unwrap_or(0);
+        env.storage()
+            .instance()
+            .set(&DataKey::ActiveProposalCount, &count.saturating_sub(1));
+    }
+
+    pub fn get_dao(env: Env) -> Option<Address> {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        env.storage().instance().get(&DataKey::Dao)
+    }
+
+    pub fn get_governor(env: Env) -> Option<Address> {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        env.storage().instance().get(&DataKey::Governor)
+    }
+
+    pub fn get_active_proposal_count(env: Env) -> u32 {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        env.storage()
+            .instance()
+            .get(&DataKey::ActiveProposalCount)
+            .unwrap_or(0)
     }
 
     pub fn get_params(env: Env) -> GovernanceParams {
@@ -215,6 +317,14 @@ impl GovernanceCoreContract {
     pub fn accept_admin(env: Env, new_admin: Address) {
         pulsar_common_admin::accept_admin(&env, &DataKey::Admin, &DataKey::PendingAdmin, new_admin);
     }
+
+    fn _require_dao(env: &Env, dao: &Address) {
+        dao.require_auth();
+        let stored_dao: Option<Address> = env.storage().instance().get(&DataKey::Dao);
+        if stored_dao.as_ref() != Some(dao) {
+            panic!("unauthorized");
+        }
+    }
 }
 
 mod test;
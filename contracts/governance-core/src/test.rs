@@ -120,3 +120,71 @@ fn test_get_role_grant_nonexistent() {
         .get_role_grant(&Address::generate(&env), &Role::Operator)
         .is_none());
 }
+
+fn params(max_active_proposals: u32) -> GovernanceParams {
+    GovernanceParams {
+        min_proposal_threshold: 1000,
+        voting_period_ledgers: 100,
+        quorum_pct: 10,
+        pass_threshold_pct: 51,
+        timelock_ledgers: 10,
+        max_active_proposals,
+    }
+}
+
+#[test]
+fn test_governor_controls_params_after_set_governance() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (c, admin) = setup(&env);
+    let dao = Address::generate(&env);
+    let governor = Address::generate(&env);
+    assert_eq!(c.get_governor(), None);
+    c.set_governance(&admin, &dao, &governor);
+    assert_eq!(c.get_dao(), Some(dao));
+    assert_eq!(c.get_governor(), Some(governor.clone()));
+
+    assert!(c.try_update_params(&admin, &params(5)).is_err());
+    c.update_params(&governor, &params(5));
+    assert_eq!(c.get_params().max_active_proposals, 5);
+}
+
+#[test]
+#[should_panic(expected = "governance already set")]
+fn test_set_governance_once() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (c, admin) = setup(&env);
+    let dao = Address::generate(&env);
+    c.set_governance(&admin, &dao, &dao);
+    c.set_governance(&admin, &dao, &admin);
+}
+
+#[test]
+#[should_panic(expected = "invalid params")]
+fn test_update_params_rejects_invalid() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (c, admin) = setup(&env);
+    let mut p = params(5);
+    p.quorum_pct = 101;
+    c.update_params(&admin, &p);
+}
+
+#[test]
+fn test_active_proposal_limit() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (c, admin) = setup(&env);
+    let dao = Address::generate(&env);
+    c.update_params(&admin, &params(2));
+    c.set_governance(&admin, &dao, &dao);
+
+    c.open_proposal(&dao);
+    c.open_proposal(&dao);
+    assert!(c.try_open_proposal(&dao).is_err());
+    c.close_proposal(&dao);
+    c.open_proposal(&dao);
+    assert_eq!(c.get_active_proposal_count(), 2);
+    assert!(c.try_open_proposal(&admin).is_err());
+}
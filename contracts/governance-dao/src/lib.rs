@@ -4,11 +4,45 @@
 //! Events:
 //! - ("proposal", "created"): [proposal_id: u64, proposer: Address]
 //! - ("gov", "voted"): [proposal_id: u64, voter: Address, power: i128]
+//! - ("gov", "delegate"): [proposal_id: u64, delegator: Address, delegate: Address]
 //! - ("proposal", "finalized"): [proposal_id: u64, status: ProposalStatus]
+//!
+//! Voting power is read from the governance token's checkpoints as of the end
+//! of the ledger before the proposal was created, so tokens moved after that
+//! cannot be voted again. A vote can be changed until the voting period ends,
+//! cast off-chain as an ed25519-signed ballot relayed by anyone, or handed to
+//! another voter for a single proposal with `delegate_vote`.
+//!
+//! Executable proposals carry a list of calls. When such a proposal passes,
+//! each call is queued into the configured timelock-executor, and once the
+//! timelock's eta has passed anyone may trigger `execute_proposal`. The DAO
+//! must be both the timelock's admin and its executor.
+//!
+//! With a governance-core configured, proposals take their voting period,
+//! quorum, pass threshold, proposer minimum and timelock delay from its live
+//! `GovernanceParams` instead of the values given at initialization, opening
+//! a proposal counts against its `max_active_proposals`, and its moderators
+//! may cancel proposals.
+//!
+//! Voting strategies, chosen per proposal:
+//! - TokenWeighted: one vote per token of checkpointed voting power.
+//! - Quadratic: the square root of that power, for identity-registry verified
+//!   voters only; quorum is measured against the square root of total supply.
+//! - Conviction: continuous funding requests paid from a multisig-treasury the
+//!   DAO is a signer of. Supporters lock governance tokens in the DAO; each
+//!   period conviction moves towards the locked total, keeping
+//!   `retention_bps` of its previous value. Once conviction reaches
+//!   `base_threshold_bps` plus the request's share of the treasury (both as a
+//!   share of total supply), anyone can submit the payment to the treasury.
+//!
+//! With `set_ve_voting` enabled, proposals created afterwards weigh votes by
+//! the governance token's vote-escrowed power at the same snapshot ledger and
+//! measure quorum against its total ve supply.
 
 #![no_std]
 use soroban_sdk::{
-    contract, contractimpl, contracttype, symbol_short, token, Address, Env, String,
+    contract, contractimpl, contracttype, symbol_short, token, xdr::ToXdr, Address, Bytes, BytesN,
+    Env, IntoVal, String, Symbol, Val, Vec,
 };
 
 // ============================================================
@@ -20,12 +54,21 @@ use soroban_sdk::{
 pub enum ProposalStatus {
     Active,
     Passed,
+    Queued, // passed, calls waiting in the timelock
     Rejected,
     Executed,
     Cancelled,
     Expired,
 }
 
+#[contracttype]
+#[derive(Clone, PartialEq)]
+pub enum VotingStrategy {
+    TokenWeighted,
+    Quadratic,
+    Conviction,
+}
+
 #[contracttype]
 #[derive(Clone)]
 pub enum VoteChoice {
@@ -34,6 +77,36 @@ pub enum VoteChoice {
     Abstain,
 }
 
+/// Mirror of governance-core's `GovernanceParams`
+#[contracttype]
+#[derive(Clone)]
+pub struct GovernanceParams {
+    pub min_proposal_threshold: i128,
+    pub voting_period_ledgers: u32,
+    pub quorum_pct: u32,
+    pub pass_threshold_pct: u32,
+    pub timelock_ledgers: u32,
+    pub max_active_proposals: u32,
+}
+
+/// Mirror of governance-core's `Role`
+#[contracttype]
+#[derive(Clone, PartialEq)]
+pub enum Role {
+    Admin,
+    Moderator,
+    Oracle,
+    Operator,
+}
+
+#[contracttype]
+#[derive(Clone)]
+pub struct ProposalCall {
+    pub contract: Address,
+    pub function: Symbol,
+    pub args: Vec<Val>,
+}
+
 #[contracttype]
 #[derive(Clone)]
 pub struct Proposal {
@@ -51,6 +124,53 @@ pub struct Proposal {
     pub end_ledger: u32,
     pub created_at: u64,
     pub executed_at: Option<u64>,
+    pub calls: Vec<ProposalCall>,
+    pub timelock_entries: Vec<u64>, // one per call, once queued
+    pub strategy: VotingStrategy,
+    pub ve_voting: bool, // weigh votes by governance-token ve-power
+}
+
+/// Proposal layout before calls, voting strategies and ve-voting; such
+/// records stay under `DataKey::Proposal` and are upgraded when next saved.
+#[contracttype]
+#[derive(Clone)]
+pub struct LegacyProposal {
+    pub proposer: Address,
+    pub title: String,
+    pub description: String,
+    pub target_contract: Option<Address>,
+    pub status: ProposalStatus,
+    pub votes_for: i128,
+    pub votes_against: i128,
+    pub votes_abstain: i128,
+    pub quorum_bps: u32,
+    pub threshold_pct: u32,
+    pub start_ledger: u32,
+    pub end_ledger: u32,
+    pub created_at: u64,
+    pub executed_at: Option<u64>,
+}
+
+#[contracttype]
+#[derive(Clone)]
+pub struct ConvictionConfig {
+    pub treasury: Address, // multisig-treasury with the DAO as a signer
+    pub funding_token: Address,
+    pub period_ledgers: u32,
+    pub retention_bps: u32,      // share of conviction kept each period
+    pub base_threshold_bps: u32, // of total supply
+    pub max_share_bps: u32,      // largest request, as a share of the treasury
+}
+
+#[contracttype]
+#[derive(Clone)]
+pub struct FundingRequest {
+    pub recipient: Address,
+    pub amount: i128,
+    pub staked: i128,
+    pub conviction: i128,
+    pub last_period: u64,
+    pub treasury_tx: Option<u64>,
 }
 
 #[contracttype]
@@ -76,9 +196,26 @@ pub enum DataKey {
     QuorumBps,
     PassThreshold,
     ProposerMinTokens,
-    Proposal(u64),
+    Proposal(u64),   // LegacyProposal, written before executable proposals
+    ProposalV2(u64), // Proposal
     Vote(u64, Address),
     HasVoted(u64, Address),
+    Timelock,
+    TimelockDelay,
+    GovernanceCore,
+    IdentityRegistry,
+    VeVoting,
+    ConvictionConfig,
+    Funding(u64),          // proposal_id
+    Support(u64, Address), // proposal_id, supporter -> locked tokens
+    VoteKey(BytesN<32>),   // ed25519 key -> voter
+    BallotNonce(Address),
+    VoteDelegate(u64, Address),   // proposal_id, delegator -> delegate
+    VoteDelegators(u64, Address), // proposal_id, delegate -> Vec<Address>
+    DelegatedVotes(u64, Address), // proposal_id, delegate -> delegators' snapshot power
+    CoreSlot(u64),                // proposal_id -> holds a governance-core active slot
+    LinearVote(u64, Address),     // proposal_id, voter -> pre-sqrt power
+    LinearTurnout(u64),           // proposal_id -> For + Against pre-sqrt power
 }
 
 // ============================================================
@@ -89,6 +226,13 @@ const INSTANCE_LIFETIME_THRESHOLD: u32 = 17_280;
 const INSTANCE_BUMP_AMOUNT: u32 = 86_400;
 const PERSISTENT_LIFETIME_THRESHOLD: u32 = 34_560;
 const PERSISTENT_BUMP_AMOUNT: u32 = 259_200;
+/// Approximate ledger close time, to turn governance-core's timelock_ledgers
+/// into the timelock-executor's delay in seconds
+const LEDGER_SECONDS: u64 = 5;
+/// Seconds the treasury transaction for a funded request stays open
+const FUNDING_TX_EXPIRY: u64 = 604_800;
+/// Most accounts that may delegate one proposal to the same delegate
+const MAX_VOTE_DELEGATORS: u32 = 100;
 
 #[contract]
 pub struct GovernanceDaoContract;
@@ -133,6 +277,116 @@ impl GovernanceDaoContract {
             .set(&DataKey::ProposerMinTokens, &proposer_min);
     }
 
+    /// Set the timelock-executor passed proposals are queued into (admin, or
+    /// governance-core's governor once a core is set)
+    pub fn set_timelock(env: Env, admin: Address, timelock: Address, delay_secs: u64) {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        Self::_require_config_authority(&env, &admin);
+        // Queued calls are queued, executed and cancelled by the DAO itself
+        let this = env.current_contract_address();
+        let timelock_admin: Address =
+            env.invoke_contract(&timelock, &Symbol::new(&env, "get_admin"), Vec::new(&env));
+        let timelock_executor: Address = env.invoke_contract(
+            &timelock,
+            &Symbol::new(&env, "get_executor"),
+            Vec::new(&env),
+        );
+        if timelock_admin != this || timelock_executor != this {
+            panic!("dao must be timelock admin and executor");
+        }
+        let (min_delay, max_delay) = Self::_timelock_delay_bounds(&env, &timelock);
+        if delay_secs < min_delay || delay_secs > max_delay {
+            panic!("invalid delay");
+        }
+        env.storage().instance().set(&DataKey::Timelock, &timelock);
+        env.storage()
+            .instance()
+            .set(&DataKey::TimelockDelay, &delay_secs);
+    }
+
+    /// Take governance parameters from governance-core from now on (admin only,
+    /// once). The core must name this DAO as its dao and this DAO or its
+    /// timelock as governor; parameters and voting configuration then change
+    /// only through that governor.
+    pub fn set_governance_core(env: Env, admin: Address, governance_core: Address) {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        admin.require_auth();
+        let stored_admin: Address = env.storage().instance().get(&DataKey::Admin).unwrap();
+        if admin != stored_admin {
+            panic!("unauthorized");
+        }
+        if env.storage().instance().has(&DataKey::GovernanceCore) {
+            panic!("governance core already set");
+        }
+        let this = env.current_contract_address();
+        let core_dao: Option<Address> = env.invoke_contract(
+            &governance_core,
+            &Symbol::new(&env, "get_dao"),
+            Vec::new(&env),
+        );
+        let governor: Option<Address> = env.invoke_contract(
+            &governance_core,
+            &Symbol::new(&env, "get_governor"),
+            Vec::new(&env),
+        );
+        let timelock: Option<Address> = env.storage().instance().get(&DataKey::Timelock);
+        if core_dao != Some(this.clone())
+            || governor.is_none()
+            || (governor != Some(this) && governor != timelock)
+        {
+            panic!("dao must control governance core");
+        }
+        env.storage()
+            .instance()
+            .set(&DataKey::GovernanceCore, &governance_core);
+    }
+
+    /// Set the identity-registry quadratic voters must be verified in (admin,
+    /// or governance-core's governor once a core is set)
+    pub fn set_identity_registry(env: Env, admin: Address, identity_registry: Address) {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        Self::_require_config_authority(&env, &admin);
+        env.storage()
+            .instance()
+            .set(&DataKey::IdentityRegistry, &identity_registry);
+    }
+
+    /// Weigh votes on new proposals by governance-token ve-power instead of
+    /// checkpointed votes, with quorum measured against total ve supply
+    /// (admin, or governance-core's governor once a core is set)
+    pub fn set_ve_voting(env: Env, admin: Address, enabled: bool) {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        Self::_require_config_authority(&env, &admin);
+        env.storage().instance().set(&DataKey::VeVoting, &enabled);
+    }
+
+    /// Configure conviction voting for funding requests (admin, or
+    /// governance-core's governor once a core is set)
+    pub fn set_conviction_config(env: Env, admin: Address, config: ConvictionConfig) {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        Self::_require_config_authority(&env, &admin);
+        if config.period_ledgers == 0
+            || config.retention_bps == 0
+            || config.retention_bps >= 10_000
+            || config.max_share_bps > 10_000
+        {
+            panic!("invalid conviction config");
+        }
+        env.storage()
+            .instance()
+            .set(&DataKey::ConvictionConfig, &config);
+    }
+
     /// Create a new governance proposal
     pub fn create_proposal(
         env: Env,
@@ -146,185 +400,410 @@ impl GovernanceDaoContract {
             .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
         proposer.require_auth();
 
-        // Enforce minimum token requirement for proposal creation
-        let min_tokens: i128 = env
-            .storage()
+        Self::_create_proposal(
+            &env,
+            proposer,
+            title,
+            description,
+            target_contract,
+            Vec::new(&env),
+            VotingStrategy::TokenWeighted,
+        )
+    }
+
+    /// Create a proposal whose calls are queued into the timelock if it passes
+    pub fn create_executable_proposal(
+        env: Env,
+        proposer: Address,
+        title: String,
+        description: String,
+        calls: Vec<ProposalCall>,
+    ) -> u64 {
+        env.storage()
             .instance()
-            .get(&DataKey::ProposerMinTokens)
-            .unwrap_or(0);
-        if min_tokens > 0 {
-            let gov_token: Address = env
-                .storage()
-                .instance()
-                .get(&DataKey::GovernanceToken)
-                .unwrap();
-            let balance = token::Client::new(&env, &gov_token).balance(&proposer);
-            if balance < min_tokens {
-                panic!("insufficient tokens to create proposal");
-            }
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        proposer.require_auth();
+
+        if calls.is_empty() {
+            panic!("no calls");
+        }
+        if !env.storage().instance().has(&DataKey::Timelock) {
+            panic!("timelock not configured");
         }
 
-        let counter: u64 = env
-            .storage()
-            .instance()
-            .get(&DataKey::ProposalCounter)
-            .unwrap_or(0);
-        let proposal_id = counter + 1;
+        Self::_create_proposal(
+            &env,
+            proposer,
+            title,
+            description,
+            None,
+            calls,
+            VotingStrategy::TokenWeighted,
+        )
+    }
 
-        let voting_period: u32 = env
-            .storage()
+    /// Create a quadratically voted proposal, optionally with calls for the
+    /// timelock
+    pub fn create_quadratic_proposal(
+        env: Env,
+        proposer: Address,
+        title: String,
+        description: String,
+        calls: Vec<ProposalCall>,
+    ) -> u64 {
+        env.storage()
             .instance()
-            .get(&DataKey::VotingPeriod)
-            .unwrap_or(1_000);
-        let quorum_bps: u32 = env
-            .storage()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        proposer.require_auth();
+
+        if !env.storage().instance().has(&DataKey::IdentityRegistry) {
+            panic!("identity registry not configured");
+        }
+        if !calls.is_empty() && !env.storage().instance().has(&DataKey::Timelock) {
+            panic!("timelock not configured");
+        }
+
+        Self::_create_proposal(
+            &env,
+            proposer,
+            title,
+            description,
+            None,
+            calls,
+            VotingStrategy::Quadratic,
+        )
+    }
+
+    /// Request `amount` of the funding token from the treasury for `recipient`,
+    /// decided by conviction voting
+    pub fn create_funding_proposal(
+        env: Env,
+        proposer: Address,
+        title: String,
+        description: String,
+        recipient: Address,
+        amount: i128,
+    ) -> u64 {
+        env.storage()
             .instance()
-            .get(&DataKey::QuorumBps)
-            .unwrap_or(0);
-        let threshold: u32 = env
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        proposer.require_auth();
+
+        let config: ConvictionConfig = env
             .storage()
             .instance()
-            .get(&DataKey::PassThreshold)
-            .unwrap_or(51);
+            .get(&DataKey::ConvictionConfig)
+            .expect("conviction voting not configured");
+        if amount <= 0 {
+            panic!("invalid amount");
+        }
+        // Rejects requests above max_share_bps of the treasury up front
+        Self::_required_conviction(&env, &config, amount);
 
-        let start = env.ledger().sequence();
-        let proposal = Proposal {
-            proposer: proposer.clone(),
+        let proposal_id = Self::_create_proposal(
+            &env,
+            proposer,
             title,
             description,
-            target_contract,
-            status: ProposalStatus::Active,
-            votes_for: 0,
-            votes_against: 0,
-            votes_abstain: 0,
-            quorum_bps,
-            threshold_pct: threshold,
-            start_ledger: start,
-            end_ledger: start + voting_period,
-            created_at: env.ledger().timestamp(),
-            executed_at: None,
-        };
+            None,
+            Vec::new(&env),
+            VotingStrategy::Conviction,
+        );
 
-        let _ttl_key = DataKey::Proposal(proposal_id);
-        env.storage().persistent().set(&_ttl_key, &proposal);
+        let request = FundingRequest {
+            recipient,
+            amount,
+            staked: 0,
+            conviction: 0,
+            last_period: env.ledger().sequence() as u64 / config.period_ledgers as u64,
+            treasury_tx: None,
+        };
+        let _ttl_key = DataKey::Funding(proposal_id);
+        env.storage().persistent().set(&_ttl_key, &request);
         env.storage().persistent().extend_ttl(
             &_ttl_key,
             PERSISTENT_LIFETIME_THRESHOLD,
             PERSISTENT_BUMP_AMOUNT,
         );
-        env.storage()
-            .instance()
-            .set(&DataKey::ProposalCounter, &proposal_id);
-
-        env.events().publish(
-            (symbol_short!("proposal"), symbol_short!("created")),
-            (proposal_id, proposer),
-        );
 
         proposal_id
     }
 
-    /// Cast a vote on a proposal
-    pub fn cast_vote(env: Env, voter: Address, proposal_id: u64, choice: VoteChoice, power: i128) {
+    /// Cast a vote on a proposal, or change it before the voting period ends
+    pub fn cast_vote(env: Env, voter: Address, proposal_id: u64, choice: VoteChoice) {
         env.storage()
             .instance()
             .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
         voter.require_auth();
+        Self::_cast_vote(&env, voter, proposal_id, choice);
+    }
 
-        // Check not already voted
-        if env
+    /// Register an ed25519 key whose signed ballots count as `voter`'s votes.
+    /// `signature` is the key's signature over `vote_key_payload`, proving
+    /// the voter holds it.
+    pub fn register_vote_key(env: Env, voter: Address, key: BytesN<32>, signature: BytesN<64>) {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        voter.require_auth();
+
+        let payload = Self::_vote_key_payload(&env, &voter, &key);
+        // Traps on an invalid signature
+        env.crypto().ed25519_verify(&key, &payload, &signature);
+
+        let _ttl_key = DataKey::VoteKey(key.clone());
+        if let Some(owner) = env
             .storage()
             .persistent()
-            .has(&DataKey::HasVoted(proposal_id, voter.clone()))
+            .get::<DataKey, Address>(&_ttl_key)
         {
-            panic!("already voted");
+            if owner != voter {
+                panic!("key already registered");
+            }
         }
+        env.storage().persistent().set(&_ttl_key, &voter);
+        env.storage().persistent().extend_ttl(
+            &_ttl_key,
+            PERSISTENT_LIFETIME_THRESHOLD,
+            PERSISTENT_BUMP_AMOUNT,
+        );
+    }
 
-        let gov_token: Address = env
-            .storage()
+    /// Stop accepting ballots signed with `key`
+    pub fn revoke_vote_key(env: Env, voter: Address, key: BytesN<32>) {
+        env.storage()
             .instance()
-            .get(&DataKey::GovernanceToken)
-            .unwrap();
-        let token_client = token::Client::new(&env, &gov_token);
-        let balance = token_client.balance(&voter);
-        if power > balance {
-            panic!("insufficient governance tokens");
-        }
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        voter.require_auth();
 
-        let mut proposal: Proposal = env
+        let owner: Address = env
             .storage()
             .persistent()
-            .get(&DataKey::Proposal(proposal_id))
-            .expect("proposal not found");
-
-        if proposal.status != ProposalStatus::Active {
-            panic!("proposal not active");
+            .get(&DataKey::VoteKey(key.clone()))
+            .expect("unknown vote key");
+        if owner != voter {
+            panic!("not key owner");
         }
+        env.storage().persistent().remove(&DataKey::VoteKey(key));
+    }
 
-        if env.ledger().sequence() > proposal.end_ledger {
-            panic!("voting period ended");
-        }
+    /// The message a vote key signs to be registered for `voter`
+    pub fn vote_key_payload(env: Env, voter: Address, key: BytesN<32>) -> Bytes {
+        Self::_vote_key_payload(&env, &voter, &key)
+    }
 
-        if power <= 0 {
-            panic!("invalid voting power");
-        }
+    /// Cast a ballot signed off-chain with a registered vote key. Anyone may
+    /// relay it; `nonce` must be the voter's next ballot nonce.
+    pub fn cast_vote_by_sig(
+        env: Env,
+        key: BytesN<32>,
+        proposal_id: u64,
+        choice: VoteChoice,
+        nonce: u64,
+        signature: BytesN<64>,
+    ) {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
 
-        // Record vote
-        match choice {
-            VoteChoice::For => proposal.votes_for += power,
-            VoteChoice::Against => proposal.votes_against += power,
-            VoteChoice::Abstain => proposal.votes_abstain += power,
+        let voter: Address = env
+            .storage()
+            .persistent()
+            .get(&DataKey::VoteKey(key.clone()))
+            .expect("unknown vote key");
+
+        let _ttl_key = DataKey::BallotNonce(voter.clone());
+        let expected: u64 = env.storage().persistent().get(&_ttl_key).unwrap_or(0);
+        if nonce != expected {
+            panic!("invalid nonce");
         }
 
-        let vote = Vote {
-            choice,
-            power,
-            voted_at: env.ledger().timestamp(),
-        };
+        let payload = Self::_ballot_payload(&env, proposal_id, &choice, nonce);
+        // Traps on an invalid signature
+        env.crypto().ed25519_verify(&key, &payload, &signature);
 
-        let _ttl_key = DataKey::Vote(proposal_id, voter.clone());
-        env.storage().persistent().set(&_ttl_key, &vote);
-        env.storage().persistent().extend_ttl(
-            &_ttl_key,
-            PERSISTENT_LIFETIME_THRESHOLD,
-            PERSISTENT_BUMP_AMOUNT,
-        );
-        let _ttl_key = DataKey::HasVoted(proposal_id, voter.clone());
-        env.storage().persistent().set(&_ttl_key, &true);
-        env.storage().persistent().extend_ttl(
-            &_ttl_key,
-            PERSISTENT_LIFETIME_THRESHOLD,
-            PERSISTENT_BUMP_AMOUNT,
-        );
-        let _ttl_key = DataKey::Proposal(proposal_id);
-        env.storage().persistent().set(&_ttl_key, &proposal);
+        env.storage().persistent().set(&_ttl_key, &(nonce + 1));
         env.storage().persistent().extend_ttl(
             &_ttl_key,
             PERSISTENT_LIFETIME_THRESHOLD,
             PERSISTENT_BUMP_AMOUNT,
         );
 
-        env.events().publish(
-            (symbol_short!("gov"), symbol_short!("voted")),
-            (proposal_id, voter, power),
+        Self::_cast_vote(&env, voter, proposal_id, choice);
+    }
+
+    /// The message a vote key signs for a ballot
+    pub fn ballot_payload(env: Env, proposal_id: u64, choice: VoteChoice, nonce: u64) -> Bytes {
+        Self::_ballot_payload(&env, proposal_id, &choice, nonce)
+    }
+
+    pub fn get_ballot_nonce(env: Env, voter: Address) -> u64 {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        env.storage()
+            .persistent()
+            .get(&DataKey::BallotNonce(voter))
+            .unwrap_or(0)
+    }
+
+    /// Let `delegate` vote with `delegator`'s power on one proposal only,
+    /// independently of governance-token delegation
+    pub fn delegate_vote(env: Env, delegator: Address, proposal_id: u64, delegate: Address) {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        delegator.require_auth();
+
+        let proposal = Self::_votable_proposal(&env, proposal_id);
+        if proposal.strategy == VotingStrategy::Conviction {
+            panic!("conviction proposal");
+        }
+        if delegator == delegate {
+            panic!("cannot delegate to self");
+        }
+        if env
+            .storage()
+            .persistent()
+            .has(&DataKey::HasVoted(proposal_id, delegator.clone()))
+        {
+            panic!("already voted");
+        }
+        if env
+            .storage()
+            .persistent()
+            .has(&DataKey::VoteDelegate(proposal_id, delegator.clone()))
+        {
+            panic!("already delegated");
+        }
+        if env
+            .storage()
+            .persistent()
+            .has(&DataKey::VoteDelegate(proposal_id, delegate.clone()))
+        {
+            panic!("delegate has delegated");
+        }
+        if !Self::_vote_delegators(&env, proposal_id, &delegator).is_empty() {
+            panic!("delegator has delegators");
+        }
+        if env
+            .storage()
+            .persistent()
+            .has(&DataKey::HasVoted(proposal_id, delegate.clone()))
+        {
+            panic!("delegate already voted");
+        }
+        let mut delegators = Self::get_vote_delegators(env.clone(), proposal_id, delegate.clone());
+        if delegators.len() >= MAX_VOTE_DELEGATORS {
+            panic!("too many delegators");
+        }
+
+        // The snapshot is fixed, so the delegator's power is added to the
+        // delegate now rather than re-read on every vote
+        let power = Self::_past_votes(
+            &env,
+            &proposal,
+            &delegator,
+            proposal.start_ledger.saturating_sub(1),
+        );
+        if power <= 0 {
+            panic!("invalid voting power");
+        }
+        if proposal.strategy == VotingStrategy::Quadratic && !Self::_is_verified(&env, &delegator) {
+            panic!("delegator not verified");
+        }
+        Self::_add_delegated_votes(&env, proposal_id, &delegate, power);
+
+        let _ttl_key = DataKey::VoteDelegate(proposal_id, delegator.clone());
+        env.storage().persistent().set(&_ttl_key, &delegate);
+        env.storage().persistent().extend_ttl(
+            &_ttl_key,
+            PERSISTENT_LIFETIME_THRESHOLD,
+            PERSISTENT_BUMP_AMOUNT,
+        );
+        delegators.push_back(delegator.clone());
+        Self::_save_vote_delegators(&env, proposal_id, &delegate, &delegators);
+
+        env.events().publish(
+            (symbol_short!("gov"), symbol_short!("delegate")),
+            (proposal_id, delegator, delegate),
         );
     }
 
-    /// Finalize a proposal after voting period
-    pub fn finalize_proposal(env: Env, proposal_id: u64) {
+    /// Take back a per-proposal delegation the delegate has not voted with yet
+    pub fn undelegate_vote(env: Env, delegator: Address, proposal_id: u64) {
         env.storage()
             .instance()
             .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
-        let mut proposal: Proposal = env
+        delegator.require_auth();
+
+        let _ttl_key = DataKey::VoteDelegate(proposal_id, delegator.clone());
+        let delegate: Address = env
             .storage()
             .persistent()
-            .get(&DataKey::Proposal(proposal_id))
-            .expect("proposal not found");
+            .get(&_ttl_key)
+            .expect("not delegated");
+        if env
+            .storage()
+            .persistent()
+            .has(&DataKey::HasVoted(proposal_id, delegate.clone()))
+        {
+            panic!("delegate already voted");
+        }
+        env.storage().persistent().remove(&_ttl_key);
+
+        let proposal: Proposal =
+            Self::_load_proposal(&env, proposal_id).expect("proposal not found");
+        let power = Self::_past_votes(
+            &env,
+            &proposal,
+            &delegator,
+            proposal.start_ledger.saturating_sub(1),
+        );
+        Self::_add_delegated_votes(&env, proposal_id, &delegate, -power);
+
+        let mut delegators = Self::get_vote_delegators(env.clone(), proposal_id, delegate.clone());
+        if let Some(index) = delegators.first_index_of(&delegator) {
+            delegators.remove(index);
+        }
+        Self::_save_vote_delegators(&env, proposal_id, &delegate, &delegators);
+    }
+
+    pub fn get_vote_delegate(env: Env, proposal_id: u64, delegator: Address) -> Option<Address> {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        env.storage()
+            .persistent()
+            .get(&DataKey::VoteDelegate(proposal_id, delegator))
+    }
+
+    pub fn get_vote_delegators(env: Env, proposal_id: u64, delegate: Address) -> Vec<Address> {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        env.storage()
+            .persistent()
+            .get(&DataKey::VoteDelegators(proposal_id, delegate))
+            .unwrap_or(Vec::new(&env))
+    }
+
+    /// Finalize a proposal after voting period
+    pub fn finalize_proposal(env: Env, proposal_id: u64) {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        let mut proposal: Proposal =
+            Self::_load_proposal(&env, proposal_id).expect("proposal not found");
 
         if proposal.status != ProposalStatus::Active {
             panic!("proposal not active");
         }
 
+        if proposal.strategy == VotingStrategy::Conviction {
+            panic!("conviction proposal");
+        }
+
         if env.ledger().sequence() <= proposal.end_ledger {
             panic!("voting period not ended");
         }
@@ -334,15 +813,37 @@ impl GovernanceDaoContract {
             .instance()
             .get(&DataKey::GovernanceToken)
             .unwrap();
-        let total_supply: i128 = env.invoke_contract(
-            &token_address,
-            &soroban_sdk::Symbol::new(&env, "total_supply"),
-            soroban_sdk::vec![&env],
-        );
+        // ve supply decays every ledger, so read it at the same snapshot
+        // ledger votes were weighed at
+        let total_supply: i128 = if proposal.ve_voting {
+            let snapshot_ledger = proposal.start_ledger.saturating_sub(1);
+            env.invoke_contract(
+                &token_address,
+                &soroban_sdk::Symbol::new(&env, "get_past_total_ve_supply"),
+                soroban_sdk::vec![&env, snapshot_ledger.into_val(&env)],
+            )
+        } else {
+            env.invoke_contract(
+                &token_address,
+                &soroban_sdk::Symbol::new(&env, "total_supply"),
+                soroban_sdk::vec![&env],
+            )
+        };
 
         let total_votes = proposal.votes_for + proposal.votes_against;
 
-        let quorum_met = (total_votes * 10_000) >= (total_supply * (proposal.quorum_bps as i128));
+        // Quadratic tallies decide the outcome, but turnout is measured in
+        // linear power so quorum compares like with like
+        let turnout = if proposal.strategy == VotingStrategy::Quadratic {
+            env.storage()
+                .persistent()
+                .get(&DataKey::LinearTurnout(proposal_id))
+                .unwrap_or(0)
+        } else {
+            total_votes
+        };
+
+        let quorum_met = (turnout * 10_000) >= (total_supply * (proposal.quorum_bps as i128));
 
         let for_pct = if total_votes > 0 {
             (proposal.votes_for * 100) / total_votes
@@ -358,8 +859,59 @@ impl GovernanceDaoContract {
             ProposalStatus::Rejected
         };
 
-        let _ttl_key = DataKey::Proposal(proposal_id);
-        env.storage().persistent().set(&_ttl_key, &proposal);
+        Self::_release_core_slot(&env, proposal_id);
+
+        if proposal.status == ProposalStatus::Passed && !proposal.calls.is_empty() {
+            Self::_queue_calls(&env, &mut proposal);
+            proposal.status = ProposalStatus::Queued;
+        }
+
+        Self::_save_proposal(&env, proposal_id, &proposal);
+
+        env.events().publish(
+            (symbol_short!("proposal"), symbol_short!("finalized")),
+            (proposal_id, proposal.status),
+        );
+    }
+
+    /// Lock `amount` governance tokens in support of a funding request
+    pub fn support(env: Env, supporter: Address, proposal_id: u64, amount: i128) {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        supporter.require_auth();
+
+        if amount <= 0 {
+            panic!("invalid amount");
+        }
+        Self::_active_funding_proposal(&env, proposal_id);
+        let mut request = Self::_funding_request(&env, proposal_id);
+        let config: ConvictionConfig = env
+            .storage()
+            .instance()
+            .get(&DataKey::ConvictionConfig)
+            .unwrap();
+
+        let gov_token: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::GovernanceToken)
+            .unwrap();
+        token::Client::new(&env, &gov_token).transfer(
+            &supporter,
+            &env.current_contract_address(),
+            &amount,
+        );
+
+        Self::_update_conviction(&env, &config, &mut request);
+        request.staked += amount;
+        Self::_save_funding_request(&env, proposal_id, &request);
+
+        let _ttl_key = DataKey::Support(proposal_id, supporter.clone());
+        let locked: i128 = env.storage().persistent().get(&_ttl_key).unwrap_or(0);
+        env.storage()
+            .persistent()
+            .set(&_ttl_key, &(locked + amount));
         env.storage().persistent().extend_ttl(
             &_ttl_key,
             PERSISTENT_LIFETIME_THRESHOLD,
@@ -367,42 +919,139 @@ impl GovernanceDaoContract {
         );
 
         env.events().publish(
-            (symbol_short!("proposal"), symbol_short!("finalized")),
-            (proposal_id, proposal.status),
+            (symbol_short!("gov"), symbol_short!("support")),
+            (proposal_id, supporter, amount),
         );
     }
 
-    /// Mark proposal as executed (admin only)
-    pub fn execute_proposal(env: Env, admin: Address, proposal_id: u64) {
+    /// Unlock all of `supporter`'s tokens from a funding request. While the
+    /// request is active this also removes them from its conviction.
+    pub fn withdraw_support(env: Env, supporter: Address, proposal_id: u64) {
         env.storage()
             .instance()
             .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
-        admin.require_auth();
-        let stored_admin: Address = env.storage().instance().get(&DataKey::Admin).unwrap();
-        if admin != stored_admin {
-            panic!("unauthorized");
+        supporter.require_auth();
+
+        let _ttl_key = DataKey::Support(proposal_id, supporter.clone());
+        let locked: i128 = env.storage().persistent().get(&_ttl_key).unwrap_or(0);
+        if locked == 0 {
+            panic!("no support");
         }
+        env.storage().persistent().remove(&_ttl_key);
 
-        let mut proposal: Proposal = env
+        let proposal: Proposal =
+            Self::_load_proposal(&env, proposal_id).expect("proposal not found");
+        if proposal.status == ProposalStatus::Active {
+            let config: ConvictionConfig = env
+                .storage()
+                .instance()
+                .get(&DataKey::ConvictionConfig)
+                .unwrap();
+            let mut request = Self::_funding_request(&env, proposal_id);
+            Self::_update_conviction(&env, &config, &mut request);
+            request.staked -= locked;
+            Self::_save_funding_request(&env, proposal_id, &request);
+        }
+
+        let gov_token: Address = env
             .storage()
-            .persistent()
-            .get(&DataKey::Proposal(proposal_id))
-            .expect("proposal not found");
+            .instance()
+            .get(&DataKey::GovernanceToken)
+            .unwrap();
+        token::Client::new(&env, &gov_token).transfer(
+            &env.current_contract_address(),
+            &supporter,
+            &locked,
+        );
+
+        env.events().publish(
+            (symbol_short!("gov"), symbol_short!("unsupport")),
+            (proposal_id, supporter, locked),
+        );
+    }
+
+    /// Submit a funding request with enough conviction to the treasury as a
+    /// transaction proposed and approved by the DAO (anyone)
+    pub fn execute_funding(env: Env, proposal_id: u64) -> u64 {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
 
-        if proposal.status != ProposalStatus::Passed {
-            panic!("proposal not passed");
+        let mut proposal = Self::_active_funding_proposal(&env, proposal_id);
+        let mut request = Self::_funding_request(&env, proposal_id);
+        let config: ConvictionConfig = env
+            .storage()
+            .instance()
+            .get(&DataKey::ConvictionConfig)
+            .unwrap();
+
+        Self::_update_conviction(&env, &config, &mut request);
+        if request.conviction < Self::_required_conviction(&env, &config, request.amount) {
+            panic!("insufficient conviction");
         }
 
+        let dao = env.current_contract_address();
+        let tx_id: u64 = env.invoke_contract(
+            &config.treasury,
+            &Symbol::new(&env, "propose_transaction"),
+            soroban_sdk::vec![
+                &env,
+                dao.into_val(&env),
+                request.recipient.into_val(&env),
+                config.funding_token.into_val(&env),
+                request.amount.into_val(&env),
+                proposal.title.into_val(&env),
+                FUNDING_TX_EXPIRY.into_val(&env),
+            ],
+        );
+        env.invoke_contract::<()>(
+            &config.treasury,
+            &Symbol::new(&env, "approve_transaction"),
+            soroban_sdk::vec![&env, dao.into_val(&env), tx_id.into_val(&env)],
+        );
+
+        request.treasury_tx = Some(tx_id);
+        Self::_save_funding_request(&env, proposal_id, &request);
+
         proposal.status = ProposalStatus::Executed;
         proposal.executed_at = Some(env.ledger().timestamp());
+        Self::_save_proposal(&env, proposal_id, &proposal);
 
-        let _ttl_key = DataKey::Proposal(proposal_id);
-        env.storage().persistent().set(&_ttl_key, &proposal);
-        env.storage().persistent().extend_ttl(
-            &_ttl_key,
-            PERSISTENT_LIFETIME_THRESHOLD,
-            PERSISTENT_BUMP_AMOUNT,
+        env.events().publish(
+            (symbol_short!("gov"), symbol_short!("funded")),
+            (proposal_id, tx_id),
         );
+
+        tx_id
+    }
+
+    /// Execute a queued proposal's calls through the timelock once its eta has
+    /// passed (anyone), or mark a passed proposal without calls as executed
+    /// (admin only)
+    pub fn execute_proposal(env: Env, caller: Address, proposal_id: u64) {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        caller.require_auth();
+
+        let mut proposal: Proposal =
+            Self::_load_proposal(&env, proposal_id).expect("proposal not found");
+
+        match proposal.status {
+            ProposalStatus::Queued => Self::_timelock_each(&env, &proposal, "execute"),
+            ProposalStatus::Passed => {
+                let stored_admin: Address = env.storage().instance().get(&DataKey::Admin).unwrap();
+                if caller != stored_admin {
+                    panic!("unauthorized");
+                }
+            }
+            _ => panic!("proposal not passed"),
+        }
+
+        proposal.status = ProposalStatus::Executed;
+        proposal.executed_at = Some(env.ledger().timestamp());
+
+        Self::_save_proposal(&env, proposal_id, &proposal);
     }
 
     /// Cancel a proposal (proposer or admin)
@@ -413,24 +1062,22 @@ impl GovernanceDaoContract {
         caller.require_auth();
 
         let admin: Address = env.storage().instance().get(&DataKey::Admin).unwrap();
-        let mut proposal: Proposal = env
-            .storage()
-            .persistent()
-            .get(&DataKey::Proposal(proposal_id))
-            .expect("proposal not found");
+        let mut proposal: Proposal =
+            Self::_load_proposal(&env, proposal_id).expect("proposal not found");
 
-        if caller != proposal.proposer && caller != admin {
+        if caller != proposal.proposer && caller != admin && !Self::_is_moderator(&env, &caller) {
             panic!("unauthorized");
         }
 
+        if proposal.status == ProposalStatus::Queued {
+            Self::_timelock_each(&env, &proposal, "cancel");
+        }
+        if proposal.status == ProposalStatus::Active {
+            Self::_release_core_slot(&env, proposal_id);
+        }
+
         proposal.status = ProposalStatus::Cancelled;
-        let _ttl_key = DataKey::Proposal(proposal_id);
-        env.storage().persistent().set(&_ttl_key, &proposal);
-        env.storage().persistent().extend_ttl(
-            &_ttl_key,
-            PERSISTENT_LIFETIME_THRESHOLD,
-            PERSISTENT_BUMP_AMOUNT,
-        );
+        Self::_save_proposal(&env, proposal_id, &proposal);
     }
 
     // ============================================================
@@ -441,9 +1088,7 @@ impl GovernanceDaoContract {
         env.storage()
             .instance()
             .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
-        env.storage()
-            .persistent()
-            .get(&DataKey::Proposal(proposal_id))
+        Self::_load_proposal(&env, proposal_id)
     }
 
     pub fn get_vote(env: Env, proposal_id: u64, voter: Address) -> Option<Vote> {
@@ -465,6 +1110,51 @@ impl GovernanceDaoContract {
             .unwrap_or(false)
     }
 
+    /// Funding request with conviction brought up to the current ledger
+    pub fn get_funding_request(env: Env, proposal_id: u64) -> Option<FundingRequest> {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        let mut request: FundingRequest = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Funding(proposal_id))?;
+        let proposal: Proposal = Self::_load_proposal(&env, proposal_id).unwrap();
+        if proposal.status == ProposalStatus::Active {
+            let config: ConvictionConfig = env
+                .storage()
+                .instance()
+                .get(&DataKey::ConvictionConfig)
+                .unwrap();
+            Self::_update_conviction(&env, &config, &mut request);
+        }
+        Some(request)
+    }
+
+    /// Conviction a funding request currently needs to be paid
+    pub fn get_required_conviction(env: Env, proposal_id: u64) -> i128 {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        let request = Self::_funding_request(&env, proposal_id);
+        let config: ConvictionConfig = env
+            .storage()
+            .instance()
+            .get(&DataKey::ConvictionConfig)
+            .unwrap();
+        Self::_required_conviction(&env, &config, request.amount)
+    }
+
+    pub fn get_support(env: Env, proposal_id: u64, supporter: Address) -> i128 {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        env.storage()
+            .persistent()
+            .get(&DataKey::Support(proposal_id, supporter))
+            .unwrap_or(0)
+    }
+
     pub fn get_proposal_count(env: Env) -> u64 {
         env.storage()
             .instance()
@@ -488,6 +1178,626 @@ impl GovernanceDaoContract {
     pub fn accept_admin(env: Env, new_admin: Address) {
         pulsar_common_admin::accept_admin(&env, &DataKey::Admin, &DataKey::PendingAdmin, new_admin);
     }
+
+    // ============================================================
+    // Internal Helpers
+    // ============================================================
+
+    fn _create_proposal(
+        env: &Env,
+        proposer: Address,
+        title: String,
+        description: String,
+        target_contract: Option<Address>,
+        calls: Vec<ProposalCall>,
+        strategy: VotingStrategy,
+    ) -> u64 {
+        let core_params = Self::_core_params(env);
+        let (voting_period, quorum_bps, threshold, min_tokens) = match &core_params {
+            Some(params) => (
+                params.voting_period_ledgers,
+                params.quorum_pct * 100,
+                params.pass_threshold_pct,
+                params.min_proposal_threshold,
+            ),
+            None => (
+                env.storage()
+                    .instance()
+                    .get(&DataKey::VotingPeriod)
+                    .unwrap_or(1_000),
+                env.storage()
+                    .instance()
+                    .get(&DataKey::QuorumBps)
+                    .unwrap_or(0),
+                env.storage()
+                    .instance()
+                    .get(&DataKey::PassThreshold)
+                    .unwrap_or(51),
+                env.storage()
+                    .instance()
+                    .get(&DataKey::ProposerMinTokens)
+                    .unwrap_or(0),
+            ),
+        };
+
+        // Enforce minimum token requirement for proposal creation
+        if min_tokens > 0 {
+            let gov_token: Address = env
+                .storage()
+                .instance()
+                .get(&DataKey::GovernanceToken)
+                .unwrap();
+            let balance = token::Client::new(env, &gov_token).balance(&proposer);
+            if balance < min_tokens {
+                panic!("insufficient tokens to create proposal");
+            }
+        }
+
+        let counter: u64 = env
+            .storage()
+            .instance()
+            .get(&DataKey::ProposalCounter)
+            .unwrap_or(0);
+        let proposal_id = counter + 1;
+
+        // Funding requests stay open until paid or cancelled, so they are
+        // kept out of governance-core's active proposal limit
+        if core_params.is_some() && strategy != VotingStrategy::Conviction {
+            Self::_core_call(env, "open_proposal");
+            let _ttl_key = DataKey::CoreSlot(proposal_id);
+            env.storage().persistent().set(&_ttl_key, &true);
+            env.storage().persistent().extend_ttl(
+                &_ttl_key,
+                PERSISTENT_LIFETIME_THRESHOLD,
+                PERSISTENT_BUMP_AMOUNT,
+            );
+        }
+
+        let start = env.ledger().sequence();
+        let proposal = Proposal {
+            proposer: proposer.clone(),
+            title,
+            description,
+            target_contract,
+            status: ProposalStatus::Active,
+            votes_for: 0,
+            votes_against: 0,
+            votes_abstain: 0,
+            quorum_bps,
+            threshold_pct: threshold,
+            start_ledger: start,
+            // Funding requests stay open until paid or cancelled
+            end_ledger: if strategy == VotingStrategy::Conviction {
+                u32::MAX
+            } else {
+                start + voting_period
+            },
+            created_at: env.ledger().timestamp(),
+            executed_at: None,
+            calls,
+            timelock_entries: Vec::new(env),
+            strategy,
+            ve_voting: env
+                .storage()
+                .instance()
+                .get(&DataKey::VeVoting)
+                .unwrap_or(false),
+        };
+
+        Self::_save_proposal(env, proposal_id, &proposal);
+        env.storage()
+            .instance()
+            .set(&DataKey::ProposalCounter, &proposal_id);
+
+        env.events().publish(
+            (symbol_short!("proposal"), symbol_short!("created")),
+            (proposal_id, proposer),
+        );
+
+        proposal_id
+    }
+
+    /// Load a proposal that is open for voting
+    fn _load_proposal(env: &Env, proposal_id: u64) -> Option<Proposal> {
+        if let Some(proposal) = env
+            .storage()
+            .persistent()
+            .get(&DataKey::ProposalV2(proposal_id))
+        {
+            return Some(proposal);
+        }
+        let legacy: LegacyProposal = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Proposal(proposal_id))?;
+        Some(Proposal {
+            proposer: legacy.proposer,
+            title: legacy.title,
+            description: legacy.description,
+            target_contract: legacy.target_contract,
+            status: legacy.status,
+            votes_for: legacy.votes_for,
+            votes_against: legacy.votes_against,
+            votes_abstain: legacy.votes_abstain,
+            quorum_bps: legacy.quorum_bps,
+            threshold_pct: legacy.threshold_pct,
+            start_ledger: legacy.start_ledger,
+            end_ledger: legacy.end_ledger,
+            created_at: legacy.created_at,
+            executed_at: legacy.executed_at,
+            calls: Vec::new(env),
+            timelock_entries: Vec::new(env),
+            strategy: VotingStrategy::TokenWeighted,
+            ve_voting: false,
+        })
+    }
+
+    fn _save_proposal(env: &Env, proposal_id: u64, proposal: &Proposal) {
+        let _ttl_key = DataKey::ProposalV2(proposal_id);
+        env.storage().persistent().set(&_ttl_key, proposal);
+        env.storage().persistent().extend_ttl(
+            &_ttl_key,
+            PERSISTENT_LIFETIME_THRESHOLD,
+            PERSISTENT_BUMP_AMOUNT,
+        );
+        env.storage()
+            .persistent()
+            .remove(&DataKey::Proposal(proposal_id));
+    }
+
+    fn _votable_proposal(env: &Env, proposal_id: u64) -> Proposal {
+        let proposal: Proposal =
+            Self::_load_proposal(env, proposal_id).expect("proposal not found");
+
+        if proposal.status != ProposalStatus::Active {
+            panic!("proposal not active");
+        }
+
+        if env.ledger().sequence() > proposal.end_ledger {
+            panic!("voting period ended");
+        }
+        proposal
+    }
+
+    /// Record `voter`'s vote, replacing any earlier vote of theirs. Power is
+    /// recomputed: their checkpointed votes plus the snapshot power delegated
+    /// to them for this proposal.
+    fn _cast_vote(env: &Env, voter: Address, proposal_id: u64, choice: VoteChoice) {
+        let mut proposal = Self::_votable_proposal(env, proposal_id);
+
+        if proposal.strategy == VotingStrategy::Conviction {
+            panic!("conviction proposal");
+        }
+
+        if env
+            .storage()
+            .persistent()
+            .has(&DataKey::VoteDelegate(proposal_id, voter.clone()))
+        {
+            panic!("vote delegated");
+        }
+
+        // Quadratic proposals only count power held by verified identities;
+        // delegate_vote checks per-proposal delegators
+        let quadratic = proposal.strategy == VotingStrategy::Quadratic;
+        if quadratic && !Self::_is_verified(env, &voter) {
+            panic!("voter not verified");
+        }
+
+        let snapshot_ledger = proposal.start_ledger.saturating_sub(1);
+        let linear = Self::_past_votes(env, &proposal, &voter, snapshot_ledger)
+            + env
+                .storage()
+                .persistent()
+                .get::<DataKey, i128>(&DataKey::DelegatedVotes(proposal_id, voter.clone()))
+                .unwrap_or(0);
+        if linear <= 0 {
+            panic!("invalid voting power");
+        }
+        let power = if quadratic {
+            Self::_isqrt(linear)
+        } else {
+            linear
+        };
+
+        // Withdraw a previous vote before counting the new one
+        let previous: Option<Vote> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Vote(proposal_id, voter.clone()));
+        if let Some(previous) = previous.clone() {
+            match previous.choice {
+                VoteChoice::For => proposal.votes_for -= previous.power,
+                VoteChoice::Against => proposal.votes_against -= previous.power,
+                VoteChoice::Abstain => proposal.votes_abstain -= previous.power,
+            }
+        }
+
+        if quadratic {
+            Self::_record_linear_vote(env, proposal_id, &voter, previous, &choice, linear);
+        }
+
+        // Record vote
+        match choice {
+            VoteChoice::For => proposal.votes_for += power,
+            VoteChoice::Against => proposal.votes_against += power,
+            VoteChoice::Abstain => proposal.votes_abstain += power,
+        }
+
+        let vote = Vote {
+            choice,
+            power,
+            voted_at: env.ledger().timestamp(),
+        };
+
+        let _ttl_key = DataKey::Vote(proposal_id, voter.clone());
+        env.storage().persistent().set(&_ttl_key, &vote);
+        env.storage().persistent().extend_ttl(
+            &_ttl_key,
+            PERSISTENT_LIFETIME_THRESHOLD,
+            PERSISTENT_BUMP_AMOUNT,
+        );
+        let _ttl_key = DataKey::HasVoted(proposal_id, voter.clone());
+        env.storage().persistent().set(&_ttl_key, &true);
+        env.storage().persistent().extend_ttl(
+            &_ttl_key,
+            PERSISTENT_LIFETIME_THRESHOLD,
+            PERSISTENT_BUMP_AMOUNT,
+        );
+        Self::_save_proposal(env, proposal_id, &proposal);
+
+        env.events().publish(
+            (symbol_short!("gov"), symbol_short!("voted")),
+            (proposal_id, voter, power),
+        );
+    }
+
+    fn _is_verified(env: &Env, account: &Address) -> bool {
+        let registry: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::IdentityRegistry)
+            .unwrap();
+        env.invoke_contract(
+            &registry,
+            &Symbol::new(env, "is_verified"),
+            soroban_sdk::vec![env, account.clone().into_val(env)],
+        )
+    }
+
+    /// Track the pre-sqrt power behind a quadratic vote so quorum can be
+    /// measured against the linear token supply.
+    fn _record_linear_vote(
+        env: &Env,
+        proposal_id: u64,
+        voter: &Address,
+        previous: Option<Vote>,
+        choice: &VoteChoice,
+        linear: i128,
+    ) {
+        let vote_key = DataKey::LinearVote(proposal_id, voter.clone());
+        let turnout_key = DataKey::LinearTurnout(proposal_id);
+        let mut turnout: i128 = env.storage().persistent().get(&turnout_key).unwrap_or(0);
+        if let Some(previous) = previous {
+            if !matches!(previous.choice, VoteChoice::Abstain) {
+                turnout -= env
+                    .storage()
+                    .persistent()
+                    .get::<DataKey, i128>(&vote_key)
+                    .unwrap_or(0);
+            }
+        }
+        if !matches!(choice, VoteChoice::Abstain) {
+            turnout += linear;
+        }
+
+        env.storage().persistent().set(&vote_key, &linear);
+        env.storage().persistent().extend_ttl(
+            &vote_key,
+            PERSISTENT_LIFETIME_THRESHOLD,
+            PERSISTENT_BUMP_AMOUNT,
+        );
+        env.storage().persistent().set(&turnout_key, &turnout);
+        env.storage().persistent().extend_ttl(
+            &turnout_key,
+            PERSISTENT_LIFETIME_THRESHOLD,
+            PERSISTENT_BUMP_AMOUNT,
+        );
+    }
+
+    fn _past_votes(env: &Env, proposal: &Proposal, account: &Address, ledger: u32) -> i128 {
+        let gov_token: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::GovernanceToken)
+            .unwrap();
+        env.invoke_contract(
+            &gov_token,
+            &Symbol::new(
+                env,
+                if proposal.ve_voting {
+                    "get_past_ve_votes"
+                } else {
+                    "get_past_votes"
+                },
+            ),
+            soroban_sdk::vec![env, account.into_val(env), ledger.into_val(env)],
+        )
+    }
+
+    fn _vote_delegators(env: &Env, proposal_id: u64, delegate: &Address) -> Vec<Address> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::VoteDelegators(proposal_id, delegate.clone()))
+            .unwrap_or(Vec::new(env))
+    }
+
+    fn _add_delegated_votes(env: &Env, proposal_id: u64, delegate: &Address, amount: i128) {
+        let _ttl_key = DataKey::DelegatedVotes(proposal_id, delegate.clone());
+        let current: i128 = env.storage().persistent().get(&_ttl_key).unwrap_or(0);
+        env.storage()
+            .persistent()
+            .set(&_ttl_key, &(current + amount));
+        env.storage().persistent().extend_ttl(
+            &_ttl_key,
+            PERSISTENT_LIFETIME_THRESHOLD,
+            PERSISTENT_BUMP_AMOUNT,
+        );
+    }
+
+    fn _save_vote_delegators(
+        env: &Env,
+        proposal_id: u64,
+        delegate: &Address,
+        delegators: &Vec<Address>,
+    ) {
+        let _ttl_key = DataKey::VoteDelegators(proposal_id, delegate.clone());
+        env.storage().persistent().set(&_ttl_key, delegators);
+        env.storage().persistent().extend_ttl(
+            &_ttl_key,
+            PERSISTENT_LIFETIME_THRESHOLD,
+            PERSISTENT_BUMP_AMOUNT,
+        );
+    }
+
+    fn _ballot_payload(env: &Env, proposal_id: u64, choice: &VoteChoice, nonce: u64) -> Bytes {
+        let mut data = Bytes::new(env);
+        data.append(&env.current_contract_address().to_xdr(env));
+        for b in proposal_id.to_be_bytes().iter() {
+            data.push_back(*b);
+        }
+        data.append(&choice.clone().to_xdr(env));
+        for b in nonce.to_be_bytes().iter() {
+            data.push_back(*b);
+        }
+        data
+    }
+
+    fn _vote_key_payload(env: &Env, voter: &Address, key: &BytesN<32>) -> Bytes {
+        let mut data = Bytes::from_slice(env, b"vote_key");
+        data.append(&env.current_contract_address().to_xdr(env));
+        data.append(&voter.clone().to_xdr(env));
+        data.append(&Bytes::from(key.clone()));
+        data
+    }
+
+    fn _active_funding_proposal(env: &Env, proposal_id: u64) -> Proposal {
+        let proposal: Proposal =
+            Self::_load_proposal(env, proposal_id).expect("proposal not found");
+        if proposal.strategy != VotingStrategy::Conviction {
+            panic!("not a funding proposal");
+        }
+        if proposal.status != ProposalStatus::Active {
+            panic!("proposal not active");
+        }
+        proposal
+    }
+
+    fn _funding_request(env: &Env, proposal_id: u64) -> FundingRequest {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Funding(proposal_id))
+            .expect("funding request not found")
+    }
+
+    fn _save_funding_request(env: &Env, proposal_id: u64, request: &FundingRequest) {
+        let _ttl_key = DataKey::Funding(proposal_id);
+        env.storage().persistent().set(&_ttl_key, request);
+        env.storage().persistent().extend_ttl(
+            &_ttl_key,
+            PERSISTENT_LIFETIME_THRESHOLD,
+            PERSISTENT_BUMP_AMOUNT,
+        );
+    }
+
+    /// Move conviction towards the staked total for every period elapsed
+    /// since it was last updated
+    fn _update_conviction(env: &Env, config: &ConvictionConfig, request: &mut FundingRequest) {
+        let period = env.ledger().sequence() as u64 / config.period_ledgers as u64;
+        if period <= request.last_period {
+            return;
+        }
+        let kept = Self::_pow_bps(config.retention_bps as i128, period - request.last_period);
+        request.conviction =
+            (request.conviction * kept + request.staked * (10_000 - kept)) / 10_000;
+        request.last_period = period;
+    }
+
+    /// (base / 10_000)^exp, in basis points
+    fn _pow_bps(base: i128, mut exp: u64) -> i128 {
+        let mut result = 10_000i128;
+        let mut factor = base;
+        while exp > 0 && result > 0 {
+            if exp & 1 == 1 {
+                result = result * factor / 10_000;
+            }
+            factor = factor * factor / 10_000;
+            exp >>= 1;
+        }
+        result
+    }
+
+    fn _required_conviction(env: &Env, config: &ConvictionConfig, amount: i128) -> i128 {
+        let treasury_balance =
+            token::Client::new(env, &config.funding_token).balance(&config.treasury);
+        if treasury_balance <= 0 {
+            panic!("empty treasury");
+        }
+        let share_bps = amount * 10_000 / treasury_balance;
+        if share_bps > config.max_share_bps as i128 {
+            panic!("request too large");
+        }
+
+        let gov_token: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::GovernanceToken)
+            .unwrap();
+        let total_supply: i128 = env.invoke_contract(
+            &gov_token,
+            &Symbol::new(env, "total_supply"),
+            soroban_sdk::vec![env],
+        );
+        total_supply * (config.base_threshold_bps as i128 + share_bps) / 10_000
+    }
+
+    fn _isqrt(n: i128) -> i128 {
+        if n <= 0 {
+            return 0;
+        }
+        let mut x = n;
+        let mut y = (x + 1) / 2;
+        while y < x {
+            x = y;
+            y = (x + n / x) / 2;
+        }
+        x
+    }
+
+    fn _core_params(env: &Env) -> Option<GovernanceParams> {
+        let core: Address = env.storage().instance().get(&DataKey::GovernanceCore)?;
+        Some(env.invoke_contract(
+            &core,
+            &Symbol::new(env, "get_params"),
+            soroban_sdk::vec![env],
+        ))
+    }
+
+    /// Close the governance-core slot `proposal_id` opened, if it opened one
+    fn _release_core_slot(env: &Env, proposal_id: u64) {
+        let _ttl_key = DataKey::CoreSlot(proposal_id);
+        if env.storage().persistent().has(&_ttl_key) {
+            env.storage().persistent().remove(&_ttl_key);
+            Self::_core_call(env, "close_proposal");
+        }
+    }
+
+    /// Panic unless `caller` may change voting configuration: the admin, or
+    /// once a governance core is set, its governor
+    fn _require_config_authority(env: &Env, caller: &Address) {
+        caller.require_auth();
+        let authority: Address = match env
+            .storage()
+            .instance()
+            .get::<DataKey, Address>(&DataKey::GovernanceCore)
+        {
+            Some(core) => env
+                .invoke_contract::<Option<Address>>(
+                    &core,
+                    &Symbol::new(env, "get_governor"),
+                    Vec::new(env),
+                )
+                .expect("governor not set"),
+            None => env.storage().instance().get(&DataKey::Admin).unwrap(),
+        };
+        if *caller != authority {
+            panic!("unauthorized");
+        }
+    }
+
+    /// Call `open_proposal` or `close_proposal` on governance-core as the DAO
+    fn _core_call(env: &Env, function: &str) {
+        let core: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::GovernanceCore)
+            .unwrap();
+        env.invoke_contract::<()>(
+            &core,
+            &Symbol::new(env, function),
+            soroban_sdk::vec![env, env.current_contract_address().into_val(env)],
+        );
+    }
+
+    fn _is_moderator(env: &Env, account: &Address) -> bool {
+        let core: Address = match env.storage().instance().get(&DataKey::GovernanceCore) {
+            Some(core) => core,
+            None => return false,
+        };
+        env.invoke_contract(
+            &core,
+            &Symbol::new(env, "has_role"),
+            soroban_sdk::vec![env, account.into_val(env), Role::Moderator.into_val(env),],
+        )
+    }
+
+    /// Queue each of the proposal's calls into the timelock
+    fn _queue_calls(env: &Env, proposal: &mut Proposal) {
+        let timelock: Address = env.storage().instance().get(&DataKey::Timelock).unwrap();
+        let delay: u64 = match Self::_core_params(env) {
+            Some(params) => params.timelock_ledgers as u64 * LEDGER_SECONDS,
+            None => env
+                .storage()
+                .instance()
+                .get(&DataKey::TimelockDelay)
+                .unwrap(),
+        };
+        // governance-core's timelock_ledgers may fall outside what the timelock
+        // accepts; clamp so a passed proposal can always be queued
+        let (min_delay, max_delay) = Self::_timelock_delay_bounds(env, &timelock);
+        let delay = delay.max(min_delay).min(max_delay);
+        for call in proposal.calls.iter() {
+            let entry_id: u64 = env.invoke_contract(
+                &timelock,
+                &Symbol::new(env, "queue"),
+                soroban_sdk::vec![
+                    env,
+                    env.current_contract_address().into_val(env),
+                    call.contract.into_val(env),
+                    call.function.into_val(env),
+                    call.args.into_val(env),
+                    proposal.title.into_val(env),
+                    delay.into_val(env),
+                ],
+            );
+            proposal.timelock_entries.push_back(entry_id);
+        }
+    }
+
+    fn _timelock_delay_bounds(env: &Env, timelock: &Address) -> (u64, u64) {
+        env.invoke_contract(
+            timelock,
+            &Symbol::new(env, "get_delay_bounds"),
+            Vec::new(env),
+        )
+    }
+
+    /// Call `function` (`execute` or `cancel`) on the timelock for every
+    /// queued entry of the proposal, with the DAO as executor/admin
+    fn _timelock_each(env: &Env, proposal: &Proposal, function: &str) {
+        let timelock: Address = env.storage().instance().get(&DataKey::Timelock).unwrap();
+        for entry_id in proposal.timelock_entries.iter() {
+            env.invoke_contract::<()>(
+                &timelock,
+                &Symbol::new(env, function),
+                soroban_sdk::vec![
+                    env,
+                    env.current_contract_address().into_val(env),
+                    entry_id.into_val(env),
+                ],
+            );
+        }
+    }
 }
 
 mod test;
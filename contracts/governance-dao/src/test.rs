@@ -1,9 +1,11 @@
 #![cfg(test)]
 use super::*;
+use ed25519_dalek::{Signer, SigningKey};
+use pulsar_timelock_executor::{TimelockExecutorContract, TimelockExecutorContractClient};
 use soroban_sdk::{
     contract as soroban_contract, contractimpl as soroban_contractimpl,
     testutils::{Address as _, Ledger},
-    Address, Env, String,
+    vec, Address, BytesN, Env, IntoVal, String, Symbol, Val, Vec,
 };
 
 // ─── Mock governance token ───────────────────────────────────────────────────
@@ -41,6 +43,241 @@ impl MockGovToken {
             .get::<Address, i128>(&id)
             .unwrap_or(1_000_000)
     }
+
+    /// Used to lock and unlock conviction support.
+    pub fn transfer(env: Env, from: Address, to: Address, amount: i128) {
+        from.require_auth();
+        let from_balance = Self::balance(env.clone(), from.clone());
+        if from_balance < amount {
+            panic!("insufficient balance");
+        }
+        let to_balance = Self::balance(env.clone(), to.clone());
+        env.storage()
+            .persistent()
+            .set(&from, &(from_balance - amount));
+        env.storage().persistent().set(&to, &(to_balance + amount));
+    }
+
+    /// Called by GovernanceDaoContract::cast_vote; the balance doubles as the
+    /// checkpointed voting power.
+    pub fn get_past_votes(env: Env, account: Address, ledger: u32) -> i128 {
+        env.storage().instance().set(&1u32, &ledger);
+        Self::balance(env, account)
+    }
+
+    /// ve-power is mocked as a tenth of the balance.
+    pub fn get_past_ve_votes(env: Env, account: Address, ledger: u32) -> i128 {
+        env.storage().instance().set(&1u32, &ledger);
+        Self::balance(env, account) / 10
+    }
+
+    pub fn get_past_total_ve_supply(env: Env, ledger: u32) -> i128 {
+        env.storage().instance().set(&1u32, &ledger);
+        Self::total_supply(env) / 10
+    }
+
+    pub fn last_queried_ledger(env: Env) -> u32 {
+        env.storage().instance().get(&1u32).unwrap()
+    }
+}
+
+// ─── Mock timelock and call target ──────────────────────────────────────────
+// Mirrors the timelock-executor's queue/execute/cancel entry points closely
+// enough to exercise executable proposals end to end.
+
+#[soroban_contract]
+pub struct MockTimelock;
+
+#[soroban_contractimpl]
+impl MockTimelock {
+    pub fn queue(
+        env: Env,
+        proposer: Address,
+        target_contract: Address,
+        function_name: Symbol,
+        args: Vec<Val>,
+        _description: String,
+        delay_secs: u64,
+    ) -> u64 {
+        proposer.require_auth();
+        let entry_id: u64 = env.storage().instance().get(&0u32).unwrap_or(0) + 1;
+        let eta = env.ledger().timestamp() + delay_secs;
+        env.storage().instance().set(&0u32, &entry_id);
+        env.storage().persistent().set(
+            &entry_id,
+            &(target_contract, function_name, args, eta, false),
+        );
+        entry_id
+    }
+
+    pub fn execute(env: Env, executor: Address, entry_id: u64) {
+        executor.require_auth();
+        let (target, function, args, eta, cancelled): (Address, Symbol, Vec<Val>, u64, bool) =
+            env.storage().persistent().get(&entry_id).unwrap();
+        if cancelled {
+            panic!("entry not queued");
+        }
+        if env.ledger().timestamp() < eta {
+            panic!("timelock not expired");
+        }
+        let _: Val = env.invoke_contract(&target, &function, args);
+    }
+
+    pub fn cancel(env: Env, admin: Address, entry_id: u64) {
+        admin.require_auth();
+        let (target, function, args, eta, _): (Address, Symbol, Vec<Val>, u64, bool) =
+            env.storage().persistent().get(&entry_id).unwrap();
+        env.storage()
+            .persistent()
+            .set(&entry_id, &(target, function, args, eta, true));
+    }
+
+    /// Make `dao` the admin and executor and accept delays in [min, max]
+    pub fn init(env: Env, dao: Address, min_delay: u64, max_delay: u64) {
+        env.storage().instance().set(&1u32, &dao);
+        env.storage().instance().set(&2u32, &(min_delay, max_delay));
+    }
+
+    pub fn get_admin(env: Env) -> Address {
+        env.storage().instance().get(&1u32).unwrap()
+    }
+
+    pub fn get_executor(env: Env) -> Address {
+        env.storage().instance().get(&1u32).unwrap()
+    }
+
+    pub fn get_delay_bounds(env: Env) -> (u64, u64) {
+        env.storage().instance().get(&2u32).unwrap()
+    }
+
+    pub fn is_cancelled(env: Env, entry_id: u64) -> bool {
+        let (_, _, _, _, cancelled): (Address, Symbol, Vec<Val>, u64, bool) =
+            env.storage().persistent().get(&entry_id).unwrap();
+        cancelled
+    }
+}
+
+#[soroban_contract]
+pub struct MockTarget;
+
+#[soroban_contractimpl]
+impl MockTarget {
+    pub fn set_value(env: Env, value: u32) {
+        env.storage().instance().set(&0u32, &value);
+    }
+
+    pub fn value(env: Env) -> u32 {
+        env.storage().instance().get(&0u32).unwrap_or(0)
+    }
+}
+
+// ─── Mock governance core ────────────────────────────────────────────────────
+
+#[soroban_contract]
+pub struct MockGovCore;
+
+#[soroban_contractimpl]
+impl MockGovCore {
+    pub fn set_params(env: Env, params: GovernanceParams) {
+        env.storage().instance().set(&0u32, &params);
+    }
+
+    pub fn get_params(env: Env) -> GovernanceParams {
+        env.storage().instance().get(&0u32).unwrap()
+    }
+
+    pub fn open_proposal(env: Env, dao: Address) {
+        dao.require_auth();
+        let count: u32 = env.storage().instance().get(&1u32).unwrap_or(0);
+        if count >= Self::get_params(env.clone()).max_active_proposals {
+            panic!("too many active proposals");
+        }
+        env.storage().instance().set(&1u32, &(count + 1));
+    }
+
+    pub fn close_proposal(env: Env, dao: Address) {
+        dao.require_auth();
+        let count: u32 = env.storage().instance().get(&1u32).unwrap_or(0);
+        env.storage()
+            .instance()
+            .set(&1u32, &count.saturating_sub(1));
+    }
+
+    pub fn active(env: Env) -> u32 {
+        env.storage().instance().get(&1u32).unwrap_or(0)
+    }
+
+    pub fn set_governance(env: Env, dao: Address, governor: Address) {
+        env.storage().instance().set(&2u32, &dao);
+        env.storage().instance().set(&3u32, &governor);
+    }
+
+    pub fn get_dao(env: Env) -> Option<Address> {
+        env.storage().instance().get(&2u32)
+    }
+
+    pub fn get_governor(env: Env) -> Option<Address> {
+        env.storage().instance().get(&3u32)
+    }
+
+    pub fn set_moderator(env: Env, account: Address) {
+        env.storage().persistent().set(&account, &true);
+    }
+
+    pub fn has_role(env: Env, account: Address, role: Role) -> bool {
+        role == Role::Moderator && env.storage().persistent().has(&account)
+    }
+}
+
+// ─── Mock identity registry and treasury ─────────────────────────────────────
+
+#[soroban_contract]
+pub struct MockIdentity;
+
+#[soroban_contractimpl]
+impl MockIdentity {
+    pub fn set_verified(env: Env, account: Address) {
+        env.storage().persistent().set(&account, &true);
+    }
+
+    pub fn is_verified(env: Env, account: Address) -> bool {
+        env.storage().persistent().has(&account)
+    }
+}
+
+#[soroban_contract]
+pub struct MockTreasury;
+
+#[soroban_contractimpl]
+impl MockTreasury {
+    pub fn propose_transaction(
+        env: Env,
+        proposer: Address,
+        recipient: Address,
+        _token: Address,
+        amount: i128,
+        _description: String,
+        _expires_in: u64,
+    ) -> u64 {
+        proposer.require_auth();
+        env.storage()
+            .persistent()
+            .set(&1u64, &(recipient, amount, false));
+        1
+    }
+
+    pub fn approve_transaction(env: Env, signer: Address, tx_id: u64) {
+        signer.require_auth();
+        let (recipient, amount, _): (Address, i128, bool) =
+            env.storage().persistent().get(&tx_id).unwrap();
+        env.storage()
+            .persistent()
+            .set(&tx_id, &(recipient, amount, true));
+    }
+
+    pub fn get_tx(env: Env, tx_id: u64) -> (Address, i128, bool) {
+        env.storage().persistent().get(&tx_id).unwrap()
+    }
 }
 
 // ─── helpers ─────────────────────────────────────────────────────────────────
@@ -83,6 +320,25 @@ fn setup_with_mock_token(
     (client, admin, token_addr)
 }
 
+/// Give `voter` `power` votes in the DAO's mock token, then vote with it.
+fn vote(
+    env: &Env,
+    client: &GovernanceDaoContractClient,
+    voter: &Address,
+    proposal_id: u64,
+    choice: VoteChoice,
+    power: i128,
+) {
+    let token_addr: Address = env.as_contract(&client.address, || {
+        env.storage()
+            .instance()
+            .get(&DataKey::GovernanceToken)
+            .unwrap()
+    });
+    MockGovTokenClient::new(env, &token_addr).set_balance(voter, &power);
+    client.cast_vote(voter, &proposal_id, &choice);
+}
+
 fn make_title(env: &Env) -> String {
     String::from_str(env, "Increase treasury allocation")
 }
@@ -179,7 +435,7 @@ fn test_cast_vote_for() {
 
     let proposal_id = client.create_proposal(&proposer, &make_title(&env), &make_desc(&env), &None);
 
-    client.cast_vote(&voter, &proposal_id, &VoteChoice::For, &1_000i128);
+    vote(&env, &client, &voter, proposal_id, VoteChoice::For, 1_000);
 
     let proposal = client.get_proposal(&proposal_id).unwrap();
     assert_eq!(proposal.votes_for, 1_000);
@@ -201,7 +457,7 @@ fn test_cast_vote_against() {
 
     let proposal_id = client.create_proposal(&proposer, &make_title(&env), &make_desc(&env), &None);
 
-    client.cast_vote(&voter, &proposal_id, &VoteChoice::Against, &500i128);
+    vote(&env, &client, &voter, proposal_id, VoteChoice::Against, 500);
 
     let proposal = client.get_proposal(&proposal_id).unwrap();
     assert_eq!(proposal.votes_against, 500);
@@ -218,7 +474,7 @@ fn test_cast_vote_abstain() {
 
     let proposal_id = client.create_proposal(&proposer, &make_title(&env), &make_desc(&env), &None);
 
-    client.cast_vote(&voter, &proposal_id, &VoteChoice::Abstain, &200i128);
+    vote(&env, &client, &voter, proposal_id, VoteChoice::Abstain, 200);
 
     let proposal = client.get_proposal(&proposal_id).unwrap();
     assert_eq!(proposal.votes_abstain, 200);
@@ -236,9 +492,16 @@ fn test_vote_tally_accumulates() {
 
     let proposal_id = client.create_proposal(&proposer, &make_title(&env), &make_desc(&env), &None);
 
-    client.cast_vote(&voter_a, &proposal_id, &VoteChoice::For, &300i128);
-    client.cast_vote(&voter_b, &proposal_id, &VoteChoice::For, &200i128);
-    client.cast_vote(&voter_c, &proposal_id, &VoteChoice::Against, &100i128);
+    vote(&env, &client, &voter_a, proposal_id, VoteChoice::For, 300);
+    vote(&env, &client, &voter_b, proposal_id, VoteChoice::For, 200);
+    vote(
+        &env,
+        &client,
+        &voter_c,
+        proposal_id,
+        VoteChoice::Against,
+        100,
+    );
 
     let proposal = client.get_proposal(&proposal_id).unwrap();
     assert_eq!(proposal.votes_for, 500);
@@ -246,8 +509,27 @@ fn test_vote_tally_accumulates() {
 }
 
 #[test]
-#[should_panic(expected = "already voted")]
-fn test_double_vote_rejected() {
+fn test_vote_power_read_before_proposal_start() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, _, _, token_addr) = setup(&env);
+    let proposer = Address::generate(&env);
+    let voter = Address::generate(&env);
+
+    env.ledger().with_mut(|li| li.sequence_number = 50);
+    let proposal_id = client.create_proposal(&proposer, &make_title(&env), &make_desc(&env), &None);
+    env.ledger().with_mut(|li| li.sequence_number = 60);
+    vote(&env, &client, &voter, proposal_id, VoteChoice::For, 700);
+
+    assert_eq!(
+        MockGovTokenClient::new(&env, &token_addr).last_queried_ledger(),
+        49
+    );
+    assert_eq!(client.get_vote(&proposal_id, &voter).unwrap().power, 700);
+}
+
+#[test]
+fn test_vote_can_be_changed() {
     let env = Env::default();
     env.mock_all_auths();
     let (client, _, _, _) = setup(&env);
@@ -256,8 +538,269 @@ fn test_double_vote_rejected() {
 
     let proposal_id = client.create_proposal(&proposer, &make_title(&env), &make_desc(&env), &None);
 
-    client.cast_vote(&voter, &proposal_id, &VoteChoice::For, &1_000i128);
-    client.cast_vote(&voter, &proposal_id, &VoteChoice::Against, &1_000i128); // panic
+    vote(&env, &client, &voter, proposal_id, VoteChoice::For, 1_000);
+    vote(
+        &env,
+        &client,
+        &voter,
+        proposal_id,
+        VoteChoice::Against,
+        1_000,
+    );
+
+    let proposal = client.get_proposal(&proposal_id).unwrap();
+    assert_eq!(proposal.votes_for, 0);
+    assert_eq!(proposal.votes_against, 1_000);
+    assert!(matches!(
+        client.get_vote(&proposal_id, &voter).unwrap().choice,
+        VoteChoice::Against
+    ));
+}
+
+// ─── Per-proposal delegation ──────────────────────────────────────────────────
+
+#[test]
+fn test_delegated_votes_add_to_delegate_power() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, _, _, token_addr) = setup(&env);
+    let token = MockGovTokenClient::new(&env, &token_addr);
+    let proposer = Address::generate(&env);
+    let delegator = Address::generate(&env);
+    let delegate = Address::generate(&env);
+
+    let proposal_id = client.create_proposal(&proposer, &make_title(&env), &make_desc(&env), &None);
+    token.set_balance(&delegator, &400);
+    client.delegate_vote(&delegator, &proposal_id, &delegate);
+    assert_eq!(
+        client.get_vote_delegate(&proposal_id, &delegator),
+        Some(delegate.clone())
+    );
+
+    vote(&env, &client, &delegate, proposal_id, VoteChoice::For, 600);
+
+    let proposal = client.get_proposal(&proposal_id).unwrap();
+    assert_eq!(proposal.votes_for, 1_000);
+    assert_eq!(
+        client.get_vote(&proposal_id, &delegate).unwrap().power,
+        1_000
+    );
+}
+
+#[test]
+#[should_panic(expected = "vote delegated")]
+fn test_delegator_cannot_vote() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, _, _, token_addr) = setup(&env);
+    let proposer = Address::generate(&env);
+    let delegator = Address::generate(&env);
+    let delegate = Address::generate(&env);
+
+    let proposal_id = client.create_proposal(&proposer, &make_title(&env), &make_desc(&env), &None);
+    MockGovTokenClient::new(&env, &token_addr).set_balance(&delegator, &500);
+    client.delegate_vote(&delegator, &proposal_id, &delegate);
+
+    vote(&env, &client, &delegator, proposal_id, VoteChoice::For, 500);
+}
+
+#[test]
+fn test_undelegate_restores_own_vote() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, _, _, token_addr) = setup(&env);
+    let token = MockGovTokenClient::new(&env, &token_addr);
+    let proposer = Address::generate(&env);
+    let delegator = Address::generate(&env);
+    let delegate = Address::generate(&env);
+
+    let proposal_id = client.create_proposal(&proposer, &make_title(&env), &make_desc(&env), &None);
+    token.set_balance(&delegator, &500);
+    client.delegate_vote(&delegator, &proposal_id, &delegate);
+    client.undelegate_vote(&delegator, &proposal_id);
+
+    assert_eq!(client.get_vote_delegate(&proposal_id, &delegator), None);
+    assert_eq!(client.get_vote_delegators(&proposal_id, &delegate).len(), 0);
+    vote(&env, &client, &delegator, proposal_id, VoteChoice::For, 500);
+    assert_eq!(client.get_proposal(&proposal_id).unwrap().votes_for, 500);
+
+    // The withdrawn power no longer counts for the delegate
+    vote(&env, &client, &delegate, proposal_id, VoteChoice::For, 100);
+    assert_eq!(client.get_vote(&proposal_id, &delegate).unwrap().power, 100);
+}
+
+#[test]
+#[should_panic(expected = "invalid voting power")]
+fn test_delegation_without_power_rejected() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, _, _, token_addr) = setup(&env);
+    let delegator = Address::generate(&env);
+    let proposal_id = client.create_proposal(
+        &Address::generate(&env),
+        &make_title(&env),
+        &make_desc(&env),
+        &None,
+    );
+    MockGovTokenClient::new(&env, &token_addr).set_balance(&delegator, &0);
+    client.delegate_vote(&delegator, &proposal_id, &Address::generate(&env));
+}
+
+#[test]
+#[should_panic(expected = "too many delegators")]
+fn test_delegators_per_delegate_capped() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, _, _, token_addr) = setup(&env);
+    let token = MockGovTokenClient::new(&env, &token_addr);
+    let delegate = Address::generate(&env);
+    let proposal_id = client.create_proposal(
+        &Address::generate(&env),
+        &make_title(&env),
+        &make_desc(&env),
+        &None,
+    );
+
+    for _ in 0..=MAX_VOTE_DELEGATORS {
+        let delegator = Address::generate(&env);
+        token.set_balance(&delegator, &1);
+        client.delegate_vote(&delegator, &proposal_id, &delegate);
+    }
+}
+
+// ─── Signed ballots ───────────────────────────────────────────────────────────
+
+fn sign_ballot(
+    env: &Env,
+    client: &GovernanceDaoContractClient,
+    signer: &SigningKey,
+    proposal_id: u64,
+    choice: VoteChoice,
+    nonce: u64,
+) -> BytesN<64> {
+    let payload = client.ballot_payload(&proposal_id, &choice, &nonce);
+    BytesN::from_array(env, &signer.sign(&payload.to_alloc_vec()).to_bytes())
+}
+
+fn register_key(
+    env: &Env,
+    client: &GovernanceDaoContractClient,
+    signer: &SigningKey,
+    voter: &Address,
+) -> BytesN<32> {
+    let key = BytesN::from_array(env, &signer.verifying_key().to_bytes());
+    let payload = client.vote_key_payload(voter, &key);
+    let signature = BytesN::from_array(env, &signer.sign(&payload.to_alloc_vec()).to_bytes());
+    client.register_vote_key(voter, &key, &signature);
+    key
+}
+
+#[test]
+fn test_cast_vote_by_sig() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, _, _, token_addr) = setup(&env);
+    let proposer = Address::generate(&env);
+    let voter = Address::generate(&env);
+    let signer = SigningKey::from_bytes(&[7u8; 32]);
+
+    let proposal_id = client.create_proposal(&proposer, &make_title(&env), &make_desc(&env), &None);
+    MockGovTokenClient::new(&env, &token_addr).set_balance(&voter, &800);
+    let key = register_key(&env, &client, &signer, &voter);
+
+    let signature = sign_ballot(&env, &client, &signer, proposal_id, VoteChoice::For, 0);
+    client.cast_vote_by_sig(&key, &proposal_id, &VoteChoice::For, &0, &signature);
+
+    assert!(client.has_voted(&proposal_id, &voter));
+    assert_eq!(client.get_proposal(&proposal_id).unwrap().votes_for, 800);
+    assert_eq!(client.get_ballot_nonce(&voter), 1);
+}
+
+#[test]
+#[should_panic(expected = "invalid nonce")]
+fn test_cast_vote_by_sig_replay_rejected() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, _, _, token_addr) = setup(&env);
+    let proposer = Address::generate(&env);
+    let voter = Address::generate(&env);
+    let signer = SigningKey::from_bytes(&[7u8; 32]);
+
+    let proposal_id = client.create_proposal(&proposer, &make_title(&env), &make_desc(&env), &None);
+    MockGovTokenClient::new(&env, &token_addr).set_balance(&voter, &800);
+    let key = register_key(&env, &client, &signer, &voter);
+
+    let signature = sign_ballot(&env, &client, &signer, proposal_id, VoteChoice::For, 0);
+    client.cast_vote_by_sig(&key, &proposal_id, &VoteChoice::For, &0, &signature);
+    client.cast_vote_by_sig(&key, &proposal_id, &VoteChoice::For, &0, &signature);
+}
+
+#[test]
+#[should_panic]
+fn test_cast_vote_by_sig_wrong_choice_rejected() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, _, _, token_addr) = setup(&env);
+    let proposer = Address::generate(&env);
+    let voter = Address::generate(&env);
+    let signer = SigningKey::from_bytes(&[7u8; 32]);
+
+    let proposal_id = client.create_proposal(&proposer, &make_title(&env), &make_desc(&env), &None);
+    MockGovTokenClient::new(&env, &token_addr).set_balance(&voter, &800);
+    let key = register_key(&env, &client, &signer, &voter);
+
+    // Signed For, relayed as Against
+    let signature = sign_ballot(&env, &client, &signer, proposal_id, VoteChoice::For, 0);
+    client.cast_vote_by_sig(&key, &proposal_id, &VoteChoice::Against, &0, &signature);
+}
+
+#[test]
+#[should_panic]
+fn test_register_vote_key_requires_key_signature() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, _, _, _) = setup(&env);
+    let voter = Address::generate(&env);
+    let squatter = Address::generate(&env);
+    let signer = SigningKey::from_bytes(&[7u8; 32]);
+    let key = BytesN::from_array(&env, &signer.verifying_key().to_bytes());
+
+    // A signature binding the key to the voter cannot register it for another
+    let payload = client.vote_key_payload(&voter, &key);
+    let signature = BytesN::from_array(&env, &signer.sign(&payload.to_alloc_vec()).to_bytes());
+    client.register_vote_key(&squatter, &key, &signature);
+}
+
+#[test]
+#[should_panic(expected = "unknown vote key")]
+fn test_revoked_vote_key_rejected() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, _, _, token_addr) = setup(&env);
+    let proposer = Address::generate(&env);
+    let voter = Address::generate(&env);
+    let signer = SigningKey::from_bytes(&[7u8; 32]);
+
+    let proposal_id = client.create_proposal(&proposer, &make_title(&env), &make_desc(&env), &None);
+    MockGovTokenClient::new(&env, &token_addr).set_balance(&voter, &800);
+    let key = register_key(&env, &client, &signer, &voter);
+    client.revoke_vote_key(&voter, &key);
+
+    let signature = sign_ballot(&env, &client, &signer, proposal_id, VoteChoice::For, 0);
+    client.cast_vote_by_sig(&key, &proposal_id, &VoteChoice::For, &0, &signature);
+}
+
+#[test]
+#[should_panic(expected = "not key owner")]
+fn test_revoke_vote_key_owner_only() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, _, _, _) = setup(&env);
+    let voter = Address::generate(&env);
+    let signer = SigningKey::from_bytes(&[7u8; 32]);
+
+    let key = register_key(&env, &client, &signer, &voter);
+    client.revoke_vote_key(&Address::generate(&env), &key);
 }
 
 #[test]
@@ -271,7 +814,7 @@ fn test_vote_zero_power_rejected() {
 
     let proposal_id = client.create_proposal(&proposer, &make_title(&env), &make_desc(&env), &None);
 
-    client.cast_vote(&voter, &proposal_id, &VoteChoice::For, &0i128);
+    vote(&env, &client, &voter, proposal_id, VoteChoice::For, 0);
 }
 
 #[test]
@@ -290,7 +833,7 @@ fn test_vote_after_period_ends() {
         li.sequence_number = 200;
     });
 
-    client.cast_vote(&voter, &proposal_id, &VoteChoice::For, &1_000i128);
+    vote(&env, &client, &voter, proposal_id, VoteChoice::For, 1_000);
 }
 
 #[test]
@@ -305,7 +848,7 @@ fn test_vote_on_cancelled_proposal() {
     let proposal_id = client.create_proposal(&proposer, &make_title(&env), &make_desc(&env), &None);
 
     client.cancel_proposal(&proposer, &proposal_id);
-    client.cast_vote(&voter, &proposal_id, &VoteChoice::For, &1_000i128);
+    vote(&env, &client, &voter, proposal_id, VoteChoice::For, 1_000);
 }
 
 // ─── finalize_proposal ────────────────────────────────────────────────────────
@@ -323,7 +866,7 @@ fn test_finalize_proposal_passed() {
     let proposal_id = client.create_proposal(&proposer, &make_title(&env), &make_desc(&env), &None);
 
     // 200 for votes  (200*10_000 >= 1_000*1_000 → quorum met; 100% >= 51% → Passed)
-    client.cast_vote(&voter, &proposal_id, &VoteChoice::For, &200i128);
+    vote(&env, &client, &voter, proposal_id, VoteChoice::For, 200);
 
     env.ledger().with_mut(|li| {
         li.sequence_number = 200;
@@ -347,7 +890,7 @@ fn test_finalize_proposal_rejected_no_quorum() {
     let voter = Address::generate(&env);
     let proposal_id = client.create_proposal(&proposer, &make_title(&env), &make_desc(&env), &None);
 
-    client.cast_vote(&voter, &proposal_id, &VoteChoice::For, &50i128);
+    vote(&env, &client, &voter, proposal_id, VoteChoice::For, 50);
 
     env.ledger().with_mut(|li| {
         li.sequence_number = 200;
@@ -377,8 +920,15 @@ fn test_finalize_proposal_rejected_not_enough_for_votes() {
     let voter_against = Address::generate(&env);
     let proposal_id = client.create_proposal(&proposer, &make_title(&env), &make_desc(&env), &None);
 
-    client.cast_vote(&voter_for, &proposal_id, &VoteChoice::For, &400i128);
-    client.cast_vote(&voter_against, &proposal_id, &VoteChoice::Against, &600i128);
+    vote(&env, &client, &voter_for, proposal_id, VoteChoice::For, 400);
+    vote(
+        &env,
+        &client,
+        &voter_against,
+        proposal_id,
+        VoteChoice::Against,
+        600,
+    );
 
     env.ledger().with_mut(|li| {
         li.sequence_number = 200;
@@ -417,7 +967,7 @@ fn test_execute_proposal() {
     let voter = Address::generate(&env);
     let proposal_id = client.create_proposal(&proposer, &make_title(&env), &make_desc(&env), &None);
 
-    client.cast_vote(&voter, &proposal_id, &VoteChoice::For, &200i128);
+    vote(&env, &client, &voter, proposal_id, VoteChoice::For, 200);
 
     env.ledger().with_mut(|li| {
         li.sequence_number = 200;
@@ -461,7 +1011,7 @@ fn test_execute_proposal_by_stranger_fails() {
     let stranger = Address::generate(&env);
     let proposal_id = client.create_proposal(&proposer, &make_title(&env), &make_desc(&env), &None);
 
-    client.cast_vote(&voter, &proposal_id, &VoteChoice::For, &200i128);
+    vote(&env, &client, &voter, proposal_id, VoteChoice::For, 200);
 
     env.ledger().with_mut(|li| {
         li.sequence_number = 200;
@@ -471,6 +1021,675 @@ fn test_execute_proposal_by_stranger_fails() {
     client.execute_proposal(&stranger, &proposal_id);
 }
 
+// ─── executable proposals ────────────────────────────────────────────────────
+
+/// DAO with a MockTimelock (1 hour delay) and a passed, queued proposal that
+/// calls MockTarget::set_value(7).
+fn setup_queued_proposal(env: &Env) -> (GovernanceDaoContractClient<'_>, u64, Address, Address) {
+    let (client, admin, _) = setup_with_mock_token(env, 1_000);
+    let timelock = env.register(MockTimelock, ());
+    let target = env.register(MockTarget, ());
+    MockTimelockClient::new(env, &timelock).init(&client.address, &0u64, &2_592_000u64);
+    client.set_timelock(&admin, &timelock, &3_600u64);
+
+    let calls = vec![
+        env,
+        ProposalCall {
+            contract: target.clone(),
+            function: Symbol::new(env, "set_value"),
+            args: vec![env, 7u32.into_val(env)],
+        },
+    ];
+    let proposer = Address::generate(env);
+    let proposal_id =
+        client.create_executable_proposal(&proposer, &make_title(env), &make_desc(env), &calls);
+    vote(
+        env,
+        &client,
+        &Address::generate(env),
+        proposal_id,
+        VoteChoice::For,
+        200,
+    );
+    env.ledger().with_mut(|li| li.sequence_number = 200);
+    client.finalize_proposal(&proposal_id);
+
+    (client, proposal_id, timelock, target)
+}
+
+#[test]
+fn test_executable_proposal_runs_after_eta() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, proposal_id, _, target) = setup_queued_proposal(&env);
+
+    let p = client.get_proposal(&proposal_id).unwrap();
+    assert!(matches!(p.status, ProposalStatus::Queued));
+    assert_eq!(p.timelock_entries.len(), 1);
+
+    let anyone = Address::generate(&env);
+    assert!(client.try_execute_proposal(&anyone, &proposal_id).is_err());
+
+    env.ledger().with_mut(|li| li.timestamp = 3_600);
+    client.execute_proposal(&anyone, &proposal_id);
+
+    let p = client.get_proposal(&proposal_id).unwrap();
+    assert!(matches!(p.status, ProposalStatus::Executed));
+    assert_eq!(MockTargetClient::new(&env, &target).value(), 7);
+}
+
+#[test]
+fn test_cancel_queued_proposal_cancels_timelock_entries() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, proposal_id, timelock, _) = setup_queued_proposal(&env);
+    let admin: Address = env.as_contract(&client.address, || {
+        env.storage().instance().get(&DataKey::Admin).unwrap()
+    });
+
+    client.cancel_proposal(&admin, &proposal_id);
+
+    let p = client.get_proposal(&proposal_id).unwrap();
+    assert!(matches!(p.status, ProposalStatus::Cancelled));
+    let entry_id = p.timelock_entries.get(0).unwrap();
+    assert!(MockTimelockClient::new(&env, &timelock).is_cancelled(&entry_id));
+}
+
+#[test]
+#[should_panic(expected = "timelock not configured")]
+fn test_executable_proposal_requires_timelock() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, _, _, _) = setup(&env);
+    let calls = vec![
+        &env,
+        ProposalCall {
+            contract: Address::generate(&env),
+            function: Symbol::new(&env, "set_value"),
+            args: Vec::new(&env),
+        },
+    ];
+    client.create_executable_proposal(
+        &Address::generate(&env),
+        &make_title(&env),
+        &make_desc(&env),
+        &calls,
+    );
+}
+
+#[test]
+fn test_legacy_proposal_still_loads() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, _, _) = setup_with_mock_token(&env, 1_000);
+    let proposer = Address::generate(&env);
+    env.as_contract(&client.address, || {
+        env.storage().persistent().set(
+            &DataKey::Proposal(1),
+            &LegacyProposal {
+                proposer: proposer.clone(),
+                title: make_title(&env),
+                description: make_desc(&env),
+                target_contract: None,
+                status: ProposalStatus::Active,
+                votes_for: 0,
+                votes_against: 0,
+                votes_abstain: 0,
+                quorum_bps: 1_000,
+                threshold_pct: 51,
+                start_ledger: 0,
+                end_ledger: 100,
+                created_at: 0,
+                executed_at: None,
+            },
+        );
+        env.storage()
+            .instance()
+            .set(&DataKey::ProposalCounter, &1u64);
+    });
+
+    let p = client.get_proposal(&1u64).unwrap();
+    assert_eq!(p.proposer, proposer);
+    assert!(p.calls.is_empty());
+    assert!(matches!(p.strategy, VotingStrategy::TokenWeighted));
+
+    vote(
+        &env,
+        &client,
+        &Address::generate(&env),
+        1,
+        VoteChoice::For,
+        200,
+    );
+    assert_eq!(client.get_proposal(&1u64).unwrap().votes_for, 200);
+}
+
+// ─── governance-core parameters ──────────────────────────────────────────────
+
+/// DAO linked to a MockGovCore with voting period 50, quorum 20%, pass 60%
+/// and at most `max_active` proposals.
+fn setup_with_core(
+    env: &Env,
+    max_active: u32,
+) -> (GovernanceDaoContractClient<'_>, Address, Address) {
+    let (client, admin, _) = setup_with_mock_token(env, 1_000);
+    let core = env.register(MockGovCore, ());
+    MockGovCoreClient::new(env, &core).set_params(&GovernanceParams {
+        min_proposal_threshold: 0,
+        voting_period_ledgers: 50,
+        quorum_pct: 20,
+        pass_threshold_pct: 60,
+        timelock_ledgers: 720,
+        max_active_proposals: max_active,
+    });
+    MockGovCoreClient::new(env, &core).set_governance(&client.address, &client.address);
+    client.set_governance_core(&admin, &core);
+    (client, admin, core)
+}
+
+#[test]
+fn test_proposal_uses_core_params() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, _, _) = setup_with_core(&env, 5);
+
+    let proposal_id = client.create_proposal(
+        &Address::generate(&env),
+        &make_title(&env),
+        &make_desc(&env),
+        &None,
+    );
+    let p = client.get_proposal(&proposal_id).unwrap();
+    assert_eq!(p.end_ledger, p.start_ledger + 50);
+    assert_eq!(p.quorum_bps, 2_000);
+    assert_eq!(p.threshold_pct, 60);
+}
+
+#[test]
+fn test_max_active_proposals_enforced() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, _, core) = setup_with_core(&env, 1);
+    let proposer = Address::generate(&env);
+
+    let first = client.create_proposal(&proposer, &make_title(&env), &make_desc(&env), &None);
+    assert!(client
+        .try_create_proposal(&proposer, &make_title(&env), &make_desc(&env), &None)
+        .is_err());
+
+    env.ledger().with_mut(|li| li.sequence_number = 100);
+    client.finalize_proposal(&first);
+    assert_eq!(MockGovCoreClient::new(&env, &core).active(), 0);
+    client.create_proposal(&proposer, &make_title(&env), &make_desc(&env), &None);
+}
+
+#[test]
+fn test_funding_requests_take_no_core_slot() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, _, core) = setup_with_core(&env, 1);
+    let treasury = env.register(MockTreasury, ());
+    let funding_token = env
+        .register_stellar_asset_contract_v2(Address::generate(&env))
+        .address();
+    soroban_sdk::token::StellarAssetClient::new(&env, &funding_token).mint(&treasury, &10_000);
+    client.set_conviction_config(
+        &client.address,
+        &ConvictionConfig {
+            treasury,
+            funding_token,
+            period_ledgers: 10,
+            retention_bps: 5_000,
+            base_threshold_bps: 1_000,
+            max_share_bps: 5_000,
+        },
+    );
+    let proposer = Address::generate(&env);
+
+    for _ in 0..3 {
+        client.create_funding_proposal(
+            &proposer,
+            &make_title(&env),
+            &make_desc(&env),
+            &Address::generate(&env),
+            &100i128,
+        );
+    }
+    assert_eq!(MockGovCoreClient::new(&env, &core).active(), 0);
+    client.create_proposal(&proposer, &make_title(&env), &make_desc(&env), &None);
+}
+
+#[test]
+fn test_proposal_from_before_core_frees_no_slot() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, admin, _) = setup_with_mock_token(&env, 1_000);
+    let proposer = Address::generate(&env);
+    let before = client.create_proposal(&proposer, &make_title(&env), &make_desc(&env), &None);
+
+    let core = MockGovCoreClient::new(&env, &env.register(MockGovCore, ()));
+    core.set_params(&GovernanceParams {
+        min_proposal_threshold: 0,
+        voting_period_ledgers: 50,
+        quorum_pct: 20,
+        pass_threshold_pct: 60,
+        timelock_ledgers: 720,
+        max_active_proposals: 1,
+    });
+    core.set_governance(&client.address, &client.address);
+    client.set_governance_core(&admin, &core.address);
+    client.create_proposal(&proposer, &make_title(&env), &make_desc(&env), &None);
+
+    client.cancel_proposal(&proposer, &before);
+    assert_eq!(core.active(), 1);
+}
+
+#[test]
+#[should_panic(expected = "dao must control governance core")]
+fn test_set_governance_core_requires_dao_governor() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, admin, _) = setup_with_mock_token(&env, 1_000);
+    let core = MockGovCoreClient::new(&env, &env.register(MockGovCore, ()));
+    core.set_governance(&client.address, &Address::generate(&env));
+    client.set_governance_core(&admin, &core.address);
+}
+
+#[test]
+fn test_voting_config_goes_through_governor_once_core_set() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, admin, _) = setup_with_core(&env, 5);
+
+    assert!(client.try_set_ve_voting(&admin, &true).is_err());
+    assert!(client
+        .try_set_identity_registry(&admin, &Address::generate(&env))
+        .is_err());
+    // The core's governor here is the DAO itself
+    client.set_ve_voting(&client.address, &true);
+    let proposal_id = client.create_proposal(
+        &Address::generate(&env),
+        &make_title(&env),
+        &make_desc(&env),
+        &None,
+    );
+    assert!(client.get_proposal(&proposal_id).unwrap().ve_voting);
+}
+
+#[test]
+fn test_core_moderator_can_cancel() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, _, core) = setup_with_core(&env, 5);
+    let moderator = Address::generate(&env);
+    MockGovCoreClient::new(&env, &core).set_moderator(&moderator);
+
+    let proposal_id = client.create_proposal(
+        &Address::generate(&env),
+        &make_title(&env),
+        &make_desc(&env),
+        &None,
+    );
+    client.cancel_proposal(&moderator, &proposal_id);
+
+    let p = client.get_proposal(&proposal_id).unwrap();
+    assert!(matches!(p.status, ProposalStatus::Cancelled));
+    assert_eq!(MockGovCoreClient::new(&env, &core).active(), 0);
+}
+
+#[test]
+fn test_core_timelock_below_min_delay_is_clamped() {
+    let env = Env::default();
+    env.mock_all_auths();
+    // timelock_ledgers 720 is one hour, below the timelock's one day minimum
+    let (client, _, core) = setup_with_core(&env, 5);
+    let timelock = env.register(TimelockExecutorContract, ());
+    TimelockExecutorContractClient::new(&env, &timelock).initialize(
+        &client.address,
+        &client.address,
+        &86_400u64,
+        &2_592_000u64,
+    );
+    let target = env.register(MockTarget, ());
+    // With a core set, the timelock is configured through its governor
+    MockGovCoreClient::new(&env, &core).set_governance(&client.address, &timelock);
+    client.set_timelock(&timelock, &timelock, &86_400u64);
+
+    let calls = vec![
+        &env,
+        ProposalCall {
+            contract: target.clone(),
+            function: Symbol::new(&env, "set_value"),
+            args: vec![&env, 7u32.into_val(&env)],
+        },
+    ];
+    let proposal_id = client.create_executable_proposal(
+        &Address::generate(&env),
+        &make_title(&env),
+        &make_desc(&env),
+        &calls,
+    );
+    vote(
+        &env,
+        &client,
+        &Address::generate(&env),
+        proposal_id,
+        VoteChoice::For,
+        200,
+    );
+    env.ledger().with_mut(|li| li.sequence_number = 100);
+    client.finalize_proposal(&proposal_id);
+    assert!(matches!(
+        client.get_proposal(&proposal_id).unwrap().status,
+        ProposalStatus::Queued
+    ));
+
+    let anyone = Address::generate(&env);
+    env.ledger().with_mut(|li| li.timestamp = 3_600);
+    assert!(client.try_execute_proposal(&anyone, &proposal_id).is_err());
+    env.ledger().with_mut(|li| li.timestamp = 86_400);
+    client.execute_proposal(&anyone, &proposal_id);
+    assert_eq!(MockTargetClient::new(&env, &target).value(), 7);
+}
+
+#[test]
+#[should_panic(expected = "dao must be timelock admin and executor")]
+fn test_set_timelock_requires_dao_control() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, admin, _, _) = setup(&env);
+    let timelock = env.register(TimelockExecutorContract, ());
+    TimelockExecutorContractClient::new(&env, &timelock).initialize(
+        &Address::generate(&env),
+        &client.address,
+        &86_400u64,
+        &2_592_000u64,
+    );
+    client.set_timelock(&admin, &timelock, &86_400u64);
+}
+
+#[test]
+#[should_panic(expected = "governance core already set")]
+fn test_set_governance_core_once() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, admin, core) = setup_with_core(&env, 5);
+    client.set_governance_core(&admin, &core);
+}
+
+#[test]
+fn test_ve_voting_uses_ve_power_and_supply() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, admin, gov_token) = setup_with_mock_token(&env, 1_000);
+    let proposer = Address::generate(&env);
+    let voter = Address::generate(&env);
+
+    let before = client.create_proposal(&proposer, &make_title(&env), &make_desc(&env), &None);
+    client.set_ve_voting(&admin, &true);
+    let proposal_id = client.create_proposal(&proposer, &make_title(&env), &make_desc(&env), &None);
+    assert!(!client.get_proposal(&before).unwrap().ve_voting);
+
+    // ve-power is a tenth of the balance: 20 of a 100 ve supply meets quorum
+    vote(&env, &client, &voter, proposal_id, VoteChoice::For, 200);
+    assert_eq!(client.get_vote(&proposal_id, &voter).unwrap().power, 20);
+
+    env.ledger().with_mut(|li| {
+        li.sequence_number = 200;
+    });
+    client.finalize_proposal(&proposal_id);
+
+    let proposal = client.get_proposal(&proposal_id).unwrap();
+    assert!(matches!(proposal.status, ProposalStatus::Passed));
+    // The ve supply is read at the vote snapshot, not the finalize ledger
+    assert_eq!(
+        MockGovTokenClient::new(&env, &gov_token).last_queried_ledger(),
+        proposal.start_ledger.saturating_sub(1)
+    );
+}
+
+// ─── quadratic voting ────────────────────────────────────────────────────────
+
+fn setup_quadratic(
+    env: &Env,
+    total_supply: i128,
+) -> (GovernanceDaoContractClient<'_>, MockIdentityClient<'_>, u64) {
+    let (client, admin, _) = setup_with_mock_token(env, total_supply);
+    let identity = MockIdentityClient::new(env, &env.register(MockIdentity, ()));
+    client.set_identity_registry(&admin, &identity.address);
+    let proposal_id = client.create_quadratic_proposal(
+        &Address::generate(env),
+        &make_title(env),
+        &make_desc(env),
+        &Vec::new(env),
+    );
+    (client, identity, proposal_id)
+}
+
+#[test]
+fn test_quadratic_vote_counts_square_root() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, identity, proposal_id) = setup_quadratic(&env, 10_000);
+    let voter = Address::generate(&env);
+    identity.set_verified(&voter);
+
+    vote(&env, &client, &voter, proposal_id, VoteChoice::For, 1_600);
+    let p = client.get_proposal(&proposal_id).unwrap();
+    assert_eq!(p.votes_for, 40);
+    assert_eq!(client.get_vote(&proposal_id, &voter).unwrap().power, 40);
+
+    // Quorum is 10% of the linear supply: 1_600 of 10_000 meets it
+    env.ledger().with_mut(|li| li.sequence_number = 200);
+    client.finalize_proposal(&proposal_id);
+    let p = client.get_proposal(&proposal_id).unwrap();
+    assert!(matches!(p.status, ProposalStatus::Passed));
+}
+
+#[test]
+fn test_quadratic_quorum_uses_linear_turnout() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, identity, proposal_id) = setup_quadratic(&env, 10_000);
+    let voter = Address::generate(&env);
+    identity.set_verified(&voter);
+
+    // 900 of 10_000 is under the 10% quorum even though sqrt(900) = 30
+    // exceeds 10% of sqrt(10_000)
+    vote(&env, &client, &voter, proposal_id, VoteChoice::For, 900);
+    env.ledger().with_mut(|li| li.sequence_number = 200);
+    client.finalize_proposal(&proposal_id);
+    let p = client.get_proposal(&proposal_id).unwrap();
+    assert!(matches!(p.status, ProposalStatus::Rejected));
+}
+
+#[test]
+fn test_quadratic_delegation_counts_verified_delegators() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, identity, proposal_id) = setup_quadratic(&env, 10_000);
+    let token = MockGovTokenClient::new(
+        &env,
+        &env.as_contract(&client.address, || {
+            env.storage()
+                .instance()
+                .get::<DataKey, Address>(&DataKey::GovernanceToken)
+                .unwrap()
+        }),
+    );
+    let delegate = Address::generate(&env);
+    let verified = Address::generate(&env);
+    let unverified = Address::generate(&env);
+    identity.set_verified(&delegate);
+    identity.set_verified(&verified);
+    token.set_balance(&verified, &700);
+    token.set_balance(&unverified, &10_000);
+    client.delegate_vote(&verified, &proposal_id, &delegate);
+    assert!(client
+        .try_delegate_vote(&unverified, &proposal_id, &delegate)
+        .is_err());
+
+    vote(&env, &client, &delegate, proposal_id, VoteChoice::For, 200);
+    assert_eq!(client.get_vote(&proposal_id, &delegate).unwrap().power, 30);
+}
+
+#[test]
+#[should_panic(expected = "voter not verified")]
+fn test_quadratic_vote_requires_verified_voter() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, _, proposal_id) = setup_quadratic(&env, 10_000);
+    vote(
+        &env,
+        &client,
+        &Address::generate(&env),
+        proposal_id,
+        VoteChoice::For,
+        900,
+    );
+}
+
+// ─── conviction voting ───────────────────────────────────────────────────────
+
+/// DAO over a gov token with supply 1_000 and a treasury holding 10_000 of the
+/// funding token; conviction halves each 10-ledger period, base threshold 10%.
+fn setup_conviction(env: &Env) -> (GovernanceDaoContractClient<'_>, Address, Address) {
+    let (client, admin, gov_token) = setup_with_mock_token(env, 1_000);
+    let treasury = env.register(MockTreasury, ());
+    let funding_token = env
+        .register_stellar_asset_contract_v2(Address::generate(env))
+        .address();
+    soroban_sdk::token::StellarAssetClient::new(env, &funding_token).mint(&treasury, &10_000);
+    client.set_conviction_config(
+        &admin,
+        &ConvictionConfig {
+            treasury: treasury.clone(),
+            funding_token,
+            period_ledgers: 10,
+            retention_bps: 5_000,
+            base_threshold_bps: 1_000,
+            max_share_bps: 5_000,
+        },
+    );
+    (client, gov_token, treasury)
+}
+
+#[test]
+fn test_conviction_funding_paid_after_conviction_builds() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, gov_token, treasury) = setup_conviction(&env);
+    let recipient = Address::generate(&env);
+    let supporter = Address::generate(&env);
+    MockGovTokenClient::new(&env, &gov_token).set_balance(&supporter, &300);
+
+    let proposal_id = client.create_funding_proposal(
+        &Address::generate(&env),
+        &make_title(&env),
+        &make_desc(&env),
+        &recipient,
+        &1_000,
+    );
+    // 10% base + 10% of the treasury, of a 1_000 supply
+    assert_eq!(client.get_required_conviction(&proposal_id), 200);
+
+    client.support(&supporter, &proposal_id, &300);
+    assert_eq!(
+        MockGovTokenClient::new(&env, &gov_token).balance(&supporter),
+        0
+    );
+
+    env.ledger().with_mut(|li| li.sequence_number = 10);
+    assert_eq!(
+        client.get_funding_request(&proposal_id).unwrap().conviction,
+        150
+    );
+    assert!(client.try_execute_funding(&proposal_id).is_err());
+
+    env.ledger().with_mut(|li| li.sequence_number = 20);
+    assert_eq!(
+        client.get_funding_request(&proposal_id).unwrap().conviction,
+        225
+    );
+    let tx_id = client.execute_funding(&proposal_id);
+
+    let p = client.get_proposal(&proposal_id).unwrap();
+    assert!(matches!(p.status, ProposalStatus::Executed));
+    assert_eq!(
+        MockTreasuryClient::new(&env, &treasury).get_tx(&tx_id),
+        (recipient, 1_000, true)
+    );
+
+    client.withdraw_support(&supporter, &proposal_id);
+    assert_eq!(
+        MockGovTokenClient::new(&env, &gov_token).balance(&supporter),
+        300
+    );
+}
+
+#[test]
+fn test_withdrawn_support_stops_conviction() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, gov_token, _) = setup_conviction(&env);
+    let supporter = Address::generate(&env);
+    MockGovTokenClient::new(&env, &gov_token).set_balance(&supporter, &300);
+    let proposal_id = client.create_funding_proposal(
+        &Address::generate(&env),
+        &make_title(&env),
+        &make_desc(&env),
+        &Address::generate(&env),
+        &1_000,
+    );
+
+    client.support(&supporter, &proposal_id, &300);
+    env.ledger().with_mut(|li| li.sequence_number = 10);
+    client.withdraw_support(&supporter, &proposal_id);
+    env.ledger().with_mut(|li| li.sequence_number = 20);
+
+    let request = client.get_funding_request(&proposal_id).unwrap();
+    assert_eq!(request.staked, 0);
+    assert_eq!(request.conviction, 75);
+}
+
+#[test]
+#[should_panic(expected = "request too large")]
+fn test_funding_request_above_max_share_rejected() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, _, _) = setup_conviction(&env);
+    client.create_funding_proposal(
+        &Address::generate(&env),
+        &make_title(&env),
+        &make_desc(&env),
+        &Address::generate(&env),
+        &6_000,
+    );
+}
+
+#[test]
+#[should_panic(expected = "conviction proposal")]
+fn test_cast_vote_on_funding_proposal_rejected() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, _, _) = setup_conviction(&env);
+    let proposal_id = client.create_funding_proposal(
+        &Address::generate(&env),
+        &make_title(&env),
+        &make_desc(&env),
+        &Address::generate(&env),
+        &1_000,
+    );
+    vote(
+        &env,
+        &client,
+        &Address::generate(&env),
+        proposal_id,
+        VoteChoice::For,
+        100,
+    );
+}
+
 // ─── cancel_proposal ─────────────────────────────────────────────────────────
 
 #[test]
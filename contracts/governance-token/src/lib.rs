@@ -1,8 +1,26 @@
 //! PulsarTrack - Governance Token (Soroban / SEP-41 compatible)
 //! PULSAR governance token with voting power and delegation on Stellar.
+//!
+//! An account's voting power is its own balance, unless it has delegated,
+//! plus the balances of every account delegating to it. Delegated power
+//! follows the delegator's balance through transfers, mints and burns.
+//!
+//! Every change to an account's balance or voting power is checkpointed by
+//! ledger so `get_past_votes` and `get_past_balance` can answer for any
+//! finished ledger.
+//!
+//! The admin can mint tokens under a linear vesting schedule with a cliff;
+//! the unvested part of a balance cannot be transferred or burned. Holders
+//! can also vote-escrow vested tokens until a future ledger. Escrowed tokens stay
+//! in the holder's balance but are frozen until `withdraw_lock`, and give
+//! ve-power `amount * remaining / MAX_LOCK_LEDGERS`, decaying linearly to
+//! zero at unlock. Lock ends are rounded down to `VE_EPOCH_LEDGERS` so the
+//! total ve supply can be tracked with per-epoch slope changes. ve-power and
+//! the total ve supply are checkpointed by ledger like ordinary votes, and
+//! ve-power is not delegable.
 
 #![no_std]
-use soroban_sdk::{contract, contractimpl, contracttype, symbol_short, Address, Env, String};
+use soroban_sdk::{contract, contractimpl, contracttype, symbol_short, Address, Env, String, Vec};
 
 // ============================================================
 // Data Types
@@ -15,6 +33,49 @@ pub struct Delegation {
     pub delegated_at: u64,
 }
 
+#[contracttype]
+#[derive(Clone)]
+pub struct Checkpoint {
+    pub ledger: u32,
+    pub balance: i128,
+    pub votes: i128,
+}
+
+#[contracttype]
+#[derive(Clone)]
+pub struct VestingSchedule {
+    pub total: i128,
+    pub start: u64,    // timestamp when vesting begins
+    pub cliff: u64,    // seconds after start before anything vests
+    pub duration: u64, // seconds after start until fully vested
+}
+
+#[contracttype]
+#[derive(Clone)]
+pub struct VeLock {
+    pub amount: i128,
+    pub end_ledger: u32,
+}
+
+#[contracttype]
+#[derive(Clone)]
+pub struct VeCheckpoint {
+    pub ledger: u32,
+    pub amount: i128,
+    pub end_ledger: u32,
+}
+
+/// Sum of all lock contributions, scaled by MAX_LOCK_LEDGERS: at `ledger`
+/// the total ve supply is `bias / MAX_LOCK_LEDGERS`, and it falls by
+/// `slope` (the total amount locked) every ledger until a lock expires.
+#[contracttype]
+#[derive(Clone)]
+pub struct VeSupply {
+    pub ledger: u32,
+    pub bias: i128,
+    pub slope: i128,
+}
+
 #[contracttype]
 #[derive(Clone)]
 pub struct TokenMetadata {
@@ -38,10 +99,26 @@ pub enum DataKey {
     Balance(Address),
     Allowance(Address, Address),
     Delegation(Address),
-    VotingSnapshot(Address, u32), // Address, ledger_sequence
+    VotingSnapshot(Address, u32), // Address, checkpoint index
+    CheckpointCount(Address),
+    DelegatedPower(Address),     // delegate -> sum of delegators' balances
+    DelegatorCount(Address),     // delegate -> number of delegators
+    DelegatorSlot(Address, u32), // delegate, list position -> delegator
+    DelegatorPosition(Address),  // delegator -> position in its delegate's list
+    DelegationAccrued(Address),  // delegator whose balance is counted in DelegatedPower
+    Vesting(Address),
+    VeLock(Address),
+    VeSnapshot(Address, u32), // Address, ve checkpoint index
+    VeCheckpointCount(Address),
+    VeSupply,
+    VeSlopeChange(u32),    // epoch end ledger -> amount whose locks expire there
+    VeSupplySnapshot(u32), // ve supply checkpoint index
+    VeSupplyCheckpointCount,
 }
 
 pub const MAX_SUPPLY: i128 = 1_000_000_000_000; // 1M tokens with 6 decimals
+pub const VE_EPOCH_LEDGERS: u32 = 120_960; // ~1 week of 5s ledgers
+pub const MAX_LOCK_LEDGERS: u32 = 208 * VE_EPOCH_LEDGERS; // ~4 years
 
 // ============================================================
 // Contract
@@ -149,6 +226,7 @@ impl GovernanceTokenContract {
         if from_balance < amount {
             panic!("insufficient balance");
         }
+        Self::_require_unlocked(&env, &from, from_balance - amount);
 
         let _ttl_key = DataKey::Balance(from.clone());
         env.storage()
@@ -175,6 +253,9 @@ impl GovernanceTokenContract {
             PERSISTENT_BUMP_AMOUNT,
         );
 
+        Self::_on_balance_change(&env, &from, -amount);
+        Self::_on_balance_change(&env, &to, amount);
+
         env.events()
             .publish((symbol_short!("transfer"),), (from, to, amount));
     }
@@ -205,6 +286,7 @@ impl GovernanceTokenContract {
         if from_balance < amount {
             panic!("insufficient balance");
         }
+        Self::_require_unlocked(&env, &from, from_balance - amount);
 
         let _ttl_key = DataKey::Allowance(from.clone(), spender);
         env.storage()
@@ -239,6 +321,9 @@ impl GovernanceTokenContract {
             PERSISTENT_LIFETIME_THRESHOLD,
             PERSISTENT_BUMP_AMOUNT,
         );
+
+        Self::_on_balance_change(&env, &from, -amount);
+        Self::_on_balance_change(&env, &to, amount);
     }
 
     /// Approve token spending
@@ -278,33 +363,7 @@ impl GovernanceTokenContract {
             panic!("unauthorized");
         }
 
-        let current_supply: i128 = env
-            .storage()
-            .instance()
-            .get(&DataKey::TotalSupply)
-            .unwrap_or(0);
-
-        if current_supply + amount > MAX_SUPPLY {
-            panic!("exceeds max supply");
-        }
-
-        let balance: i128 = env
-            .storage()
-            .persistent()
-            .get(&DataKey::Balance(recipient.clone()))
-            .unwrap_or(0);
-        let _ttl_key = DataKey::Balance(recipient);
-        env.storage()
-            .persistent()
-            .set(&_ttl_key, &(balance + amount));
-        env.storage().persistent().extend_ttl(
-            &_ttl_key,
-            PERSISTENT_LIFETIME_THRESHOLD,
-            PERSISTENT_BUMP_AMOUNT,
-        );
-        env.storage()
-            .instance()
-            .set(&DataKey::TotalSupply, &(current_supply + amount));
+        Self::_mint(&env, &recipient, amount);
     }
 
     /// Burn tokens
@@ -323,8 +382,9 @@ impl GovernanceTokenContract {
         if balance < amount {
             panic!("insufficient balance");
         }
+        Self::_require_unlocked(&env, &from, balance - amount);
 
-        let _ttl_key = DataKey::Balance(from);
+        let _ttl_key = DataKey::Balance(from.clone());
         env.storage()
             .persistent()
             .set(&_ttl_key, &(balance - amount));
@@ -342,6 +402,8 @@ impl GovernanceTokenContract {
         env.storage()
             .instance()
             .set(&DataKey::TotalSupply, &(supply - amount));
+
+        Self::_on_balance_change(&env, &from, -amount);
     }
 
     /// Delegate voting power
@@ -351,6 +413,9 @@ impl GovernanceTokenContract {
             .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
         delegator.require_auth();
 
+        // Redelegation moves the delegator's balance off the previous delegate
+        Self::_clear_delegation(&env, &delegator);
+
         let delegation = Delegation {
             delegate: delegate_to.clone(),
             delegated_at: env.ledger().timestamp(),
@@ -364,40 +429,105 @@ impl GovernanceTokenContract {
             PERSISTENT_BUMP_AMOUNT,
         );
 
+        Self::_push_delegator(&env, &delegate_to, &delegator);
+        let balance = Self::balance(env.clone(), delegator.clone());
+        Self::_add_delegated_power(&env, &delegate_to, balance);
+        Self::_mark_delegation_accrued(&env, &delegator);
+        Self::_checkpoint(&env, &delegator);
+        Self::_checkpoint(&env, &delegate_to);
+
         env.events()
             .publish((symbol_short!("delegate"),), (delegator, delegate_to));
     }
 
+    /// Count a delegation made before delegated balances accrued to the
+    /// delegate. Until migrated, such a delegation moves no power to the
+    /// delegate and clearing it takes none away. Callable by anyone.
+    pub fn migrate_delegation(env: Env, delegator: Address) {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        let delegation: Delegation = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Delegation(delegator.clone()))
+            .expect("no delegation");
+        if Self::_delegation_accrued(&env, &delegator) {
+            panic!("delegation already migrated");
+        }
+
+        Self::_push_delegator(&env, &delegation.delegate, &delegator);
+        let balance = Self::balance(env.clone(), delegator.clone());
+        Self::_add_delegated_power(&env, &delegation.delegate, balance);
+        Self::_mark_delegation_accrued(&env, &delegator);
+        Self::_checkpoint(&env, &delegation.delegate);
+    }
+
     /// Revoke delegation
     pub fn revoke_delegation(env: Env, delegator: Address) {
         env.storage()
             .instance()
             .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
         delegator.require_auth();
-        env.storage()
-            .persistent()
-            .remove(&DataKey::Delegation(delegator));
+        Self::_clear_delegation(&env, &delegator);
     }
 
-    /// Get voting power (0 if delegated)
+    /// Get voting power: own balance (0 if delegated) plus balances delegated
+    /// to the voter
     pub fn voting_power(env: Env, voter: Address) -> i128 {
         env.storage()
             .instance()
             .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
-        let delegation = env
-            .storage()
-            .persistent()
-            .get::<DataKey, Delegation>(&DataKey::Delegation(voter.clone()));
+        Self::_current_votes(&env, &voter)
+    }
 
-        if delegation.is_some() {
-            // Delegated - no direct voting power
-            0
-        } else {
-            env.storage()
+    /// Voting power of `account` at the end of `ledger`
+    pub fn get_past_votes(env: Env, account: Address, ledger: u32) -> i128 {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        Self::_checkpoint_at(&env, &account, ledger)
+            .map(|c| c.votes)
+            .unwrap_or(0)
+    }
+
+    /// Balance of `account` at the end of `ledger`
+    pub fn get_past_balance(env: Env, account: Address, ledger: u32) -> i128 {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        Self::_checkpoint_at(&env, &account, ledger)
+            .map(|c| c.balance)
+            .unwrap_or(0)
+    }
+
+    /// Accounts currently delegating to `delegate`, at most `limit` starting
+    /// at list position `start`. Removing a delegator moves the last entry
+    /// into its slot, so the order is not stable across revocations.
+    pub fn get_delegators(env: Env, delegate: Address, start: u32, limit: u32) -> Vec<Address> {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        let count = Self::_delegator_count(&env, &delegate);
+        let end = start.saturating_add(limit).min(count);
+        let mut result = Vec::new(&env);
+        for i in start..end {
+            if let Some(delegator) = env
+                .storage()
                 .persistent()
-                .get(&DataKey::Balance(voter))
-                .unwrap_or(0)
+                .get(&DataKey::DelegatorSlot(delegate.clone(), i))
+            {
+                result.push_back(delegator);
+            }
         }
+        result
+    }
+
+    pub fn get_delegator_count(env: Env, delegate: Address) -> u32 {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        Self::_delegator_count(&env, &delegate)
     }
 
     /// Get delegation info
@@ -410,6 +540,252 @@ impl GovernanceTokenContract {
             .get(&DataKey::Delegation(delegator))
     }
 
+    /// Mint `amount` to `beneficiary` vesting linearly over `duration`
+    /// seconds from `start`, with nothing vested before `start + cliff`
+    pub fn create_vesting(
+        env: Env,
+        admin: Address,
+        beneficiary: Address,
+        amount: i128,
+        start: u64,
+        cliff: u64,
+        duration: u64,
+    ) {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        admin.require_auth();
+        let stored_admin: Address = env.storage().instance().get(&DataKey::Admin).unwrap();
+        if admin != stored_admin {
+            panic!("unauthorized");
+        }
+
+        if amount <= 0 || duration == 0 || cliff > duration {
+            panic!("invalid schedule");
+        }
+        let _ttl_key = DataKey::Vesting(beneficiary.clone());
+        if env.storage().persistent().has(&_ttl_key) {
+            panic!("vesting exists");
+        }
+
+        let schedule = VestingSchedule {
+            total: amount,
+            start,
+            cliff,
+            duration,
+        };
+        env.storage().persistent().set(&_ttl_key, &schedule);
+        env.storage().persistent().extend_ttl(
+            &_ttl_key,
+            PERSISTENT_LIFETIME_THRESHOLD,
+            PERSISTENT_BUMP_AMOUNT,
+        );
+        Self::_mint(&env, &beneficiary, amount);
+
+        env.events()
+            .publish((symbol_short!("vest"),), (beneficiary, amount));
+    }
+
+    pub fn get_vesting(env: Env, beneficiary: Address) -> Option<VestingSchedule> {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        env.storage()
+            .persistent()
+            .get(&DataKey::Vesting(beneficiary))
+    }
+
+    /// Part of `beneficiary`'s vesting schedule that has vested so far
+    pub fn vested_amount(env: Env, beneficiary: Address) -> i128 {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        match Self::get_vesting(env.clone(), beneficiary.clone()) {
+            Some(schedule) => schedule.total - Self::_unvested(&env, &beneficiary),
+            None => 0,
+        }
+    }
+
+    /// Balance `account` cannot move: its unvested tokens plus its
+    /// vote-escrowed tokens
+    pub fn locked_balance(env: Env, account: Address) -> i128 {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        Self::_locked(&env, &account)
+    }
+
+    /// Vote-escrow `amount` of `owner`'s balance until `unlock_ledger`
+    /// (rounded down to a VE_EPOCH_LEDGERS boundary)
+    pub fn create_lock(env: Env, owner: Address, amount: i128, unlock_ledger: u32) {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        owner.require_auth();
+
+        if amount <= 0 {
+            panic!("invalid amount");
+        }
+        if Self::get_lock(env.clone(), owner.clone()).is_some() {
+            panic!("lock exists");
+        }
+        if Self::_lockable(&env, &owner) < amount {
+            panic!("insufficient balance");
+        }
+
+        let lock = VeLock {
+            amount,
+            end_ledger: Self::_lock_end(&env, unlock_ledger),
+        };
+        Self::_update_lock(&env, &owner, None, Some(lock.clone()));
+
+        env.events().publish(
+            (symbol_short!("lock"),),
+            (owner, lock.amount, lock.end_ledger),
+        );
+    }
+
+    /// Add `amount` more of `owner`'s balance to its unexpired lock
+    pub fn increase_lock_amount(env: Env, owner: Address, amount: i128) {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        owner.require_auth();
+
+        if amount <= 0 {
+            panic!("invalid amount");
+        }
+        let old = Self::_active_lock(&env, &owner);
+        if Self::_lockable(&env, &owner) < old.amount + amount {
+            panic!("insufficient balance");
+        }
+
+        let lock = VeLock {
+            amount: old.amount + amount,
+            end_ledger: old.end_ledger,
+        };
+        Self::_update_lock(&env, &owner, Some(old), Some(lock.clone()));
+
+        env.events().publish(
+            (symbol_short!("lock"),),
+            (owner, lock.amount, lock.end_ledger),
+        );
+    }
+
+    /// Move `owner`'s unexpired lock to a later `unlock_ledger`
+    pub fn extend_lock(env: Env, owner: Address, unlock_ledger: u32) {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        owner.require_auth();
+
+        let old = Self::_active_lock(&env, &owner);
+        let end_ledger = Self::_lock_end(&env, unlock_ledger);
+        if end_ledger <= old.end_ledger {
+            panic!("invalid unlock ledger");
+        }
+
+        let lock = VeLock {
+            amount: old.amount,
+            end_ledger,
+        };
+        Self::_update_lock(&env, &owner, Some(old), Some(lock.clone()));
+
+        env.events().publish(
+            (symbol_short!("lock"),),
+            (owner, lock.amount, lock.end_ledger),
+        );
+    }
+
+    /// Release an expired lock, making its tokens transferable again
+    pub fn withdraw_lock(env: Env, owner: Address) {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        owner.require_auth();
+
+        let lock = Self::get_lock(env.clone(), owner.clone()).expect("no lock");
+        if env.ledger().sequence() < lock.end_ledger {
+            panic!("lock not expired");
+        }
+        Self::_update_lock(&env, &owner, Some(lock.clone()), None);
+
+        env.events()
+            .publish((symbol_short!("unlock"),), (owner, lock.amount));
+    }
+
+    pub fn get_lock(env: Env, owner: Address) -> Option<VeLock> {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        env.storage().persistent().get(&DataKey::VeLock(owner))
+    }
+
+    /// Current ve-power of `owner`
+    pub fn ve_balance(env: Env, owner: Address) -> i128 {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        match Self::get_lock(env.clone(), owner) {
+            Some(lock) => Self::_ve_power(lock.amount, lock.end_ledger, env.ledger().sequence()),
+            None => 0,
+        }
+    }
+
+    /// ve-power of `account` at the end of `ledger`
+    pub fn get_past_ve_votes(env: Env, account: Address, ledger: u32) -> i128 {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        if ledger >= env.ledger().sequence() {
+            panic!("ledger not finished");
+        }
+        let count: u32 = env
+            .storage()
+            .persistent()
+            .get(&DataKey::VeCheckpointCount(account.clone()))
+            .unwrap_or(0);
+        let index = Self::_search_checkpoints(count, ledger, |i| {
+            Self::_get_ve_checkpoint(&env, &account, i).unwrap().ledger
+        });
+        match index.and_then(|i| Self::_get_ve_checkpoint(&env, &account, i)) {
+            Some(c) => Self::_ve_power(c.amount, c.end_ledger, ledger),
+            None => 0,
+        }
+    }
+
+    /// Current sum of every holder's ve-power
+    pub fn total_ve_supply(env: Env) -> i128 {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        Self::_ve_supply(&env).bias / MAX_LOCK_LEDGERS as i128
+    }
+
+    /// Sum of every holder's ve-power at the end of `ledger`
+    pub fn get_past_total_ve_supply(env: Env, ledger: u32) -> i128 {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        if ledger >= env.ledger().sequence() {
+            panic!("ledger not finished");
+        }
+        let count: u32 = env
+            .storage()
+            .persistent()
+            .get(&DataKey::VeSupplyCheckpointCount)
+            .unwrap_or(0);
+        let index = Self::_search_checkpoints(count, ledger, |i| {
+            Self::_get_ve_supply_checkpoint(&env, i).unwrap().ledger
+        });
+        match index.and_then(|i| Self::_get_ve_supply_checkpoint(&env, i)) {
+            Some(supply) => {
+                Self::_ve_supply_at(&env, supply, ledger).bias / MAX_LOCK_LEDGERS as i128
+            }
+            None => 0,
+        }
+    }
+
     pub fn propose_admin(env: Env, current_admin: Address, new_admin: Address) {
         pulsar_common_admin::propose_admin(
             &env,
@@ -423,6 +799,523 @@ impl GovernanceTokenContract {
     pub fn accept_admin(env: Env, new_admin: Address) {
         pulsar_common_admin::accept_admin(&env, &DataKey::Admin, &DataKey::PendingAdmin, new_admin);
     }
+
+    // ============================================================
+    // Internal Helpers
+    // ============================================================
+
+    /// Apply a balance change of `account` to its delegate's power, if any,
+    /// and checkpoint both
+    fn _on_balance_change(env: &Env, account: &Address, amount: i128) {
+        if let Some(delegation) = env
+            .storage()
+            .persistent()
+            .get::<DataKey, Delegation>(&DataKey::Delegation(account.clone()))
+        {
+            if Self::_delegation_accrued(env, account) {
+                Self::_add_delegated_power(env, &delegation.delegate, amount);
+            }
+            Self::_checkpoint(env, &delegation.delegate);
+        }
+        Self::_checkpoint(env, account);
+    }
+
+    fn _current_votes(env: &Env, account: &Address) -> i128 {
+        let delegated: i128 = env
+            .storage()
+            .persistent()
+            .get(&DataKey::DelegatedPower(account.clone()))
+            .unwrap_or(0);
+
+        if env
+            .storage()
+            .persistent()
+            .has(&DataKey::Delegation(account.clone()))
+        {
+            delegated
+        } else {
+            let own: i128 = env
+                .storage()
+                .persistent()
+                .get(&DataKey::Balance(account.clone()))
+                .unwrap_or(0);
+            own + delegated
+        }
+    }
+
+    /// Record `account`'s current balance and votes. Several changes in one
+    /// ledger share a single checkpoint.
+    fn _checkpoint(env: &Env, account: &Address) {
+        let checkpoint = Checkpoint {
+            ledger: env.ledger().sequence(),
+            balance: env
+                .storage()
+                .persistent()
+                .get(&DataKey::Balance(account.clone()))
+                .unwrap_or(0),
+            votes: Self::_current_votes(env, account),
+        };
+
+        let count: u32 = env
+            .storage()
+            .persistent()
+            .get(&DataKey::CheckpointCount(account.clone()))
+            .unwrap_or(0);
+        let last = count
+            .checked_sub(1)
+            .and_then(|i| Self::_get_checkpoint(env, account, i));
+        let index = match last {
+            Some(c) if c.ledger == checkpoint.ledger => count - 1,
+            _ => count,
+        };
+
+        let _ttl_key = DataKey::VotingSnapshot(account.clone(), index);
+        env.storage().persistent().set(&_ttl_key, &checkpoint);
+        env.storage().persistent().extend_ttl(
+            &_ttl_key,
+            PERSISTENT_LIFETIME_THRESHOLD,
+            PERSISTENT_BUMP_AMOUNT,
+        );
+        let _ttl_key = DataKey::CheckpointCount(account.clone());
+        env.storage().persistent().set(&_ttl_key, &(index + 1));
+        env.storage().persistent().extend_ttl(
+            &_ttl_key,
+            PERSISTENT_LIFETIME_THRESHOLD,
+            PERSISTENT_BUMP_AMOUNT,
+        );
+    }
+
+    fn _get_checkpoint(env: &Env, account: &Address, index: u32) -> Option<Checkpoint> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::VotingSnapshot(account.clone(), index))
+    }
+
+    /// Latest checkpoint at or before `ledger`, which must be finished
+    fn _checkpoint_at(env: &Env, account: &Address, ledger: u32) -> Option<Checkpoint> {
+        if ledger >= env.ledger().sequence() {
+            panic!("ledger not finished");
+        }
+        let count: u32 = env
+            .storage()
+            .persistent()
+            .get(&DataKey::CheckpointCount(account.clone()))
+            .unwrap_or(0);
+
+        Self::_search_checkpoints(count, ledger, |i| {
+            Self::_get_checkpoint(env, account, i).unwrap().ledger
+        })
+        .and_then(|i| Self::_get_checkpoint(env, account, i))
+    }
+
+    /// Index of the last of `count` checkpoints whose ledger is <= `ledger`
+    fn _search_checkpoints(count: u32, ledger: u32, ledger_of: impl Fn(u32) -> u32) -> Option<u32> {
+        let (mut low, mut high) = (0u32, count);
+        while low < high {
+            let mid = low + (high - low) / 2;
+            if ledger_of(mid) <= ledger {
+                low = mid + 1;
+            } else {
+                high = mid;
+            }
+        }
+        low.checked_sub(1)
+    }
+
+    fn _mint(env: &Env, recipient: &Address, amount: i128) {
+        let current_supply: i128 = env
+            .storage()
+            .instance()
+            .get(&DataKey::TotalSupply)
+            .unwrap_or(0);
+
+        if current_supply + amount > MAX_SUPPLY {
+            panic!("exceeds max supply");
+        }
+
+        let balance: i128 = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Balance(recipient.clone()))
+            .unwrap_or(0);
+        let _ttl_key = DataKey::Balance(recipient.clone());
+        env.storage()
+            .persistent()
+            .set(&_ttl_key, &(balance + amount));
+        env.storage().persistent().extend_ttl(
+            &_ttl_key,
+            PERSISTENT_LIFETIME_THRESHOLD,
+            PERSISTENT_BUMP_AMOUNT,
+        );
+        env.storage()
+            .instance()
+            .set(&DataKey::TotalSupply, &(current_supply + amount));
+
+        Self::_on_balance_change(env, recipient, amount);
+    }
+
+    /// Tokens of `account` that are still unvested
+    fn _unvested(env: &Env, account: &Address) -> i128 {
+        let schedule: VestingSchedule = match env
+            .storage()
+            .persistent()
+            .get(&DataKey::Vesting(account.clone()))
+        {
+            Some(s) => s,
+            None => return 0,
+        };
+        let now = env.ledger().timestamp();
+        let elapsed = now.saturating_sub(schedule.start);
+        if elapsed < schedule.cliff {
+            return schedule.total;
+        }
+        let vested = (schedule.total as u128 * elapsed.min(schedule.duration) as u128
+            / schedule.duration as u128) as i128;
+        schedule.total - vested
+    }
+
+    fn _locked(env: &Env, account: &Address) -> i128 {
+        let escrowed = env
+            .storage()
+            .persistent()
+            .get::<DataKey, VeLock>(&DataKey::VeLock(account.clone()))
+            .map(|l| l.amount)
+            .unwrap_or(0);
+        Self::_unvested(env, account) + escrowed
+    }
+
+    /// Vested balance of `account`, the most it may vote-escrow
+    fn _lockable(env: &Env, account: &Address) -> i128 {
+        Self::balance(env.clone(), account.clone()) - Self::_unvested(env, account)
+    }
+
+    /// Panic unless `account` keeps at least its locked balance
+    fn _require_unlocked(env: &Env, account: &Address, remaining: i128) {
+        if remaining < Self::_locked(env, account) {
+            panic!("tokens locked");
+        }
+    }
+
+    fn _active_lock(env: &Env, owner: &Address) -> VeLock {
+        let lock: VeLock = env
+            .storage()
+            .persistent()
+            .get(&DataKey::VeLock(owner.clone()))
+            .expect("no lock");
+        if env.ledger().sequence() >= lock.end_ledger {
+            panic!("lock expired");
+        }
+        lock
+    }
+
+    /// Round `unlock_ledger` down to an epoch boundary and check it lies
+    /// within the allowed lock range
+    fn _lock_end(env: &Env, unlock_ledger: u32) -> u32 {
+        let end_ledger = unlock_ledger / VE_EPOCH_LEDGERS * VE_EPOCH_LEDGERS;
+        let now = env.ledger().sequence();
+        if end_ledger <= now {
+            panic!("invalid unlock ledger");
+        }
+        if end_ledger - now > MAX_LOCK_LEDGERS {
+            panic!("lock too long");
+        }
+        end_ledger
+    }
+
+    fn _ve_power(amount: i128, end_ledger: u32, ledger: u32) -> i128 {
+        if ledger >= end_ledger {
+            return 0;
+        }
+        amount * (end_ledger - ledger) as i128 / MAX_LOCK_LEDGERS as i128
+    }
+
+    /// Total ve supply brought forward to the current ledger
+    fn _ve_supply(env: &Env) -> VeSupply {
+        let now = env.ledger().sequence();
+        let supply: VeSupply =
+            env.storage()
+                .instance()
+                .get(&DataKey::VeSupply)
+                .unwrap_or(VeSupply {
+                    ledger: now,
+                    bias: 0,
+                    slope: 0,
+                });
+        Self::_ve_supply_at(env, supply, now)
+    }
+
+    /// Bring `supply` forward to `now`, applying the slope change of every
+    /// epoch boundary crossed
+    fn _ve_supply_at(env: &Env, mut supply: VeSupply, now: u32) -> VeSupply {
+        let mut next = (supply.ledger / VE_EPOCH_LEDGERS + 1) * VE_EPOCH_LEDGERS;
+        while next <= now && supply.slope > 0 {
+            supply.bias -= supply.slope * (next - supply.ledger) as i128;
+            supply.slope -= env
+                .storage()
+                .persistent()
+                .get::<DataKey, i128>(&DataKey::VeSlopeChange(next))
+                .unwrap_or(0);
+            supply.ledger = next;
+            next += VE_EPOCH_LEDGERS;
+        }
+        supply.bias -= supply.slope * (now - supply.ledger) as i128;
+        supply.ledger = now;
+        supply
+    }
+
+    fn _add_slope_change(env: &Env, end_ledger: u32, amount: i128) {
+        let _ttl_key = DataKey::VeSlopeChange(end_ledger);
+        let change: i128 = env.storage().persistent().get(&_ttl_key).unwrap_or(0);
+        env.storage()
+            .persistent()
+            .set(&_ttl_key, &(change + amount));
+        env.storage().persistent().extend_ttl(
+            &_ttl_key,
+            PERSISTENT_LIFETIME_THRESHOLD,
+            PERSISTENT_BUMP_AMOUNT,
+        );
+    }
+
+    /// Replace `owner`'s lock `old` with `new`, updating the total ve supply
+    /// and checkpointing the owner's lock
+    fn _update_lock(env: &Env, owner: &Address, old: Option<VeLock>, new: Option<VeLock>) {
+        let now = env.ledger().sequence();
+        let mut supply = Self::_ve_supply(env);
+        // An expired lock has already left the supply at its end ledger
+        if let Some(lock) = old.filter(|l| l.end_ledger > now) {
+            supply.bias -= lock.amount * (lock.end_ledger - now) as i128;
+            supply.slope -= lock.amount;
+            Self::_add_slope_change(env, lock.end_ledger, -lock.amount);
+        }
+        if let Some(lock) = new.clone() {
+            supply.bias += lock.amount * (lock.end_ledger - now) as i128;
+            supply.slope += lock.amount;
+            Self::_add_slope_change(env, lock.end_ledger, lock.amount);
+        }
+        env.storage().instance().set(&DataKey::VeSupply, &supply);
+        Self::_checkpoint_ve_supply(env, &supply);
+
+        let _ttl_key = DataKey::VeLock(owner.clone());
+        let checkpoint = match new {
+            Some(lock) => {
+                env.storage().persistent().set(&_ttl_key, &lock);
+                env.storage().persistent().extend_ttl(
+                    &_ttl_key,
+                    PERSISTENT_LIFETIME_THRESHOLD,
+                    PERSISTENT_BUMP_AMOUNT,
+                );
+                VeCheckpoint {
+                    ledger: now,
+                    amount: lock.amount,
+                    end_ledger: lock.end_ledger,
+                }
+            }
+            None => {
+                env.storage().persistent().remove(&_ttl_key);
+                VeCheckpoint {
+                    ledger: now,
+                    amount: 0,
+                    end_ledger: now,
+                }
+            }
+        };
+
+        let count: u32 = env
+            .storage()
+            .persistent()
+            .get(&DataKey::VeCheckpointCount(owner.clone()))
+            .unwrap_or(0);
+        let last = count
+            .checked_sub(1)
+            .and_then(|i| Self::_get_ve_checkpoint(env, owner, i));
+        let index = match last {
+            Some(c) if c.ledger == now => count - 1,
+            _ => count,
+        };
+        let _ttl_key = DataKey::VeSnapshot(owner.clone(), index);
+        env.storage().persistent().set(&_ttl_key, &checkpoint);
+        env.storage().persistent().extend_ttl(
+            &_ttl_key,
+            PERSISTENT_LIFETIME_THRESHOLD,
+            PERSISTENT_BUMP_AMOUNT,
+        );
+        let _ttl_key = DataKey::VeCheckpointCount(owner.clone());
+        env.storage().persistent().set(&_ttl_key, &(index + 1));
+        env.storage().persistent().extend_ttl(
+            &_ttl_key,
+            PERSISTENT_LIFETIME_THRESHOLD,
+            PERSISTENT_BUMP_AMOUNT,
+        );
+    }
+
+    fn _checkpoint_ve_supply(env: &Env, supply: &VeSupply) {
+        let count: u32 = env
+            .storage()
+            .persistent()
+            .get(&DataKey::VeSupplyCheckpointCount)
+            .unwrap_or(0);
+        let last = count
+            .checked_sub(1)
+            .and_then(|i| Self::_get_ve_supply_checkpoint(env, i));
+        let index = match last {
+            Some(c) if c.ledger == supply.ledger => count - 1,
+            _ => count,
+        };
+        let _ttl_key = DataKey::VeSupplySnapshot(index);
+        env.storage().persistent().set(&_ttl_key, supply);
+        env.storage().persistent().extend_ttl(
+            &_ttl_key,
+            PERSISTENT_LIFETIME_THRESHOLD,
+            PERSISTENT_BUMP_AMOUNT,
+        );
+        let _ttl_key = DataKey::VeSupplyCheckpointCount;
+        env.storage().persistent().set(&_ttl_key, &(index + 1));
+        env.storage().persistent().extend_ttl(
+            &_ttl_key,
+            PERSISTENT_LIFETIME_THRESHOLD,
+            PERSISTENT_BUMP_AMOUNT,
+        );
+    }
+
+    fn _get_ve_supply_checkpoint(env: &Env, index: u32) -> Option<VeSupply> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::VeSupplySnapshot(index))
+    }
+
+    fn _get_ve_checkpoint(env: &Env, account: &Address, index: u32) -> Option<VeCheckpoint> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::VeSnapshot(account.clone(), index))
+    }
+
+    fn _add_delegated_power(env: &Env, delegate: &Address, amount: i128) {
+        let _ttl_key = DataKey::DelegatedPower(delegate.clone());
+        let power: i128 = env.storage().persistent().get(&_ttl_key).unwrap_or(0);
+        env.storage().persistent().set(&_ttl_key, &(power + amount));
+        env.storage().persistent().extend_ttl(
+            &_ttl_key,
+            PERSISTENT_LIFETIME_THRESHOLD,
+            PERSISTENT_BUMP_AMOUNT,
+        );
+    }
+
+    fn _delegation_accrued(env: &Env, delegator: &Address) -> bool {
+        env.storage()
+            .persistent()
+            .has(&DataKey::DelegationAccrued(delegator.clone()))
+    }
+
+    fn _mark_delegation_accrued(env: &Env, delegator: &Address) {
+        let _ttl_key = DataKey::DelegationAccrued(delegator.clone());
+        env.storage().persistent().set(&_ttl_key, &true);
+        env.storage().persistent().extend_ttl(
+            &_ttl_key,
+            PERSISTENT_LIFETIME_THRESHOLD,
+            PERSISTENT_BUMP_AMOUNT,
+        );
+    }
+
+    fn _delegator_count(env: &Env, delegate: &Address) -> u32 {
+        env.storage()
+            .persistent()
+            .get(&DataKey::DelegatorCount(delegate.clone()))
+            .unwrap_or(0)
+    }
+
+    fn _set_delegator_count(env: &Env, delegate: &Address, count: u32) {
+        let _ttl_key = DataKey::DelegatorCount(delegate.clone());
+        env.storage().persistent().set(&_ttl_key, &count);
+        env.storage().persistent().extend_ttl(
+            &_ttl_key,
+            PERSISTENT_LIFETIME_THRESHOLD,
+            PERSISTENT_BUMP_AMOUNT,
+        );
+    }
+
+    fn _set_delegator_slot(env: &Env, delegate: &Address, position: u32, delegator: &Address) {
+        let _ttl_key = DataKey::DelegatorSlot(delegate.clone(), position);
+        env.storage().persistent().set(&_ttl_key, delegator);
+        env.storage().persistent().extend_ttl(
+            &_ttl_key,
+            PERSISTENT_LIFETIME_THRESHOLD,
+            PERSISTENT_BUMP_AMOUNT,
+        );
+        let _ttl_key = DataKey::DelegatorPosition(delegator.clone());
+        env.storage().persistent().set(&_ttl_key, &position);
+        env.storage().persistent().extend_ttl(
+            &_ttl_key,
+            PERSISTENT_LIFETIME_THRESHOLD,
+            PERSISTENT_BUMP_AMOUNT,
+        );
+    }
+
+    fn _push_delegator(env: &Env, delegate: &Address, delegator: &Address) {
+        let count = Self::_delegator_count(env, delegate);
+        Self::_set_delegator_slot(env, delegate, count, delegator);
+        Self::_set_delegator_count(env, delegate, count + 1);
+    }
+
+    /// Swap-removes the delegator so the delegate's list stays dense.
+    /// Unmigrated legacy delegations were never listed.
+    fn _remove_delegator(env: &Env, delegate: &Address, delegator: &Address) {
+        let position: u32 = match env
+            .storage()
+            .persistent()
+            .get(&DataKey::DelegatorPosition(delegator.clone()))
+        {
+            Some(p) => p,
+            None => return,
+        };
+        let last = Self::_delegator_count(env, delegate) - 1;
+        if position != last {
+            let moved: Address = env
+                .storage()
+                .persistent()
+                .get(&DataKey::DelegatorSlot(delegate.clone(), last))
+                .unwrap();
+            Self::_set_delegator_slot(env, delegate, position, &moved);
+        }
+        env.storage()
+            .persistent()
+            .remove(&DataKey::DelegatorSlot(delegate.clone(), last));
+        env.storage()
+            .persistent()
+            .remove(&DataKey::DelegatorPosition(delegator.clone()));
+        Self::_set_delegator_count(env, delegate, last);
+    }
+
+    /// Remove `delegator`'s delegation, taking its balance off the delegate
+    fn _clear_delegation(env: &Env, delegator: &Address) {
+        let delegation = match env
+            .storage()
+            .persistent()
+            .get::<DataKey, Delegation>(&DataKey::Delegation(delegator.clone()))
+        {
+            Some(d) => d,
+            None => return,
+        };
+
+        // Legacy delegations never added the balance, so there is nothing to take off
+        let accrued_key = DataKey::DelegationAccrued(delegator.clone());
+        if env.storage().persistent().has(&accrued_key) {
+            let balance: i128 = env
+                .storage()
+                .persistent()
+                .get(&DataKey::Balance(delegator.clone()))
+                .unwrap_or(0);
+            Self::_add_delegated_power(env, &delegation.delegate, -balance);
+            env.storage().persistent().remove(&accrued_key);
+        }
+
+        Self::_remove_delegator(env, &delegation.delegate, delegator);
+
+        env.storage()
+            .persistent()
+            .remove(&DataKey::Delegation(delegator.clone()));
+        Self::_checkpoint(env, &delegation.delegate);
+        Self::_checkpoint(env, delegator);
+    }
 }
 
 mod test;
@@ -1,6 +1,9 @@
 #![cfg(test)]
 use super::*;
-use soroban_sdk::{testutils::Address as _, Address, Env};
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    vec, Address, Env,
+};
 
 fn setup(env: &Env) -> (GovernanceTokenContractClient<'_>, Address) {
     let admin = Address::generate(env);
@@ -100,8 +103,55 @@ fn test_delegate() {
     c.delegate(&delegator, &delegate);
     let d = c.get_delegation(&delegator).unwrap();
     assert_eq!(d.delegate, delegate);
-    // Delegator loses voting power after delegating
+    // Delegator's voting power moves to the delegate
     assert_eq!(c.voting_power(&delegator), 0);
+    assert_eq!(c.voting_power(&delegate), 1_000);
+}
+
+#[test]
+fn test_delegated_power_follows_balance() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (c, admin) = setup(&env);
+    let delegator = Address::generate(&env);
+    let delegate = Address::generate(&env);
+    let other = Address::generate(&env);
+    c.mint(&admin, &delegate, &200i128);
+    c.mint(&admin, &delegator, &1_000i128);
+    c.delegate(&delegator, &delegate);
+    assert_eq!(c.voting_power(&delegate), 1_200);
+
+    c.mint(&admin, &delegator, &500i128);
+    c.transfer(&delegator, &other, &300i128);
+    c.burn(&delegator, &200i128);
+    assert_eq!(c.voting_power(&delegate), 1_200);
+
+    c.approve(&other, &delegator, &100i128, &1000u32);
+    c.transfer_from(&delegator, &other, &delegator, &100i128);
+    assert_eq!(c.voting_power(&delegate), 1_300);
+    assert_eq!(c.voting_power(&other), 200);
+}
+
+#[test]
+fn test_redelegate_moves_power() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (c, admin) = setup(&env);
+    let delegator = Address::generate(&env);
+    let first = Address::generate(&env);
+    let second = Address::generate(&env);
+    c.mint(&admin, &delegator, &1_000i128);
+    c.delegate(&delegator, &first);
+    assert_eq!(c.get_delegator_count(&first), 1);
+
+    c.delegate(&delegator, &second);
+    assert_eq!(c.voting_power(&first), 0);
+    assert_eq!(c.voting_power(&second), 1_000);
+    assert_eq!(c.get_delegator_count(&first), 0);
+    assert_eq!(
+        c.get_delegators(&second, &0, &10).get(0).unwrap(),
+        delegator
+    );
 }
 
 #[test]
@@ -115,6 +165,100 @@ fn test_revoke_delegation() {
     c.delegate(&delegator, &delegate);
     c.revoke_delegation(&delegator);
     assert!(c.get_delegation(&delegator).is_none());
+    assert_eq!(c.voting_power(&delegator), 1_000);
+    assert_eq!(c.voting_power(&delegate), 0);
+    assert_eq!(c.get_delegator_count(&delegate), 0);
+}
+
+#[test]
+fn test_delegators_paginated_and_swap_removed() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (c, admin) = setup(&env);
+    let delegate = Address::generate(&env);
+    let a = Address::generate(&env);
+    let b = Address::generate(&env);
+    let d = Address::generate(&env);
+    for delegator in [&a, &b, &d] {
+        c.mint(&admin, delegator, &100i128);
+        c.delegate(delegator, &delegate);
+    }
+    assert_eq!(c.get_delegator_count(&delegate), 3);
+    assert_eq!(
+        c.get_delegators(&delegate, &0, &2),
+        vec![&env, a.clone(), b.clone()]
+    );
+    assert_eq!(c.get_delegators(&delegate, &2, &10), vec![&env, d.clone()]);
+
+    // The last delegator takes the revoked one's slot
+    c.revoke_delegation(&a);
+    assert_eq!(
+        c.get_delegators(&delegate, &0, &10),
+        vec![&env, d.clone(), b.clone()]
+    );
+    assert_eq!(c.voting_power(&delegate), 200);
+
+    c.delegate(&a, &delegate);
+    assert_eq!(c.get_delegators(&delegate, &2, &1), vec![&env, a.clone()]);
+}
+
+/// A delegation stored before delegated balances accrued to the delegate
+fn legacy_delegate(
+    env: &Env,
+    c: &GovernanceTokenContractClient,
+    delegator: &Address,
+    to: &Address,
+) {
+    env.as_contract(&c.address, || {
+        env.storage().persistent().set(
+            &DataKey::Delegation(delegator.clone()),
+            &Delegation {
+                delegate: to.clone(),
+                delegated_at: 0,
+            },
+        );
+    });
+}
+
+#[test]
+fn test_revoke_legacy_delegation() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (c, admin) = setup(&env);
+    let delegator = Address::generate(&env);
+    let delegate = Address::generate(&env);
+    c.mint(&admin, &delegate, &200i128);
+    c.mint(&admin, &delegator, &1_000i128);
+    legacy_delegate(&env, &c, &delegator, &delegate);
+
+    // Balance moves don't touch a delegate that never received the balance
+    c.transfer(&delegator, &Address::generate(&env), &100i128);
+    c.revoke_delegation(&delegator);
+    assert_eq!(c.voting_power(&delegate), 200);
+    assert_eq!(c.voting_power(&delegator), 900);
+}
+
+#[test]
+fn test_migrate_legacy_delegation() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (c, admin) = setup(&env);
+    let delegator = Address::generate(&env);
+    let delegate = Address::generate(&env);
+    c.mint(&admin, &delegator, &1_000i128);
+    legacy_delegate(&env, &c, &delegator, &delegate);
+    assert_eq!(c.voting_power(&delegate), 0);
+
+    c.migrate_delegation(&delegator);
+    assert_eq!(c.voting_power(&delegate), 1_000);
+    assert_eq!(
+        c.get_delegators(&delegate, &0, &10).get(0).unwrap(),
+        delegator
+    );
+    assert!(c.try_migrate_delegation(&delegator).is_err());
+
+    c.revoke_delegation(&delegator);
+    assert_eq!(c.voting_power(&delegate), 0);
 }
 
 #[test]
@@ -146,3 +290,204 @@ fn test_balance_zero() {
     let (c, _) = setup(&env);
     assert_eq!(c.balance(&Address::generate(&env)), 0);
 }
+
+#[test]
+fn test_past_votes_checkpoints() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (c, admin) = setup(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+
+    env.ledger().with_mut(|li| li.sequence_number = 10);
+    c.mint(&admin, &alice, &1_000i128);
+    env.ledger().with_mut(|li| li.sequence_number = 20);
+    c.transfer(&alice, &bob, &400i128);
+    c.transfer(&alice, &bob, &100i128);
+    env.ledger().with_mut(|li| li.sequence_number = 30);
+    c.delegate(&bob, &alice);
+    env.ledger().with_mut(|li| li.sequence_number = 40);
+
+    assert_eq!(c.get_past_votes(&alice, &9u32), 0);
+    assert_eq!(c.get_past_votes(&alice, &15u32), 1_000);
+    assert_eq!(c.get_past_votes(&alice, &20u32), 500);
+    assert_eq!(c.get_past_votes(&bob, &25u32), 500);
+    assert_eq!(c.get_past_votes(&bob, &30u32), 0);
+    assert_eq!(c.get_past_votes(&alice, &39u32), 1_000);
+    assert_eq!(c.get_past_balance(&alice, &39u32), 500);
+    assert_eq!(c.get_past_balance(&bob, &39u32), 500);
+}
+
+#[test]
+#[should_panic(expected = "ledger not finished")]
+fn test_past_votes_current_ledger_rejected() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (c, _) = setup(&env);
+    env.ledger().with_mut(|li| li.sequence_number = 10);
+    c.get_past_votes(&Address::generate(&env), &10u32);
+}
+
+#[test]
+fn test_vesting_restricts_unvested_transfers() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (c, admin) = setup(&env);
+    let member = Address::generate(&env);
+    let other = Address::generate(&env);
+
+    env.ledger().with_mut(|li| li.timestamp = 1_000);
+    c.create_vesting(&admin, &member, &1_000i128, &1_000u64, &100u64, &1_000u64);
+    assert_eq!(c.balance(&member), 1_000);
+    assert_eq!(c.total_supply(), 1_000);
+    assert_eq!(c.vested_amount(&member), 0);
+
+    // Before the cliff nothing vests
+    env.ledger().with_mut(|li| li.timestamp = 1_050);
+    assert_eq!(c.locked_balance(&member), 1_000);
+
+    env.ledger().with_mut(|li| li.timestamp = 1_500);
+    assert_eq!(c.vested_amount(&member), 500);
+    c.transfer(&member, &other, &500i128);
+    assert_eq!(c.balance(&other), 500);
+
+    env.ledger().with_mut(|li| li.timestamp = 2_000);
+    assert_eq!(c.locked_balance(&member), 0);
+    c.transfer(&member, &other, &500i128);
+}
+
+#[test]
+#[should_panic(expected = "tokens locked")]
+fn test_vesting_unvested_transfer_rejected() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (c, admin) = setup(&env);
+    let member = Address::generate(&env);
+
+    c.create_vesting(&admin, &member, &1_000i128, &0u64, &0u64, &1_000u64);
+    env.ledger().with_mut(|li| li.timestamp = 500);
+    c.transfer(&member, &Address::generate(&env), &501i128);
+}
+
+#[test]
+fn test_ve_power_decays_until_unlock() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (c, admin) = setup(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    c.mint(&admin, &alice, &1_040_000i128);
+    c.mint(&admin, &bob, &520_000i128);
+
+    env.ledger().with_mut(|li| li.sequence_number = 10);
+    // Rounded down to the epoch boundaries at 2 and 1 epochs
+    c.create_lock(&alice, &1_040_000i128, &(2 * VE_EPOCH_LEDGERS + 5));
+    c.create_lock(&bob, &520_000i128, &VE_EPOCH_LEDGERS);
+    assert_eq!(c.get_lock(&alice).unwrap().end_ledger, 2 * VE_EPOCH_LEDGERS);
+
+    env.ledger().with_mut(|li| li.sequence_number = 75_600);
+    assert_eq!(c.ve_balance(&alice), 6_875);
+    assert_eq!(c.ve_balance(&bob), 937);
+    assert_eq!(c.total_ve_supply(), 7_812);
+
+    // Bob's lock expires at the first epoch boundary
+    env.ledger()
+        .with_mut(|li| li.sequence_number = VE_EPOCH_LEDGERS);
+    assert_eq!(c.ve_balance(&alice), 5_000);
+    assert_eq!(c.ve_balance(&bob), 0);
+    assert_eq!(c.total_ve_supply(), 5_000);
+    assert_eq!(c.get_past_ve_votes(&alice, &9u32), 0);
+    assert_eq!(c.get_past_ve_votes(&alice, &10u32), 9_999);
+    assert_eq!(c.get_past_ve_votes(&bob, &75_600u32), 937);
+    assert_eq!(c.get_past_total_ve_supply(&9u32), 0);
+    assert_eq!(c.get_past_total_ve_supply(&75_600u32), 7_812);
+
+    c.withdraw_lock(&bob);
+    assert!(c.get_lock(&bob).is_none());
+    c.transfer(&bob, &alice, &520_000i128);
+    assert_eq!(c.total_ve_supply(), 5_000);
+}
+
+#[test]
+fn test_extend_and_increase_lock() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (c, admin) = setup(&env);
+    let alice = Address::generate(&env);
+    c.mint(&admin, &alice, &1_040_000i128);
+
+    c.create_lock(&alice, &520_000i128, &VE_EPOCH_LEDGERS);
+    assert_eq!(c.ve_balance(&alice), 2_500);
+    c.increase_lock_amount(&alice, &520_000i128);
+    assert_eq!(c.ve_balance(&alice), 5_000);
+    c.extend_lock(&alice, &(2 * VE_EPOCH_LEDGERS));
+    assert_eq!(c.ve_balance(&alice), 10_000);
+    assert_eq!(c.total_ve_supply(), 10_000);
+}
+
+#[test]
+#[should_panic(expected = "tokens locked")]
+fn test_locked_tokens_cannot_be_transferred() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (c, admin) = setup(&env);
+    let alice = Address::generate(&env);
+    c.mint(&admin, &alice, &1_000i128);
+    c.create_lock(&alice, &600i128, &VE_EPOCH_LEDGERS);
+    c.transfer(&alice, &Address::generate(&env), &401i128);
+}
+
+#[test]
+#[should_panic(expected = "insufficient balance")]
+fn test_unvested_tokens_cannot_be_locked() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (c, admin) = setup(&env);
+    let member = Address::generate(&env);
+    c.mint(&admin, &member, &400i128);
+    c.create_vesting(&admin, &member, &1_000i128, &0u64, &0u64, &1_000u64);
+
+    // 500 of the vesting is unvested, leaving 900 of the 1_400 lockable
+    env.ledger().with_mut(|li| li.timestamp = 500);
+    c.create_lock(&member, &901i128, &VE_EPOCH_LEDGERS);
+}
+
+#[test]
+fn test_locked_balance_sums_unvested_and_escrowed() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (c, admin) = setup(&env);
+    let member = Address::generate(&env);
+    c.mint(&admin, &member, &400i128);
+    c.create_vesting(&admin, &member, &1_000i128, &0u64, &0u64, &1_000u64);
+
+    env.ledger().with_mut(|li| li.timestamp = 500);
+    c.create_lock(&member, &900i128, &VE_EPOCH_LEDGERS);
+    assert_eq!(c.locked_balance(&member), 1_400);
+    assert!(c
+        .try_transfer(&member, &Address::generate(&env), &1i128)
+        .is_err());
+}
+
+#[test]
+#[should_panic(expected = "lock not expired")]
+fn test_withdraw_lock_before_unlock_rejected() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (c, admin) = setup(&env);
+    let alice = Address::generate(&env);
+    c.mint(&admin, &alice, &1_000i128);
+    c.create_lock(&alice, &1_000i128, &VE_EPOCH_LEDGERS);
+    c.withdraw_lock(&alice);
+}
+
+#[test]
+#[should_panic(expected = "lock too long")]
+fn test_lock_longer_than_max_rejected() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (c, admin) = setup(&env);
+    let alice = Address::generate(&env);
+    c.mint(&admin, &alice, &1_000i128);
+    c.create_lock(&alice, &1_000i128, &(MAX_LOCK_LEDGERS + VE_EPOCH_LEDGERS));
+}
@@ -1,8 +1,29 @@
 //! PulsarTrack - Oracle Integration (Soroban)
 //! Price feeds and external data oracle integration on Stellar.
+//!
+//! Prices are collected in per-asset rounds. A round opens with the first
+//! submission, takes one price per oracle and publishes the median once
+//! `quorum` oracles have submitted; a round that outlives `round_duration` is
+//! discarded. Without a configured `PriceConfig` the quorum is a majority of
+//! the authorized oracles, and submissions from oracles removed mid-round no
+//! longer count. A median more than `max_deviation_bps` away from the last
+//! published price trips the asset's circuit breaker instead of publishing.
+//!
+//! SEP-40: `lastprice`, `price`, `prices`, `assets`, `base`, `decimals` and
+//! `resolution` expose the feeds registered with `register_sep40_asset` in USD
+//! with 7 decimals. Every published price is also recorded for its
+//! `RESOLUTION`-second period, stamped with the period start.
+//!
+//! Performance reports are cumulative. Each one is appended to a per-campaign
+//! ring buffer of `MAX_PERFORMANCE_RECORDS` entries, and a report whose
+//! impressions or clicks fall below the previous one is rejected. A report
+//! stored before the ring buffer existed becomes the campaign's first record,
+//! on its next report or through `migrate_performance`.
 
 #![no_std]
-use soroban_sdk::{contract, contractimpl, contracttype, symbol_short, Address, Env, String};
+use soroban_sdk::{
+    contract, contractimpl, contracttype, symbol_short, Address, Env, String, Symbol, Vec,
+};
 
 #[contracttype]
 #[derive(Clone)]
@@ -25,6 +46,53 @@ pub struct PerformanceData {
     pub timestamp: u64,
 }
 
+#[contracttype]
+#[derive(Clone)]
+pub struct PriceConfig {
+    pub quorum: u32,            // submissions needed to publish a round
+    pub round_duration: u64,    // seconds before an incomplete round is discarded
+    pub max_deviation_bps: u32, // 0 disables the circuit breaker
+}
+
+#[contracttype]
+#[derive(Clone)]
+pub struct PriceSubmission {
+    pub oracle: Address,
+    pub price_usd: i128,
+    pub confidence: u32,
+}
+
+#[contracttype]
+#[derive(Clone)]
+pub struct PriceRound {
+    pub round_id: u64,
+    pub started_at: u64,
+    pub submissions: Vec<PriceSubmission>,
+}
+
+#[contracttype]
+#[derive(Clone)]
+pub struct CircuitBreaker {
+    pub tripped_at: u64,
+    pub rejected_price: i128, // latest median refused for deviating too far
+}
+
+/// SEP-40 asset identifier
+#[contracttype]
+#[derive(Clone, PartialEq)]
+pub enum Asset {
+    Stellar(Address),
+    Other(Symbol),
+}
+
+/// SEP-40 price record
+#[contracttype]
+#[derive(Clone)]
+pub struct PriceData {
+    pub price: i128,
+    pub timestamp: u64,
+}
+
 #[contracttype]
 #[derive(Clone)]
 pub enum DataKey {
@@ -34,12 +102,34 @@ pub enum DataKey {
     PerformanceData(u64), // campaign_id
     OracleCount,
     AuthorizedOracle(Address),
+    PriceConfig,
+    PriceRound(String),   // asset symbol, the open round
+    RoundCounter(String), // asset symbol
+    Breaker(String),      // asset symbol
+    Sep40Assets,
+    Sep40Feed(Asset),            // -> feed asset symbol
+    PriceHistory(String, u64),   // asset symbol, period
+    LastPeriod(String),          // asset symbol
+    PerformanceCount(u64),       // campaign_id, records ever appended
+    PerformanceRecord(u64, u32), // campaign_id, ring buffer slot
 }
 
 const INSTANCE_LIFETIME_THRESHOLD: u32 = 17_280;
 const INSTANCE_BUMP_AMOUNT: u32 = 86_400;
 const PERSISTENT_LIFETIME_THRESHOLD: u32 = 120_960;
 const PERSISTENT_BUMP_AMOUNT: u32 = 1_051_200;
+/// Ledgers an open price round is kept for, well past any round_duration
+const ROUND_TTL: u32 = 17_280;
+/// SEP-40 period length in seconds
+const RESOLUTION: u32 = 300;
+/// Prices are USD * 10^DECIMALS
+const DECIMALS: u32 = 7;
+/// Ledgers a period's price record is kept for (about a week)
+const HISTORY_TTL: u32 = 120_960;
+/// Most periods `prices` looks back over
+const MAX_PRICE_RECORDS: u32 = 100;
+/// Performance records kept per campaign before the oldest is overwritten
+const MAX_PERFORMANCE_RECORDS: u32 = 720;
 
 #[contract]
 pub struct OracleIntegrationContract;
@@ -67,6 +157,7 @@ impl OracleIntegrationContract {
         if admin != stored_admin {
             panic!("unauthorized");
         }
+        let already = Self::is_oracle_authorized(env.clone(), oracle.clone());
         let _ttl_key = DataKey::AuthorizedOracle(oracle.clone());
         env.storage().persistent().set(&_ttl_key, &true);
         env.storage().persistent().extend_ttl(
@@ -75,14 +166,12 @@ impl OracleIntegrationContract {
             PERSISTENT_BUMP_AMOUNT,
         );
 
-        let count: u32 = env
-            .storage()
-            .instance()
-            .get(&DataKey::OracleCount)
-            .unwrap_or(0);
-        env.storage()
-            .instance()
-            .set(&DataKey::OracleCount, &(count + 1));
+        if !already {
+            let count = Self::_oracle_count(&env);
+            env.storage()
+                .instance()
+                .set(&DataKey::OracleCount, &(count + 1));
+        }
     }
 
     pub fn remove_oracle(env: Env, admin: Address, oracle: Address) {
@@ -94,11 +183,50 @@ impl OracleIntegrationContract {
         if admin != stored_admin {
             panic!("unauthorized");
         }
+        if !Self::is_oracle_authorized(env.clone(), oracle.clone()) {
+            return;
+        }
         env.storage()
             .persistent()
             .remove(&DataKey::AuthorizedOracle(oracle));
+        let count = Self::_oracle_count(&env);
+        env.storage()
+            .instance()
+            .set(&DataKey::OracleCount, &count.saturating_sub(1));
     }
 
+    pub fn set_price_config(env: Env, admin: Address, config: PriceConfig) {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        admin.require_auth();
+        let stored_admin: Address = env.storage().instance().get(&DataKey::Admin).unwrap();
+        if admin != stored_admin {
+            panic!("unauthorized");
+        }
+        if config.quorum == 0 || config.round_duration == 0 {
+            panic!("invalid config");
+        }
+        env.storage().instance().set(&DataKey::PriceConfig, &config);
+    }
+
+    /// The configured price rules, or by default a majority of the
+    /// authorized oracles over five-minute rounds
+    pub fn get_price_config(env: Env) -> PriceConfig {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        env.storage()
+            .instance()
+            .get(&DataKey::PriceConfig)
+            .unwrap_or_else(|| PriceConfig {
+                quorum: Self::_oracle_count(&env) / 2 + 1,
+                round_duration: 300,
+                max_deviation_bps: 2_000,
+            })
+    }
+
+    /// Submit a price to the asset's current round
     pub fn update_price(
         env: Env,
         oracle: Address,
@@ -112,26 +240,108 @@ impl OracleIntegrationContract {
             .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
         oracle.require_auth();
         Self::_require_oracle(&env, &oracle);
+        if price_usd <= 0 {
+            panic!("invalid price");
+        }
+        if confidence > 100 {
+            panic!("invalid confidence");
+        }
 
-        let feed = PriceFeed {
-            asset: asset.clone(),
+        let config = Self::get_price_config(env.clone());
+        let now = env.ledger().timestamp();
+        let round_key = DataKey::PriceRound(asset.clone());
+        let mut round = match env
+            .storage()
+            .temporary()
+            .get::<DataKey, PriceRound>(&round_key)
+        {
+            Some(round) if now <= round.started_at + config.round_duration => {
+                Self::_drop_removed_oracles(&env, round)
+            }
+            _ => {
+                let counter_key = DataKey::RoundCounter(asset.clone());
+                let round_id: u64 = env.storage().instance().get(&counter_key).unwrap_or(0) + 1;
+                env.storage().instance().set(&counter_key, &round_id);
+                PriceRound {
+                    round_id,
+                    started_at: now,
+                    submissions: Vec::new(&env),
+                }
+            }
+        };
+        if round.submissions.iter().any(|sub| sub.oracle == oracle) {
+            panic!("already submitted");
+        }
+        round.submissions.push_back(PriceSubmission {
+            oracle,
             price_usd,
             confidence,
-            timestamp: env.ledger().timestamp(),
+        });
+
+        env.events().publish(
+            (symbol_short!("oracle"), symbol_short!("submit")),
+            (asset.clone(), round.round_id, price_usd),
+        );
+
+        if round.submissions.len() < config.quorum {
+            env.storage().temporary().set(&round_key, &round);
+            env.storage()
+                .temporary()
+                .extend_ttl(&round_key, ROUND_TTL, ROUND_TTL);
+            return;
+        }
+        env.storage().temporary().remove(&round_key);
+
+        let source = if round.submissions.len() == 1 {
+            source
+        } else {
+            String::from_str(&env, "median")
+        };
+        let feed = PriceFeed {
+            asset: asset.clone(),
+            price_usd: Self::_median(&env, round.submissions.iter().map(|sub| sub.price_usd)),
+            confidence: Self::_median(
+                &env,
+                round.submissions.iter().map(|sub| sub.confidence as i128),
+            ) as u32,
+            timestamp: now,
             source,
         };
+        Self::_publish(&env, &config, feed);
+    }
 
-        let _ttl_key = DataKey::PriceFeed(asset.clone());
-        env.storage().persistent().set(&_ttl_key, &feed);
-        env.storage().persistent().extend_ttl(
-            &_ttl_key,
-            PERSISTENT_LIFETIME_THRESHOLD,
-            PERSISTENT_BUMP_AMOUNT,
-        );
+    /// Clear a tripped breaker, accepting its rejected price as the new
+    /// reference after the move has been confirmed off-chain.
+    pub fn reset_breaker(env: Env, admin: Address, asset: String) {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        admin.require_auth();
+        let stored_admin: Address = env.storage().instance().get(&DataKey::Admin).unwrap();
+        if admin != stored_admin {
+            panic!("unauthorized");
+        }
+        let breaker: CircuitBreaker = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Breaker(asset.clone()))
+            .expect("breaker not tripped");
+        env.storage()
+            .persistent()
+            .remove(&DataKey::Breaker(asset.clone()));
+
+        let mut feed: PriceFeed = env
+            .storage()
+            .persistent()
+            .get(&DataKey::PriceFeed(asset.clone()))
+            .expect("price not found");
+        feed.price_usd = breaker.rejected_price;
+        feed.timestamp = env.ledger().timestamp();
+        Self::_store_feed(&env, &feed);
 
         env.events().publish(
-            (symbol_short!("oracle"), symbol_short!("price")),
-            (asset, price_usd),
+            (symbol_short!("oracle"), symbol_short!("reset")),
+            (asset, breaker.rejected_price),
         );
     }
 
@@ -150,6 +360,17 @@ impl OracleIntegrationContract {
         oracle.require_auth();
         Self::_require_oracle(&env, &oracle);
 
+        if let Some(previous) = env
+            .storage()
+            .persistent()
+            .get::<DataKey, PerformanceData>(&DataKey::PerformanceData(campaign_id))
+        {
+            if impressions < previous.impressions || clicks < previous.clicks {
+                panic!("non-monotonic performance data");
+            }
+            Self::_migrate_performance(&env, campaign_id, &previous);
+        }
+
         let data = PerformanceData {
             campaign_id,
             impressions,
@@ -167,12 +388,30 @@ impl OracleIntegrationContract {
             PERSISTENT_BUMP_AMOUNT,
         );
 
+        Self::_append_performance(&env, campaign_id, &data);
+
         env.events().publish(
             (symbol_short!("oracle"), symbol_short!("perf")),
             campaign_id,
         );
     }
 
+    /// Seed the history of a campaign whose latest report predates the ring
+    /// buffer with that report. Callable by anyone.
+    pub fn migrate_performance(env: Env, campaign_id: u64) {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        let data: PerformanceData = env
+            .storage()
+            .persistent()
+            .get(&DataKey::PerformanceData(campaign_id))
+            .expect("performance not found");
+        if !Self::_migrate_performance(&env, campaign_id, &data) {
+            panic!("performance already migrated");
+        }
+    }
+
     pub fn get_price(env: Env, asset: String) -> Option<PriceFeed> {
         env.storage()
             .instance()
@@ -180,6 +419,163 @@ impl OracleIntegrationContract {
         env.storage().persistent().get(&DataKey::PriceFeed(asset))
     }
 
+    /// The published price, only if it is at most `max_age` seconds old, has
+    /// at least `min_confidence` and the asset's circuit breaker is not tripped
+    pub fn get_price_checked(
+        env: Env,
+        asset: String,
+        max_age: u64,
+        min_confidence: u32,
+    ) -> PriceFeed {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        if env
+            .storage()
+            .persistent()
+            .has(&DataKey::Breaker(asset.clone()))
+        {
+            panic!("circuit breaker tripped");
+        }
+        let feed: PriceFeed = env
+            .storage()
+            .persistent()
+            .get(&DataKey::PriceFeed(asset))
+            .expect("price not found");
+        if env.ledger().timestamp().saturating_sub(feed.timestamp) > max_age {
+            panic!("stale price");
+        }
+        if feed.confidence < min_confidence {
+            panic!("low confidence");
+        }
+        feed
+    }
+
+    pub fn get_round(env: Env, asset: String) -> Option<PriceRound> {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        env.storage()
+            .temporary()
+            .get(&DataKey::PriceRound(asset))
+            .map(|round| Self::_drop_removed_oracles(&env, round))
+    }
+
+    pub fn get_breaker(env: Env, asset: String) -> Option<CircuitBreaker> {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        env.storage().persistent().get(&DataKey::Breaker(asset))
+    }
+
+    // ─── SEP-40 ──────────────────────────────────────────────────────────────
+
+    /// Expose the feed for `feed_asset` as `asset` through the SEP-40 interface
+    pub fn register_sep40_asset(env: Env, admin: Address, asset: Asset, feed_asset: String) {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        admin.require_auth();
+        let stored_admin: Address = env.storage().instance().get(&DataKey::Admin).unwrap();
+        if admin != stored_admin {
+            panic!("unauthorized");
+        }
+        let mut assets = Self::assets(env.clone());
+        if !assets.contains(&asset) {
+            assets.push_back(asset.clone());
+            env.storage().instance().set(&DataKey::Sep40Assets, &assets);
+        }
+        let _ttl_key = DataKey::Sep40Feed(asset);
+        env.storage().persistent().set(&_ttl_key, &feed_asset);
+        env.storage().persistent().extend_ttl(
+            &_ttl_key,
+            PERSISTENT_LIFETIME_THRESHOLD,
+            PERSISTENT_BUMP_AMOUNT,
+        );
+    }
+
+    pub fn base(env: Env) -> Asset {
+        Asset::Other(Symbol::new(&env, "USD"))
+    }
+
+    pub fn assets(env: Env) -> Vec<Asset> {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        env.storage()
+            .instance()
+            .get(&DataKey::Sep40Assets)
+            .unwrap_or(Vec::new(&env))
+    }
+
+    pub fn decimals(_env: Env) -> u32 {
+        DECIMALS
+    }
+
+    pub fn resolution(_env: Env) -> u32 {
+        RESOLUTION
+    }
+
+    /// Price recorded for the period containing `timestamp`
+    pub fn price(env: Env, asset: Asset, timestamp: u64) -> Option<PriceData> {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        let feed_asset = Self::_sep40_feed(&env, &asset)?;
+        Self::_history_record(&env, &feed_asset, timestamp / RESOLUTION as u64)
+    }
+
+    /// Up to `records` prices from the most recent periods, newest first.
+    /// Periods without a published price are skipped.
+    pub fn prices(env: Env, asset: Asset, records: u32) -> Option<Vec<PriceData>> {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        let feed_asset = Self::_sep40_feed(&env, &asset)?;
+        let last_period: u64 = env
+            .storage()
+            .persistent()
+            .get(&DataKey::LastPeriod(feed_asset.clone()))?;
+
+        let mut result = Vec::new(&env);
+        let oldest = last_period.saturating_sub(MAX_PRICE_RECORDS as u64 - 1);
+        let mut period = last_period;
+        while result.len() < records {
+            if let Some(record) = Self::_history_record(&env, &feed_asset, period) {
+                result.push_back(record);
+            }
+            if period == oldest {
+                break;
+            }
+            period -= 1;
+        }
+        if result.is_empty() {
+            None
+        } else {
+            Some(result)
+        }
+    }
+
+    /// Latest published price, or None while the asset's circuit breaker is tripped
+    pub fn lastprice(env: Env, asset: Asset) -> Option<PriceData> {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        let feed_asset = Self::_sep40_feed(&env, &asset)?;
+        if env
+            .storage()
+            .persistent()
+            .has(&DataKey::Breaker(feed_asset.clone()))
+        {
+            return None;
+        }
+        let last_period: u64 = env
+            .storage()
+            .persistent()
+            .get(&DataKey::LastPeriod(feed_asset.clone()))?;
+        Self::_history_record(&env, &feed_asset, last_period)
+    }
+
     pub fn get_performance(env: Env, campaign_id: u64) -> Option<PerformanceData> {
         env.storage()
             .instance()
@@ -189,6 +585,68 @@ impl OracleIntegrationContract {
             .get(&DataKey::PerformanceData(campaign_id))
     }
 
+    /// Number of performance records ever appended for a campaign. Only the
+    /// last MAX_PERFORMANCE_RECORDS are retained.
+    pub fn get_performance_count(env: Env, campaign_id: u64) -> u32 {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        env.storage()
+            .persistent()
+            .get(&DataKey::PerformanceCount(campaign_id))
+            .unwrap_or(0)
+    }
+
+    /// The `index`-th record appended for a campaign, or None if it was never
+    /// written or has been overwritten
+    pub fn get_performance_record(
+        env: Env,
+        campaign_id: u64,
+        index: u32,
+    ) -> Option<PerformanceData> {
+        let count = Self::get_performance_count(env.clone(), campaign_id);
+        if index >= count || count - index > MAX_PERFORMANCE_RECORDS {
+            return None;
+        }
+        env.storage().persistent().get(&DataKey::PerformanceRecord(
+            campaign_id,
+            index % MAX_PERFORMANCE_RECORDS,
+        ))
+    }
+
+    /// Retained records with `from <= timestamp <= to`, oldest first, at most
+    /// `limit` of them
+    pub fn get_performance_range(
+        env: Env,
+        campaign_id: u64,
+        from: u64,
+        to: u64,
+        limit: u32,
+    ) -> Vec<PerformanceData> {
+        let count = Self::get_performance_count(env.clone(), campaign_id);
+        let mut result = Vec::new(&env);
+        let mut index = count.saturating_sub(MAX_PERFORMANCE_RECORDS);
+        while index < count && result.len() < limit {
+            let record: PerformanceData = env
+                .storage()
+                .persistent()
+                .get(&DataKey::PerformanceRecord(
+                    campaign_id,
+                    index % MAX_PERFORMANCE_RECORDS,
+                ))
+                .unwrap();
+            // Records are appended in time order
+            if record.timestamp > to {
+                break;
+            }
+            if record.timestamp >= from {
+                result.push_back(record);
+            }
+            index += 1;
+        }
+        result
+    }
+
     pub fn is_oracle_authorized(env: Env, oracle: Address) -> bool {
         env.storage()
             .instance()
@@ -199,6 +657,168 @@ impl OracleIntegrationContract {
             .unwrap_or(false)
     }
 
+    /// Publish a completed round unless it moves too far from the last
+    /// published price. A round back within range clears a tripped breaker.
+    fn _publish(env: &Env, config: &PriceConfig, feed: PriceFeed) {
+        let breaker_key = DataKey::Breaker(feed.asset.clone());
+        let previous: Option<PriceFeed> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::PriceFeed(feed.asset.clone()));
+        if let Some(previous) = previous {
+            let deviation_bps =
+                (feed.price_usd - previous.price_usd).abs() * 10_000 / previous.price_usd;
+            if config.max_deviation_bps > 0 && deviation_bps > config.max_deviation_bps as i128 {
+                let tripped_at = env
+                    .storage()
+                    .persistent()
+                    .get::<DataKey, CircuitBreaker>(&breaker_key)
+                    .map(|breaker| breaker.tripped_at)
+                    .unwrap_or(env.ledger().timestamp());
+                let breaker = CircuitBreaker {
+                    tripped_at,
+                    rejected_price: feed.price_usd,
+                };
+                env.storage().persistent().set(&breaker_key, &breaker);
+                env.storage().persistent().extend_ttl(
+                    &breaker_key,
+                    PERSISTENT_LIFETIME_THRESHOLD,
+                    PERSISTENT_BUMP_AMOUNT,
+                );
+                env.events().publish(
+                    (symbol_short!("oracle"), symbol_short!("breaker")),
+                    (feed.asset, feed.price_usd),
+                );
+                return;
+            }
+        }
+        env.storage().persistent().remove(&breaker_key);
+        Self::_store_feed(env, &feed);
+    }
+
+    fn _store_feed(env: &Env, feed: &PriceFeed) {
+        let _ttl_key = DataKey::PriceFeed(feed.asset.clone());
+        env.storage().persistent().set(&_ttl_key, feed);
+        env.storage().persistent().extend_ttl(
+            &_ttl_key,
+            PERSISTENT_LIFETIME_THRESHOLD,
+            PERSISTENT_BUMP_AMOUNT,
+        );
+
+        // Record the price for its SEP-40 period; a later price in the same
+        // period replaces it
+        let period = feed.timestamp / RESOLUTION as u64;
+        let _ttl_key = DataKey::PriceHistory(feed.asset.clone(), period);
+        env.storage().temporary().set(&_ttl_key, &feed.price_usd);
+        env.storage()
+            .temporary()
+            .extend_ttl(&_ttl_key, HISTORY_TTL, HISTORY_TTL);
+        let _ttl_key = DataKey::LastPeriod(feed.asset.clone());
+        env.storage().persistent().set(&_ttl_key, &period);
+        env.storage().persistent().extend_ttl(
+            &_ttl_key,
+            PERSISTENT_LIFETIME_THRESHOLD,
+            PERSISTENT_BUMP_AMOUNT,
+        );
+
+        env.events().publish(
+            (symbol_short!("oracle"), symbol_short!("price")),
+            (feed.asset.clone(), feed.price_usd),
+        );
+    }
+
+    fn _sep40_feed(env: &Env, asset: &Asset) -> Option<String> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Sep40Feed(asset.clone()))
+    }
+
+    fn _history_record(env: &Env, feed_asset: &String, period: u64) -> Option<PriceData> {
+        let price: i128 = env
+            .storage()
+            .temporary()
+            .get(&DataKey::PriceHistory(feed_asset.clone(), period))?;
+        Some(PriceData {
+            price,
+            timestamp: period * RESOLUTION as u64,
+        })
+    }
+
+    /// Median of the values; the mean of the middle two for an even count
+    fn _median(env: &Env, values: impl Iterator<Item = i128>) -> i128 {
+        let mut sorted: Vec<i128> = Vec::new(env);
+        for value in values {
+            let mut pos = sorted.len();
+            while pos > 0 && sorted.get_unchecked(pos - 1) > value {
+                pos -= 1;
+            }
+            sorted.insert(pos, value);
+        }
+        let mid = sorted.len() / 2;
+        if sorted.len() % 2 == 1 {
+            sorted.get_unchecked(mid)
+        } else {
+            (sorted.get_unchecked(mid - 1) + sorted.get_unchecked(mid)) / 2
+        }
+    }
+
+    /// Ring buffer: overwrite the oldest record once MAX_PERFORMANCE_RECORDS
+    /// is reached
+    fn _append_performance(env: &Env, campaign_id: u64, data: &PerformanceData) {
+        let count: u32 = env
+            .storage()
+            .persistent()
+            .get(&DataKey::PerformanceCount(campaign_id))
+            .unwrap_or(0);
+        let _ttl_key = DataKey::PerformanceRecord(campaign_id, count % MAX_PERFORMANCE_RECORDS);
+        env.storage().persistent().set(&_ttl_key, data);
+        env.storage().persistent().extend_ttl(
+            &_ttl_key,
+            PERSISTENT_LIFETIME_THRESHOLD,
+            PERSISTENT_BUMP_AMOUNT,
+        );
+        let _ttl_key = DataKey::PerformanceCount(campaign_id);
+        env.storage().persistent().set(&_ttl_key, &(count + 1));
+        env.storage().persistent().extend_ttl(
+            &_ttl_key,
+            PERSISTENT_LIFETIME_THRESHOLD,
+            PERSISTENT_BUMP_AMOUNT,
+        );
+    }
+
+    /// Every report since the ring buffer also wrote `PerformanceCount`, so a
+    /// stored report without one predates it. Returns whether it was moved.
+    fn _migrate_performance(env: &Env, campaign_id: u64, legacy: &PerformanceData) -> bool {
+        if env
+            .storage()
+            .persistent()
+            .has(&DataKey::PerformanceCount(campaign_id))
+        {
+            return false;
+        }
+        Self::_append_performance(env, campaign_id, legacy);
+        true
+    }
+
+    fn _oracle_count(env: &Env) -> u32 {
+        env.storage()
+            .instance()
+            .get(&DataKey::OracleCount)
+            .unwrap_or(0)
+    }
+
+    /// Submissions only count while their oracle is still authorized
+    fn _drop_removed_oracles(env: &Env, mut round: PriceRound) -> PriceRound {
+        let mut kept = Vec::new(env);
+        for sub in round.submissions.iter() {
+            if Self::is_oracle_authorized(env.clone(), sub.oracle.clone()) {
+                kept.push_back(sub);
+            }
+        }
+        round.submissions = kept;
+        round
+    }
+
     fn _require_oracle(env: &Env, oracle: &Address) {
         let is_auth: bool = env
             .storage()
@@ -1,6 +1,9 @@
 #![cfg(test)]
 use super::*;
-use soroban_sdk::{testutils::Address as _, Address, Env, String};
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    Address, Env, String, Symbol,
+};
 
 fn setup(env: &Env) -> (OracleIntegrationContractClient<'_>, Address) {
     let admin = Address::generate(env);
@@ -101,6 +104,136 @@ fn test_update_performance() {
     assert_eq!(perf.impressions, 1000);
 }
 
+#[test]
+fn test_performance_history() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (c, admin) = setup(&env);
+    let oracle = Address::generate(&env);
+    c.add_oracle(&admin, &oracle);
+    for i in 0..4u64 {
+        env.ledger().with_mut(|li| li.timestamp = i * 100);
+        c.update_performance(&oracle, &1u64, &(1000 * (i + 1)), &(50 * i), &0u64, &10u32);
+    }
+
+    assert_eq!(c.get_performance_count(&1u64), 4);
+    assert_eq!(
+        c.get_performance_record(&1u64, &0u32).unwrap().impressions,
+        1000
+    );
+    assert!(c.get_performance_record(&1u64, &4u32).is_none());
+    assert_eq!(c.get_performance(&1u64).unwrap().impressions, 4000);
+
+    let range = c.get_performance_range(&1u64, &100u64, &250u64, &10u32);
+    assert_eq!(range.len(), 2);
+    assert_eq!(range.get(0).unwrap().timestamp, 100);
+    assert_eq!(range.get(1).unwrap().timestamp, 200);
+    assert_eq!(
+        c.get_performance_range(&1u64, &0u64, &1_000u64, &3u32)
+            .len(),
+        3
+    );
+}
+
+#[test]
+fn test_performance_ring_buffer_wraps() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (c, admin) = setup(&env);
+    let oracle = Address::generate(&env);
+    c.add_oracle(&admin, &oracle);
+    c.update_performance(&oracle, &1u64, &1u64, &0u64, &0u64, &0u32);
+
+    // Skip ahead so the next report lands back in slot 0
+    env.as_contract(&c.address, || {
+        env.storage()
+            .persistent()
+            .set(&DataKey::PerformanceCount(1), &MAX_PERFORMANCE_RECORDS);
+    });
+    c.update_performance(&oracle, &1u64, &2u64, &0u64, &0u64, &0u32);
+
+    assert_eq!(c.get_performance_count(&1u64), MAX_PERFORMANCE_RECORDS + 1);
+    // The oldest record has been overwritten
+    assert!(c.get_performance_record(&1u64, &0u32).is_none());
+    assert_eq!(
+        c.get_performance_record(&1u64, &MAX_PERFORMANCE_RECORDS)
+            .unwrap()
+            .impressions,
+        2
+    );
+}
+
+/// A report stored before the ring buffer existed
+fn legacy_performance(env: &Env, c: &OracleIntegrationContractClient, campaign_id: u64) {
+    env.as_contract(&c.address, || {
+        env.storage().persistent().set(
+            &DataKey::PerformanceData(campaign_id),
+            &PerformanceData {
+                campaign_id,
+                impressions: 500,
+                clicks: 20,
+                conversions: 1,
+                fraud_score: 5,
+                timestamp: 0,
+            },
+        );
+    });
+}
+
+#[test]
+fn test_legacy_performance_seeds_history() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (c, admin) = setup(&env);
+    let oracle = Address::generate(&env);
+    c.add_oracle(&admin, &oracle);
+    legacy_performance(&env, &c, 1);
+    assert_eq!(c.get_performance_count(&1u64), 0);
+
+    env.ledger().with_mut(|li| li.timestamp = 100);
+    c.update_performance(&oracle, &1u64, &800u64, &30u64, &2u64, &5u32);
+    assert_eq!(c.get_performance_count(&1u64), 2);
+    assert_eq!(
+        c.get_performance_record(&1u64, &0u32).unwrap().impressions,
+        500
+    );
+    assert_eq!(
+        c.get_performance_record(&1u64, &1u32).unwrap().impressions,
+        800
+    );
+}
+
+#[test]
+fn test_migrate_performance() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (c, _) = setup(&env);
+    legacy_performance(&env, &c, 1);
+
+    c.migrate_performance(&1u64);
+    assert_eq!(c.get_performance_count(&1u64), 1);
+    assert_eq!(
+        c.get_performance_range(&1u64, &0u64, &u64::MAX, &10u32)
+            .get(0)
+            .unwrap()
+            .clicks,
+        20
+    );
+    assert!(c.try_migrate_performance(&1u64).is_err());
+}
+
+#[test]
+#[should_panic(expected = "non-monotonic performance data")]
+fn test_update_performance_rejects_decreasing_clicks() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (c, admin) = setup(&env);
+    let oracle = Address::generate(&env);
+    c.add_oracle(&admin, &oracle);
+    c.update_performance(&oracle, &1u64, &1000u64, &50u64, &5u64, &10u32);
+    c.update_performance(&oracle, &1u64, &1200u64, &40u64, &5u64, &10u32);
+}
+
 #[test]
 fn test_get_price_nonexistent() {
     let env = Env::default();
@@ -116,3 +249,310 @@ fn test_is_oracle_authorized_false() {
     let (c, _) = setup(&env);
     assert!(!c.is_oracle_authorized(&Address::generate(&env)));
 }
+
+// ─── price rounds ────────────────────────────────────────────────────────────
+
+fn setup_oracles(env: &Env, c: &OracleIntegrationContractClient, admin: &Address) -> [Address; 3] {
+    let oracles = [
+        Address::generate(env),
+        Address::generate(env),
+        Address::generate(env),
+    ];
+    for oracle in oracles.iter() {
+        c.add_oracle(admin, oracle);
+    }
+    c.set_price_config(
+        admin,
+        &PriceConfig {
+            quorum: 3,
+            round_duration: 60,
+            max_deviation_bps: 2_000,
+        },
+    );
+    oracles
+}
+
+fn submit(
+    env: &Env,
+    c: &OracleIntegrationContractClient,
+    oracle: &Address,
+    price: i128,
+    confidence: u32,
+) {
+    c.update_price(oracle, &s(env, "XLM"), &price, &confidence, &s(env, "feed"));
+}
+
+fn single_submission_rounds(c: &OracleIntegrationContractClient, admin: &Address) {
+    c.set_price_config(
+        admin,
+        &PriceConfig {
+            quorum: 1,
+            round_duration: 300,
+            max_deviation_bps: 2_000,
+        },
+    );
+}
+
+#[test]
+fn test_default_quorum_is_oracle_majority() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (c, admin) = setup(&env);
+    assert_eq!(c.get_price_config().quorum, 1);
+
+    let oracles = [
+        Address::generate(&env),
+        Address::generate(&env),
+        Address::generate(&env),
+    ];
+    for oracle in oracles.iter() {
+        c.add_oracle(&admin, oracle);
+    }
+    // Re-adding an oracle doesn't count it twice
+    c.add_oracle(&admin, &oracles[0]);
+    assert_eq!(c.get_price_config().quorum, 2);
+
+    submit(&env, &c, &oracles[0], 1_000_000, 90);
+    assert!(c.get_price(&s(&env, "XLM")).is_none());
+    submit(&env, &c, &oracles[1], 1_200_000, 90);
+    assert_eq!(c.get_price(&s(&env, "XLM")).unwrap().price_usd, 1_100_000);
+
+    c.remove_oracle(&admin, &oracles[2]);
+    c.remove_oracle(&admin, &oracles[2]);
+    assert_eq!(c.get_price_config().quorum, 2);
+}
+
+#[test]
+fn test_removed_oracle_submission_dropped_from_round() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (c, admin) = setup(&env);
+    let [o1, o2, o3] = setup_oracles(&env, &c, &admin);
+    let o4 = Address::generate(&env);
+    c.add_oracle(&admin, &o4);
+
+    submit(&env, &c, &o1, 9_000_000, 90);
+    submit(&env, &c, &o2, 1_000_000, 90);
+    c.remove_oracle(&admin, &o1);
+    assert_eq!(c.get_round(&s(&env, "XLM")).unwrap().submissions.len(), 1);
+
+    // o1's price no longer counts towards quorum or the median
+    submit(&env, &c, &o3, 1_100_000, 80);
+    assert!(c.get_price(&s(&env, "XLM")).is_none());
+    submit(&env, &c, &o4, 1_200_000, 70);
+    assert_eq!(c.get_price(&s(&env, "XLM")).unwrap().price_usd, 1_100_000);
+}
+
+#[test]
+fn test_round_publishes_median_at_quorum() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (c, admin) = setup(&env);
+    let [o1, o2, o3] = setup_oracles(&env, &c, &admin);
+
+    submit(&env, &c, &o1, 1_000_000, 90);
+    submit(&env, &c, &o2, 5_000_000, 60);
+    assert!(c.get_price(&s(&env, "XLM")).is_none());
+    assert_eq!(c.get_round(&s(&env, "XLM")).unwrap().submissions.len(), 2);
+
+    submit(&env, &c, &o3, 1_100_000, 80);
+    let feed = c.get_price(&s(&env, "XLM")).unwrap();
+    assert_eq!(feed.price_usd, 1_100_000);
+    assert_eq!(feed.confidence, 80);
+    assert_eq!(feed.source, s(&env, "median"));
+    assert!(c.get_round(&s(&env, "XLM")).is_none());
+}
+
+#[test]
+#[should_panic(expected = "already submitted")]
+fn test_round_one_submission_per_oracle() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (c, admin) = setup(&env);
+    let [o1, _, _] = setup_oracles(&env, &c, &admin);
+    submit(&env, &c, &o1, 1_000_000, 90);
+    submit(&env, &c, &o1, 1_000_000, 90);
+}
+
+#[test]
+fn test_expired_round_is_discarded() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (c, admin) = setup(&env);
+    let [o1, o2, o3] = setup_oracles(&env, &c, &admin);
+
+    submit(&env, &c, &o1, 1_000_000, 90);
+    submit(&env, &c, &o2, 1_000_000, 90);
+    env.ledger().with_mut(|li| li.timestamp = 61);
+    submit(&env, &c, &o3, 1_000_000, 90);
+
+    assert!(c.get_price(&s(&env, "XLM")).is_none());
+    let round = c.get_round(&s(&env, "XLM")).unwrap();
+    assert_eq!(round.round_id, 2);
+    assert_eq!(round.submissions.len(), 1);
+}
+
+#[test]
+fn test_get_price_checked() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (c, admin) = setup(&env);
+    let oracle = Address::generate(&env);
+    c.add_oracle(&admin, &oracle);
+    submit(&env, &c, &oracle, 1_500_000, 95);
+
+    env.ledger().with_mut(|li| li.timestamp = 100);
+    let feed = c.get_price_checked(&s(&env, "XLM"), &100u64, &90u32);
+    assert_eq!(feed.price_usd, 1_500_000);
+    assert!(c
+        .try_get_price_checked(&s(&env, "XLM"), &99u64, &90u32)
+        .is_err());
+    assert!(c
+        .try_get_price_checked(&s(&env, "XLM"), &100u64, &96u32)
+        .is_err());
+}
+
+#[test]
+#[should_panic(expected = "stale price")]
+fn test_get_price_checked_stale() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (c, admin) = setup(&env);
+    let oracle = Address::generate(&env);
+    c.add_oracle(&admin, &oracle);
+    submit(&env, &c, &oracle, 1_500_000, 95);
+    env.ledger().with_mut(|li| li.timestamp = 3_601);
+    c.get_price_checked(&s(&env, "XLM"), &3_600u64, &0u32);
+}
+
+#[test]
+fn test_circuit_breaker_trips_and_recovers() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (c, admin) = setup(&env);
+    let oracle = Address::generate(&env);
+    c.add_oracle(&admin, &oracle);
+    let other = Address::generate(&env);
+    c.add_oracle(&admin, &other);
+    single_submission_rounds(&c, &admin);
+
+    submit(&env, &c, &oracle, 1_000_000, 95);
+    // +50% is beyond the default 20% limit
+    submit(&env, &c, &other, 1_500_000, 95);
+    assert_eq!(c.get_price(&s(&env, "XLM")).unwrap().price_usd, 1_000_000);
+    assert_eq!(
+        c.get_breaker(&s(&env, "XLM")).unwrap().rejected_price,
+        1_500_000
+    );
+    assert!(c
+        .try_get_price_checked(&s(&env, "XLM"), &3_600u64, &0u32)
+        .is_err());
+
+    // A round back within range publishes and clears the breaker
+    submit(&env, &c, &oracle, 1_100_000, 95);
+    assert!(c.get_breaker(&s(&env, "XLM")).is_none());
+    assert_eq!(
+        c.get_price_checked(&s(&env, "XLM"), &3_600u64, &0u32)
+            .price_usd,
+        1_100_000
+    );
+}
+
+#[test]
+fn test_reset_breaker_accepts_rejected_price() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (c, admin) = setup(&env);
+    let oracle = Address::generate(&env);
+    c.add_oracle(&admin, &oracle);
+    let other = Address::generate(&env);
+    c.add_oracle(&admin, &other);
+    single_submission_rounds(&c, &admin);
+
+    submit(&env, &c, &oracle, 1_000_000, 95);
+    submit(&env, &c, &other, 3_000_000, 95);
+    c.reset_breaker(&admin, &s(&env, "XLM"));
+
+    assert!(c.get_breaker(&s(&env, "XLM")).is_none());
+    assert_eq!(c.get_price(&s(&env, "XLM")).unwrap().price_usd, 3_000_000);
+}
+
+#[test]
+#[should_panic(expected = "invalid price")]
+fn test_update_price_rejects_non_positive() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (c, admin) = setup(&env);
+    let oracle = Address::generate(&env);
+    c.add_oracle(&admin, &oracle);
+    submit(&env, &c, &oracle, 0, 95);
+}
+
+// ─── SEP-40 ──────────────────────────────────────────────────────────────────
+
+#[test]
+fn test_sep40_lastprice_and_history() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (c, admin) = setup(&env);
+    let oracle = Address::generate(&env);
+    c.add_oracle(&admin, &oracle);
+    let xlm = Asset::Other(Symbol::new(&env, "XLM"));
+    c.register_sep40_asset(&admin, &xlm, &s(&env, "XLM"));
+    assert_eq!(c.assets().len(), 1);
+    assert_eq!(c.decimals(), 7);
+    assert_eq!(c.resolution(), 300);
+    assert!(c.lastprice(&xlm).is_none());
+
+    env.ledger().with_mut(|li| li.timestamp = 310);
+    submit(&env, &c, &oracle, 1_000_000, 95);
+    // A later price in the same period replaces the record
+    env.ledger().with_mut(|li| li.timestamp = 550);
+    submit(&env, &c, &oracle, 1_050_000, 95);
+    // Period 2 has no price
+    env.ledger().with_mut(|li| li.timestamp = 905);
+    submit(&env, &c, &oracle, 1_100_000, 95);
+
+    let last = c.lastprice(&xlm).unwrap();
+    assert_eq!(last.price, 1_100_000);
+    assert_eq!(last.timestamp, 900);
+    assert_eq!(c.price(&xlm, &420u64).unwrap().price, 1_050_000);
+    assert!(c.price(&xlm, &600u64).is_none());
+
+    let history = c.prices(&xlm, &5u32).unwrap();
+    assert_eq!(history.len(), 2);
+    assert_eq!(history.get(0).unwrap().timestamp, 900);
+    assert_eq!(history.get(1).unwrap().timestamp, 300);
+    assert_eq!(c.prices(&xlm, &1u32).unwrap().len(), 1);
+}
+
+#[test]
+fn test_sep40_unknown_asset_and_tripped_breaker() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (c, admin) = setup(&env);
+    let oracle = Address::generate(&env);
+    c.add_oracle(&admin, &oracle);
+    let xlm = Asset::Stellar(Address::generate(&env));
+    assert!(c.lastprice(&xlm).is_none());
+    assert!(c.prices(&xlm, &3u32).is_none());
+
+    c.register_sep40_asset(&admin, &xlm, &s(&env, "XLM"));
+    submit(&env, &c, &oracle, 1_000_000, 95);
+    submit(&env, &c, &oracle, 2_000_000, 95);
+    assert!(c.get_breaker(&s(&env, "XLM")).is_some());
+    assert!(c.lastprice(&xlm).is_none());
+}
+
+#[test]
+#[should_panic(expected = "unauthorized")]
+fn test_register_sep40_asset_unauthorized() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (c, _) = setup(&env);
+    c.register_sep40_asset(
+        &Address::generate(&env),
+        &Asset::Other(Symbol::new(&env, "XLM")),
+        &s(&env, "XLM"),
+    );
+}
@@ -1,8 +1,24 @@
 //! PulsarTrack - Performance Oracle (Soroban)
 //! Validates and attests to campaign performance metrics on Stellar.
+//!
+//! Consensus: each metric is anchored on the median attestation. Attestations
+//! whose impressions or clicks deviate from the median by more than
+//! `MaxDeviationBps` are dissenting; the consensus values are the mean of the
+//! agreeing attestations, and consensus is only reached when the agreeing share
+//! meets `ConsensusThresholdPct`.
+//!
+//! Staking: once a `StakingConfig` is set, attesters must bond at least
+//! `min_stake` to attest. `finalize_consensus` settles a campaign: attestations
+//! within `MaxDeviationBps` of the final consensus earn `attestation_reward`
+//! from the reward pool, the rest are slashed by `slash_bps` into the pool.
+//! Unbonded stake stays slashable until `unbonding_period` has passed.
+//! `stake_token` can't change while any stake, unclaimed reward or reward
+//! pool balance is held in it.
 
 #![no_std]
-use soroban_sdk::{contract, contractimpl, contracttype, symbol_short, Address, BytesN, Env};
+use soroban_sdk::{
+    contract, contractimpl, contracttype, symbol_short, token, Address, BytesN, Env, Vec,
+};
 
 #[contracttype]
 #[derive(Clone)]
@@ -31,6 +47,46 @@ pub struct OracleConsensus {
     pub last_updated: u64,
 }
 
+/// Who agreed with the latest consensus. Kept under its own key so
+/// `OracleConsensus` records written before outlier rejection still decode.
+#[contracttype]
+#[derive(Clone)]
+pub struct ConsensusAgreement {
+    pub agreeing_attesters: u32,
+    pub dissenters: Vec<Address>,
+}
+
+#[contracttype]
+#[derive(Clone)]
+pub struct StakingConfig {
+    pub stake_token: Address,
+    pub min_stake: i128,
+    pub attestation_reward: i128, // paid from the reward pool per accepted attestation
+    pub slash_bps: u32,
+    pub unbonding_period: u64, // seconds unbonded stake stays slashable
+}
+
+#[contracttype]
+#[derive(Clone)]
+pub struct AttesterInfo {
+    pub stake: i128,
+    pub unbonding: i128,
+    pub unbonding_until: u64,
+    pub pending_rewards: i128,
+    pub slashed_total: i128,
+    pub accepted: u32,
+    pub rejected: u32,
+}
+
+#[contracttype]
+#[derive(Clone)]
+pub struct AccuracyRecord {
+    pub campaign_id: u64,
+    pub accepted: bool,
+    pub deviation_bps: u32, // worst of impressions and clicks vs the consensus
+    pub settled_at: u64,
+}
+
 #[contracttype]
 #[derive(Clone)]
 pub enum DataKey {
@@ -39,16 +95,30 @@ pub enum DataKey {
     MinAttesters,
     ConsensusThresholdPct,
     Attester(Address),
-    Attestation(u64, Address), // campaign_id, attester
-    AttestationCount(u64),     // campaign_id
-    Consensus(u64),            // campaign_id
+    Attestation(u64, Address),       // campaign_id, attester
+    AttestationCount(u64),           // campaign_id
+    Consensus(u64),                  // campaign_id
     CampaignAttesterIndex(u64, u32), // campaign_id, index -> Address
+    MaxDeviationBps,
+    StakingConfig,
+    AttesterInfo(Address),
+    AccuracyHistory(Address),
+    RewardPool,
+    Finalized(u64),            // campaign_id
+    ConsensusAgreement(u64),   // campaign_id
+    OpenAttestations(Address), // attestations awaiting finalize_consensus
+    StakeHeld,                 // bonded, unbonding and unclaimed reward balances
 }
 
 const INSTANCE_LIFETIME_THRESHOLD: u32 = 17_280;
 const INSTANCE_BUMP_AMOUNT: u32 = 86_400;
 const PERSISTENT_LIFETIME_THRESHOLD: u32 = 120_960;
 const PERSISTENT_BUMP_AMOUNT: u32 = 1_051_200;
+/// Accuracy records kept per attester, oldest dropped first
+const MAX_HISTORY: u32 = 50;
+/// Seconds after a campaign's first attestation before it can be settled
+/// against the median even without consensus
+const SETTLEMENT_DEADLINE: u64 = 7 * 86_400;
 
 #[contract]
 pub struct PerformanceOracleContract;
@@ -70,6 +140,55 @@ impl PerformanceOracleContract {
         env.storage()
             .instance()
             .set(&DataKey::ConsensusThresholdPct, &67u32); // 2/3 majority
+        env.storage()
+            .instance()
+            .set(&DataKey::MaxDeviationBps, &1_000u32); // 10% from the median
+    }
+
+    pub fn set_consensus_params(
+        env: Env,
+        admin: Address,
+        threshold_pct: u32,
+        max_deviation_bps: u32,
+    ) {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        admin.require_auth();
+        let stored_admin: Address = env.storage().instance().get(&DataKey::Admin).unwrap();
+        if admin != stored_admin {
+            panic!("unauthorized");
+        }
+        if threshold_pct == 0 || threshold_pct > 100 {
+            panic!("invalid threshold");
+        }
+        if max_deviation_bps > 10_000 {
+            panic!("invalid deviation");
+        }
+        env.storage()
+            .instance()
+            .set(&DataKey::ConsensusThresholdPct, &threshold_pct);
+        env.storage()
+            .instance()
+            .set(&DataKey::MaxDeviationBps, &max_deviation_bps);
+    }
+
+    /// (ConsensusThresholdPct, MaxDeviationBps)
+    pub fn get_consensus_params(env: Env) -> (u32, u32) {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        let threshold_pct: u32 = env
+            .storage()
+            .instance()
+            .get(&DataKey::ConsensusThresholdPct)
+            .unwrap_or(67);
+        let max_deviation_bps: u32 = env
+            .storage()
+            .instance()
+            .get(&DataKey::MaxDeviationBps)
+            .unwrap_or(1_000);
+        (threshold_pct, max_deviation_bps)
     }
 
     pub fn authorize_attester(env: Env, admin: Address, attester: Address) {
@@ -114,6 +233,22 @@ impl PerformanceOracleContract {
         if !is_auth {
             panic!("not authorized attester");
         }
+        if let Some(config) = env
+            .storage()
+            .instance()
+            .get::<DataKey, StakingConfig>(&DataKey::StakingConfig)
+        {
+            if Self::_load_attester(&env, &attester).stake < config.min_stake {
+                panic!("insufficient stake");
+            }
+        }
+        if env
+            .storage()
+            .persistent()
+            .has(&DataKey::Finalized(campaign_id))
+        {
+            panic!("consensus finalized");
+        }
 
         if env
             .storage()
@@ -166,6 +301,9 @@ impl PerformanceOracleContract {
             PERSISTENT_BUMP_AMOUNT,
         );
 
+        // Stake stays locked until this attestation is settled
+        Self::_adjust_open_attestations(&env, &attester, 1);
+
         // Attempt to build consensus with actual averaging
         Self::_try_build_consensus(&env, campaign_id, count + 1);
 
@@ -175,6 +313,331 @@ impl PerformanceOracleContract {
         );
     }
 
+    // ─── Staking ─────────────────────────────────────────────────────────────
+
+    pub fn set_staking_config(env: Env, admin: Address, config: StakingConfig) {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        admin.require_auth();
+        let stored_admin: Address = env.storage().instance().get(&DataKey::Admin).unwrap();
+        if admin != stored_admin {
+            panic!("unauthorized");
+        }
+        if config.min_stake <= 0 || config.attestation_reward < 0 {
+            panic!("invalid config");
+        }
+        if config.slash_bps > 10_000 {
+            panic!("invalid slash bps");
+        }
+        if let Some(current) = env
+            .storage()
+            .instance()
+            .get::<DataKey, StakingConfig>(&DataKey::StakingConfig)
+        {
+            if current.stake_token != config.stake_token
+                && (Self::_stake_held(&env) > 0 || Self::get_reward_pool(env.clone()) > 0)
+            {
+                panic!("stake token in use");
+            }
+        }
+        env.storage()
+            .instance()
+            .set(&DataKey::StakingConfig, &config);
+    }
+
+    pub fn bond_stake(env: Env, attester: Address, amount: i128) {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        attester.require_auth();
+        if amount <= 0 {
+            panic!("invalid amount");
+        }
+        let config = Self::_load_staking_config(&env);
+
+        token::Client::new(&env, &config.stake_token).transfer(
+            &attester,
+            &env.current_contract_address(),
+            &amount,
+        );
+
+        let mut info = Self::_load_attester(&env, &attester);
+        info.stake += amount;
+        Self::_save_attester(&env, &attester, &info);
+        Self::_adjust_stake_held(&env, amount);
+
+        env.events().publish(
+            (symbol_short!("attester"), symbol_short!("bonded")),
+            (attester, amount),
+        );
+    }
+
+    /// Move stake into unbonding. It can be withdrawn once the unbonding
+    /// period has passed and can still be slashed until then; unbonding more
+    /// restarts the period for the whole unbonding amount.
+    pub fn unbond_stake(env: Env, attester: Address, amount: i128) {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        attester.require_auth();
+        if amount <= 0 {
+            panic!("invalid amount");
+        }
+        let config = Self::_load_staking_config(&env);
+        let mut info = Self::_load_attester(&env, &attester);
+        if amount > info.stake {
+            panic!("insufficient stake");
+        }
+
+        info.stake -= amount;
+        info.unbonding += amount;
+        info.unbonding_until = env.ledger().timestamp() + config.unbonding_period;
+        Self::_save_attester(&env, &attester, &info);
+
+        env.events().publish(
+            (symbol_short!("attester"), symbol_short!("unbonding")),
+            (attester, amount),
+        );
+    }
+
+    pub fn withdraw_unbonded(env: Env, attester: Address) -> i128 {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        attester.require_auth();
+        let config = Self::_load_staking_config(&env);
+        let mut info = Self::_load_attester(&env, &attester);
+        let amount = info.unbonding;
+        if amount <= 0 {
+            panic!("nothing unbonding");
+        }
+        if env.ledger().timestamp() < info.unbonding_until {
+            panic!("unbonding period not over");
+        }
+        if Self::get_open_attestations(env.clone(), attester.clone()) > 0 {
+            panic!("attestations pending settlement");
+        }
+        info.unbonding = 0;
+        Self::_save_attester(&env, &attester, &info);
+        Self::_adjust_stake_held(&env, -amount);
+
+        token::Client::new(&env, &config.stake_token).transfer(
+            &env.current_contract_address(),
+            &attester,
+            &amount,
+        );
+        amount
+    }
+
+    pub fn fund_reward_pool(env: Env, funder: Address, amount: i128) {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        funder.require_auth();
+        if amount <= 0 {
+            panic!("invalid amount");
+        }
+        let config = Self::_load_staking_config(&env);
+        token::Client::new(&env, &config.stake_token).transfer(
+            &funder,
+            &env.current_contract_address(),
+            &amount,
+        );
+        let pool = Self::get_reward_pool(env.clone());
+        env.storage()
+            .instance()
+            .set(&DataKey::RewardPool, &(pool + amount));
+    }
+
+    pub fn claim_rewards(env: Env, attester: Address) -> i128 {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        attester.require_auth();
+        let config = Self::_load_staking_config(&env);
+        let mut info = Self::_load_attester(&env, &attester);
+        let amount = info.pending_rewards;
+        if amount <= 0 {
+            panic!("no rewards");
+        }
+        info.pending_rewards = 0;
+        Self::_save_attester(&env, &attester, &info);
+        Self::_adjust_stake_held(&env, -amount);
+
+        token::Client::new(&env, &config.stake_token).transfer(
+            &env.current_contract_address(),
+            &attester,
+            &amount,
+        );
+        amount
+    }
+
+    /// Settle a campaign once consensus is reached: reward attestations within
+    /// `MaxDeviationBps` of the consensus and slash the rest. Without
+    /// consensus the campaign settles against the median attestation once
+    /// `SETTLEMENT_DEADLINE` has passed since the first attestation. Callable
+    /// by anyone; closes the campaign to further attestations.
+    pub fn finalize_consensus(env: Env, campaign_id: u64) {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        let finalized_key = DataKey::Finalized(campaign_id);
+        if env.storage().persistent().has(&finalized_key) {
+            panic!("consensus finalized");
+        }
+        let total_attesters = Self::get_attestation_count(env.clone(), campaign_id);
+        let mut attestations: Vec<PerformanceAttestation> = Vec::new(&env);
+        for i in 0..total_attesters {
+            let attester: Address = env
+                .storage()
+                .persistent()
+                .get(&DataKey::CampaignAttesterIndex(campaign_id, i))
+                .expect("attester index not found");
+            attestations.push_back(
+                env.storage()
+                    .persistent()
+                    .get(&DataKey::Attestation(campaign_id, attester))
+                    .expect("attestation not found"),
+            );
+        }
+
+        let consensus: Option<OracleConsensus> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Consensus(campaign_id));
+        let (reference_impressions, reference_clicks) = match consensus {
+            Some(consensus) if consensus.consensus_reached => {
+                (consensus.avg_impressions, consensus.avg_clicks)
+            }
+            _ => {
+                let first = match attestations.first() {
+                    Some(first) => first,
+                    None => panic!("consensus not found"),
+                };
+                if env.ledger().timestamp() < first.attested_at + SETTLEMENT_DEADLINE {
+                    panic!("consensus not reached");
+                }
+                (
+                    Self::_median(&env, attestations.iter().map(|a| a.impressions_verified)),
+                    Self::_median(&env, attestations.iter().map(|a| a.clicks_verified)),
+                )
+            }
+        };
+        env.storage().persistent().set(&finalized_key, &true);
+        env.storage().persistent().extend_ttl(
+            &finalized_key,
+            PERSISTENT_LIFETIME_THRESHOLD,
+            PERSISTENT_BUMP_AMOUNT,
+        );
+
+        let config: Option<StakingConfig> = env.storage().instance().get(&DataKey::StakingConfig);
+        let (_, max_deviation_bps) = Self::get_consensus_params(env.clone());
+        let mut pool = Self::get_reward_pool(env.clone());
+        let pool_before = pool;
+
+        for attestation in attestations.iter() {
+            let attester = attestation.attester.clone();
+            Self::_adjust_open_attestations(&env, &attester, -1);
+
+            let deviation_bps =
+                Self::_deviation_bps(attestation.impressions_verified, reference_impressions).max(
+                    Self::_deviation_bps(attestation.clicks_verified, reference_clicks),
+                );
+            let accepted = deviation_bps <= max_deviation_bps;
+
+            let mut info = Self::_load_attester(&env, &attester);
+            if accepted {
+                info.accepted += 1;
+                if let Some(config) = &config {
+                    let reward = config.attestation_reward.min(pool);
+                    pool -= reward;
+                    info.pending_rewards += reward;
+                }
+            } else {
+                info.rejected += 1;
+                if let Some(config) = &config {
+                    let slashed = Self::_slash(&mut info, config.slash_bps);
+                    pool += slashed;
+                    if slashed > 0 {
+                        env.events().publish(
+                            (symbol_short!("attester"), symbol_short!("slashed")),
+                            (attester.clone(), campaign_id, slashed),
+                        );
+                    }
+                }
+            }
+            Self::_save_attester(&env, &attester, &info);
+            Self::_record_accuracy(
+                &env,
+                &attester,
+                AccuracyRecord {
+                    campaign_id,
+                    accepted,
+                    deviation_bps,
+                    settled_at: env.ledger().timestamp(),
+                },
+            );
+        }
+
+        env.storage().instance().set(&DataKey::RewardPool, &pool);
+        // Rewards leave the pool for attesters and slashes come back into it
+        Self::_adjust_stake_held(&env, pool_before - pool);
+        env.events().publish(
+            (symbol_short!("oracle"), symbol_short!("finalized")),
+            campaign_id,
+        );
+    }
+
+    pub fn is_finalized(env: Env, campaign_id: u64) -> bool {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        env.storage()
+            .persistent()
+            .has(&DataKey::Finalized(campaign_id))
+    }
+
+    /// Attestations still awaiting settlement; unbonded stake can't be
+    /// withdrawn while any remain
+    pub fn get_open_attestations(env: Env, attester: Address) -> u32 {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        env.storage()
+            .persistent()
+            .get(&DataKey::OpenAttestations(attester))
+            .unwrap_or(0)
+    }
+
+    pub fn get_attester_info(env: Env, attester: Address) -> AttesterInfo {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        Self::_load_attester(&env, &attester)
+    }
+
+    /// Most recent settlements first
+    pub fn get_accuracy_history(env: Env, attester: Address) -> Vec<AccuracyRecord> {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        env.storage()
+            .persistent()
+            .get(&DataKey::AccuracyHistory(attester))
+            .unwrap_or(Vec::new(&env))
+    }
+
+    pub fn get_reward_pool(env: Env) -> i128 {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        env.storage()
+            .instance()
+            .get(&DataKey::RewardPool)
+            .unwrap_or(0)
+    }
+
     pub fn get_attestation(
         env: Env,
         campaign_id: u64,
@@ -197,6 +660,15 @@ impl PerformanceOracleContract {
             .get(&DataKey::Consensus(campaign_id))
     }
 
+    pub fn get_consensus_agreement(env: Env, campaign_id: u64) -> Option<ConsensusAgreement> {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        env.storage()
+            .persistent()
+            .get(&DataKey::ConsensusAgreement(campaign_id))
+    }
+
     pub fn get_attestation_count(env: Env, campaign_id: u64) -> u32 {
         env.storage()
             .instance()
@@ -218,12 +690,7 @@ impl PerformanceOracleContract {
             return;
         }
 
-        // Compute actual averages by reading all attestations
-        let mut sum_impressions: u64 = 0;
-        let mut sum_clicks: u64 = 0;
-        let mut sum_fraud_rate: u64 = 0;
-        let mut sum_quality_score: u64 = 0;
-
+        let mut attestations: Vec<PerformanceAttestation> = Vec::new(env);
         for i in 0..total_attesters {
             let attester: Address = env
                 .storage()
@@ -236,18 +703,66 @@ impl PerformanceOracleContract {
                 .persistent()
                 .get(&DataKey::Attestation(campaign_id, attester))
                 .expect("attestation not found");
+            attestations.push_back(attestation);
+        }
+
+        let (threshold_pct, max_deviation_bps) = Self::get_consensus_params(env.clone());
+        let median_impressions =
+            Self::_median(env, attestations.iter().map(|a| a.impressions_verified));
+        let median_clicks = Self::_median(env, attestations.iter().map(|a| a.clicks_verified));
 
+        // Reject attestations too far from the median, average the rest
+        let mut agreeing: u64 = 0;
+        let mut dissenters: Vec<Address> = Vec::new(env);
+        let mut sum_impressions: u64 = 0;
+        let mut sum_clicks: u64 = 0;
+        let mut sum_fraud_rate: u64 = 0;
+        let mut sum_quality_score: u64 = 0;
+        for attestation in attestations.iter() {
+            let agrees = Self::_within(
+                attestation.impressions_verified,
+                median_impressions,
+                max_deviation_bps,
+            ) && Self::_within(
+                attestation.clicks_verified,
+                median_clicks,
+                max_deviation_bps,
+            );
+            if !agrees {
+                env.events().publish(
+                    (symbol_short!("oracle"), symbol_short!("dissent")),
+                    (campaign_id, attestation.attester.clone()),
+                );
+                dissenters.push_back(attestation.attester);
+                continue;
+            }
+            agreeing += 1;
             sum_impressions += attestation.impressions_verified;
             sum_clicks += attestation.clicks_verified;
             sum_fraud_rate += attestation.fraud_rate as u64;
             sum_quality_score += attestation.quality_score as u64;
         }
 
-        // Calculate averages
-        let avg_impressions = sum_impressions / (total_attesters as u64);
-        let avg_clicks = sum_clicks / (total_attesters as u64);
-        let avg_fraud_rate = (sum_fraud_rate / (total_attesters as u64)) as u32;
-        let avg_quality_score = (sum_quality_score / (total_attesters as u64)) as u32;
+        // Without any agreement fall back to the medians
+        let (avg_impressions, avg_clicks, avg_fraud_rate, avg_quality_score) =
+            if let Some(avg_impressions) = sum_impressions.checked_div(agreeing) {
+                (
+                    avg_impressions,
+                    sum_clicks / agreeing,
+                    (sum_fraud_rate / agreeing) as u32,
+                    (sum_quality_score / agreeing) as u32,
+                )
+            } else {
+                (
+                    median_impressions,
+                    median_clicks,
+                    Self::_median(env, attestations.iter().map(|a| a.fraud_rate as u64)) as u32,
+                    Self::_median(env, attestations.iter().map(|a| a.quality_score as u64)) as u32,
+                )
+            };
+
+        // Agreeing share, rounded up so 2 of 3 meets a 67% threshold
+        let agreeing_pct = (agreeing * 100).div_ceil(total_attesters as u64);
 
         let consensus = OracleConsensus {
             campaign_id,
@@ -256,9 +771,13 @@ impl PerformanceOracleContract {
             avg_clicks,
             avg_fraud_rate,
             avg_quality_score,
-            consensus_reached: true,
+            consensus_reached: agreeing_pct >= threshold_pct as u64,
             last_updated: env.ledger().timestamp(),
         };
+        let agreement = ConsensusAgreement {
+            agreeing_attesters: agreeing as u32,
+            dissenters,
+        };
 
         let _ttl_key = DataKey::Consensus(campaign_id);
         env.storage().persistent().set(&_ttl_key, &consensus);
@@ -267,6 +786,141 @@ impl PerformanceOracleContract {
             PERSISTENT_LIFETIME_THRESHOLD,
             PERSISTENT_BUMP_AMOUNT,
         );
+        let _ttl_key = DataKey::ConsensusAgreement(campaign_id);
+        env.storage().persistent().set(&_ttl_key, &agreement);
+        env.storage().persistent().extend_ttl(
+            &_ttl_key,
+            PERSISTENT_LIFETIME_THRESHOLD,
+            PERSISTENT_BUMP_AMOUNT,
+        );
+    }
+
+    fn _adjust_open_attestations(env: &Env, attester: &Address, delta: i32) {
+        let _ttl_key = DataKey::OpenAttestations(attester.clone());
+        let open: u32 = env.storage().persistent().get(&_ttl_key).unwrap_or(0);
+        let open = open.saturating_add_signed(delta);
+        if open == 0 {
+            env.storage().persistent().remove(&_ttl_key);
+            return;
+        }
+        env.storage().persistent().set(&_ttl_key, &open);
+        env.storage().persistent().extend_ttl(
+            &_ttl_key,
+            PERSISTENT_LIFETIME_THRESHOLD,
+            PERSISTENT_BUMP_AMOUNT,
+        );
+    }
+
+    fn _load_staking_config(env: &Env) -> StakingConfig {
+        env.storage()
+            .instance()
+            .get(&DataKey::StakingConfig)
+            .expect("staking not configured")
+    }
+
+    fn _stake_held(env: &Env) -> i128 {
+        env.storage()
+            .instance()
+            .get(&DataKey::StakeHeld)
+            .unwrap_or(0)
+    }
+
+    fn _adjust_stake_held(env: &Env, delta: i128) {
+        let held = Self::_stake_held(env);
+        env.storage()
+            .instance()
+            .set(&DataKey::StakeHeld, &(held + delta));
+    }
+
+    fn _load_attester(env: &Env, attester: &Address) -> AttesterInfo {
+        env.storage()
+            .persistent()
+            .get(&DataKey::AttesterInfo(attester.clone()))
+            .unwrap_or(AttesterInfo {
+                stake: 0,
+                unbonding: 0,
+                unbonding_until: 0,
+                pending_rewards: 0,
+                slashed_total: 0,
+                accepted: 0,
+                rejected: 0,
+            })
+    }
+
+    fn _save_attester(env: &Env, attester: &Address, info: &AttesterInfo) {
+        let _ttl_key = DataKey::AttesterInfo(attester.clone());
+        env.storage().persistent().set(&_ttl_key, info);
+        env.storage().persistent().extend_ttl(
+            &_ttl_key,
+            PERSISTENT_LIFETIME_THRESHOLD,
+            PERSISTENT_BUMP_AMOUNT,
+        );
+    }
+
+    /// Slash `slash_bps` of bonded plus still-slashable unbonding stake,
+    /// taking from bonded stake first. Returns the amount slashed.
+    fn _slash(info: &mut AttesterInfo, slash_bps: u32) -> i128 {
+        let penalty = (info.stake + info.unbonding) * slash_bps as i128 / 10_000;
+        let from_stake = penalty.min(info.stake);
+        info.stake -= from_stake;
+        info.unbonding -= penalty - from_stake;
+        info.slashed_total += penalty;
+        penalty
+    }
+
+    fn _record_accuracy(env: &Env, attester: &Address, record: AccuracyRecord) {
+        let _ttl_key = DataKey::AccuracyHistory(attester.clone());
+        let mut history: Vec<AccuracyRecord> = env
+            .storage()
+            .persistent()
+            .get(&_ttl_key)
+            .unwrap_or(Vec::new(env));
+        history.push_front(record);
+        if history.len() > MAX_HISTORY {
+            history.pop_back();
+        }
+        env.storage().persistent().set(&_ttl_key, &history);
+        env.storage().persistent().extend_ttl(
+            &_ttl_key,
+            PERSISTENT_LIFETIME_THRESHOLD,
+            PERSISTENT_BUMP_AMOUNT,
+        );
+    }
+
+    /// |value - reference| in basis points of the reference, saturating at u32::MAX
+    fn _deviation_bps(value: u64, reference: u64) -> u32 {
+        if reference == 0 {
+            return if value == 0 { 0 } else { u32::MAX };
+        }
+        let bps = value.abs_diff(reference) as u128 * 10_000 / reference as u128;
+        bps.min(u32::MAX as u128) as u32
+    }
+
+    /// Median of the values; the mean of the middle two for an even count
+    fn _median(env: &Env, values: impl Iterator<Item = u64>) -> u64 {
+        let mut sorted: Vec<u64> = Vec::new(env);
+        for value in values {
+            let mut pos = sorted.len();
+            while pos > 0 && sorted.get_unchecked(pos - 1) > value {
+                pos -= 1;
+            }
+            sorted.insert(pos, value);
+        }
+        let len = sorted.len();
+        if len == 0 {
+            return 0;
+        }
+        let mid = len / 2;
+        if len % 2 == 1 {
+            sorted.get_unchecked(mid)
+        } else {
+            (sorted.get_unchecked(mid - 1) + sorted.get_unchecked(mid)) / 2
+        }
+    }
+
+    fn _within(value: u64, median: u64, max_deviation_bps: u32) -> bool {
+        let tolerance = median.saturating_mul(max_deviation_bps as u64) / 10_000;
+        value.abs_diff(median) <= tolerance
     }
 
     pub fn propose_admin(env: Env, current_admin: Address, new_admin: Address) {
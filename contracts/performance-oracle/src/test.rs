@@ -1,6 +1,10 @@
 #![cfg(test)]
 use super::*;
-use soroban_sdk::{testutils::Address as _, Address, BytesN, Env};
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    token::{StellarAssetClient, TokenClient},
+    vec, Address, BytesN, Env,
+};
 
 fn setup(env: &Env) -> (PerformanceOracleContractClient<'_>, Address) {
     let admin = Address::generate(env);
@@ -85,47 +89,49 @@ fn test_consensus_with_actual_averaging() {
     let env = Env::default();
     env.mock_all_auths();
     let (c, admin) = setup(&env);
-    
+
     // Create 3 attesters
     let att1 = Address::generate(&env);
     let att2 = Address::generate(&env);
     let att3 = Address::generate(&env);
-    
+
     c.authorize_attester(&admin, &att1);
     c.authorize_attester(&admin, &att2);
     c.authorize_attester(&admin, &att3);
-    
+
     let data_hash = BytesN::from_array(&env, &[1u8; 32]);
-    
+
     // Attester 1: 1000 impressions, 100 clicks, 10% fraud, 80 quality
-    c.submit_attestation(&att1, &1u64, &1000u64, &100u64, &1000u32, &80u32, &data_hash);
-    
+    c.submit_attestation(
+        &att1, &1u64, &1000u64, &100u64, &1000u32, &80u32, &data_hash,
+    );
+
     // Should not have consensus yet (need min 2 attesters)
     assert!(c.get_consensus(&1u64).is_none());
-    
+
     // Attester 2: 2000 impressions, 200 clicks, 20% fraud, 90 quality
-    c.submit_attestation(&att2, &1u64, &2000u64, &200u64, &2000u32, &90u32, &data_hash);
-    
+    c.submit_attestation(
+        &att2, &1u64, &2000u64, &200u64, &2000u32, &90u32, &data_hash,
+    );
+
     // Now we have consensus with 2 attesters
     let consensus_opt = c.get_consensus(&1u64);
     assert!(consensus_opt.is_some());
-    
+
     let consensus = consensus_opt.unwrap();
-    
-    // Average of 1000 and 2000 = 1500
+
+    // Both are 33% off their median of 1500, so neither agrees and the
+    // medians are reported
     assert_eq!(consensus.avg_impressions, 1500);
-    
-    // Average of 100 and 200 = 150
     assert_eq!(consensus.avg_clicks, 150);
-    
-    // Average of 1000 and 2000 = 1500
     assert_eq!(consensus.avg_fraud_rate, 1500);
-    
-    // Average of 80 and 90 = 85
     assert_eq!(consensus.avg_quality_score, 85);
-    
+
     assert_eq!(consensus.total_attesters, 2);
-    assert!(consensus.consensus_reached);
+    let agreement = c.get_consensus_agreement(&1u64).unwrap();
+    assert_eq!(agreement.agreeing_attesters, 0);
+    assert_eq!(agreement.dissenters, vec![&env, att1, att2]);
+    assert!(!consensus.consensus_reached);
 }
 
 #[test]
@@ -133,40 +139,44 @@ fn test_consensus_with_three_attesters() {
     let env = Env::default();
     env.mock_all_auths();
     let (c, admin) = setup(&env);
-    
+
     let att1 = Address::generate(&env);
     let att2 = Address::generate(&env);
     let att3 = Address::generate(&env);
-    
+
     c.authorize_attester(&admin, &att1);
     c.authorize_attester(&admin, &att2);
     c.authorize_attester(&admin, &att3);
-    
+
     let data_hash = BytesN::from_array(&env, &[1u8; 32]);
-    
+
     // Attester 1: 900 impressions, 90 clicks, 5% fraud, 70 quality
     c.submit_attestation(&att1, &1u64, &900u64, &90u64, &500u32, &70u32, &data_hash);
-    
+
     // Attester 2: 1200 impressions, 120 clicks, 10% fraud, 80 quality
-    c.submit_attestation(&att2, &1u64, &1200u64, &120u64, &1000u32, &80u32, &data_hash);
-    
+    c.submit_attestation(
+        &att2, &1u64, &1200u64, &120u64, &1000u32, &80u32, &data_hash,
+    );
+
     // Attester 3: 1500 impressions, 150 clicks, 15% fraud, 90 quality
-    c.submit_attestation(&att3, &1u64, &1500u64, &150u64, &1500u32, &90u32, &data_hash);
-    
+    c.submit_attestation(
+        &att3, &1u64, &1500u64, &150u64, &1500u32, &90u32, &data_hash,
+    );
+
     let consensus = c.get_consensus(&1u64).unwrap();
-    
+
     // Average of 900, 1200, 1500 = 1200
     assert_eq!(consensus.avg_impressions, 1200);
-    
+
     // Average of 90, 120, 150 = 120
     assert_eq!(consensus.avg_clicks, 120);
-    
+
     // Average of 500, 1000, 1500 = 1000
     assert_eq!(consensus.avg_fraud_rate, 1000);
-    
+
     // Average of 70, 80, 90 = 80
     assert_eq!(consensus.avg_quality_score, 80);
-    
+
     assert_eq!(consensus.total_attesters, 3);
 }
 
@@ -175,15 +185,15 @@ fn test_no_consensus_with_insufficient_attesters() {
     let env = Env::default();
     env.mock_all_auths();
     let (c, admin) = setup(&env);
-    
+
     let att1 = Address::generate(&env);
     c.authorize_attester(&admin, &att1);
-    
+
     let data_hash = BytesN::from_array(&env, &[1u8; 32]);
-    
+
     // Only 1 attester (need min 2)
     c.submit_attestation(&att1, &1u64, &1000u64, &100u64, &500u32, &80u32, &data_hash);
-    
+
     // Should not have consensus yet
     assert!(c.get_consensus(&1u64).is_none());
 }
@@ -193,33 +203,382 @@ fn test_last_attester_does_not_override_consensus() {
     let env = Env::default();
     env.mock_all_auths();
     let (c, admin) = setup(&env);
-    
+
     let att1 = Address::generate(&env);
     let att2 = Address::generate(&env);
     let att3 = Address::generate(&env);
-    
+
     c.authorize_attester(&admin, &att1);
     c.authorize_attester(&admin, &att2);
     c.authorize_attester(&admin, &att3);
-    
+
     let data_hash = BytesN::from_array(&env, &[1u8; 32]);
-    
+
     // First two attesters report similar values
     c.submit_attestation(&att1, &1u64, &1000u64, &100u64, &500u32, &80u32, &data_hash);
     c.submit_attestation(&att2, &1u64, &1100u64, &110u64, &600u32, &85u32, &data_hash);
-    
+
     // Third attester reports wildly different values
-    c.submit_attestation(&att3, &1u64, &9000u64, &900u64, &5000u32, &50u32, &data_hash);
-    
+    c.submit_attestation(
+        &att3, &1u64, &9000u64, &900u64, &5000u32, &50u32, &data_hash,
+    );
+
     let consensus = c.get_consensus(&1u64).unwrap();
-    
-    // Average should include all three, not just the last one
-    // (1000 + 1100 + 9000) / 3 = 3700
-    assert_eq!(consensus.avg_impressions, 3700);
-    
-    // (100 + 110 + 900) / 3 = 370
-    assert_eq!(consensus.avg_clicks, 370);
-    
-    // The consensus is influenced by all attesters, not just the last one
+
+    // The outlier is rejected: (1000 + 1100) / 2 = 1050
+    assert_eq!(consensus.avg_impressions, 1050);
+
+    // (100 + 110) / 2 = 105
+    assert_eq!(consensus.avg_clicks, 105);
+
     assert_eq!(consensus.total_attesters, 3);
+    let agreement = c.get_consensus_agreement(&1u64).unwrap();
+    assert_eq!(agreement.agreeing_attesters, 2);
+    assert_eq!(agreement.dissenters, vec![&env, att3]);
+    // 2 of 3 meets the 67% threshold
+    assert!(consensus.consensus_reached);
+}
+
+#[test]
+fn test_consensus_with_agreeing_attesters() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (c, admin) = setup(&env);
+    let data_hash = BytesN::from_array(&env, &[1u8; 32]);
+
+    for impressions in [1000u64, 1020, 990] {
+        let attester = Address::generate(&env);
+        c.authorize_attester(&admin, &attester);
+        c.submit_attestation(
+            &attester,
+            &1u64,
+            &impressions,
+            &100u64,
+            &500u32,
+            &80u32,
+            &data_hash,
+        );
+    }
+
+    let consensus = c.get_consensus(&1u64).unwrap();
+    assert_eq!(consensus.avg_impressions, 1003);
+    let agreement = c.get_consensus_agreement(&1u64).unwrap();
+    assert_eq!(agreement.agreeing_attesters, 3);
+    assert!(agreement.dissenters.is_empty());
+    assert!(consensus.consensus_reached);
+}
+
+#[test]
+fn test_consensus_params_tolerate_wider_spread() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (c, admin) = setup(&env);
+    c.set_consensus_params(&admin, &100u32, &5_000u32);
+    assert_eq!(c.get_consensus_params(), (100, 5_000));
+
+    let att1 = Address::generate(&env);
+    let att2 = Address::generate(&env);
+    c.authorize_attester(&admin, &att1);
+    c.authorize_attester(&admin, &att2);
+    let data_hash = BytesN::from_array(&env, &[1u8; 32]);
+    c.submit_attestation(&att1, &1u64, &1000u64, &100u64, &500u32, &80u32, &data_hash);
+    c.submit_attestation(&att2, &1u64, &2000u64, &200u64, &500u32, &80u32, &data_hash);
+
+    // Within 50% of the 1500 median, so unanimous
+    let consensus = c.get_consensus(&1u64).unwrap();
+    assert_eq!(
+        c.get_consensus_agreement(&1u64).unwrap().agreeing_attesters,
+        2
+    );
+    assert!(consensus.consensus_reached);
+}
+
+#[test]
+#[should_panic(expected = "invalid threshold")]
+fn test_set_consensus_params_invalid_threshold() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (c, admin) = setup(&env);
+    c.set_consensus_params(&admin, &101u32, &1_000u32);
+}
+
+// ─── staking ─────────────────────────────────────────────────────────────────
+
+fn setup_staking<'a>(
+    env: &'a Env,
+    c: &PerformanceOracleContractClient,
+    admin: &Address,
+) -> (Address, StellarAssetClient<'a>) {
+    let token = env
+        .register_stellar_asset_contract_v2(admin.clone())
+        .address();
+    let sac = StellarAssetClient::new(env, &token);
+    c.set_staking_config(
+        admin,
+        &StakingConfig {
+            stake_token: token.clone(),
+            min_stake: 1_000,
+            attestation_reward: 100,
+            slash_bps: 1_000,
+            unbonding_period: 86_400,
+        },
+    );
+    (token, sac)
+}
+
+fn bonded_attester(
+    env: &Env,
+    c: &PerformanceOracleContractClient,
+    admin: &Address,
+    sac: &StellarAssetClient,
+) -> Address {
+    let attester = Address::generate(env);
+    c.authorize_attester(admin, &attester);
+    sac.mint(&attester, &1_000);
+    c.bond_stake(&attester, &1_000);
+    attester
+}
+
+#[test]
+#[should_panic(expected = "insufficient stake")]
+fn test_attestation_requires_stake() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (c, admin) = setup(&env);
+    setup_staking(&env, &c, &admin);
+    let attester = Address::generate(&env);
+    c.authorize_attester(&admin, &attester);
+    let data_hash = BytesN::from_array(&env, &[1u8; 32]);
+    c.submit_attestation(
+        &attester, &1u64, &1000u64, &100u64, &500u32, &80u32, &data_hash,
+    );
+}
+
+#[test]
+fn test_unbonding_period() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (c, admin) = setup(&env);
+    let (token, sac) = setup_staking(&env, &c, &admin);
+    let attester = bonded_attester(&env, &c, &admin, &sac);
+
+    c.unbond_stake(&attester, &400i128);
+    let info = c.get_attester_info(&attester);
+    assert_eq!(info.stake, 600);
+    assert_eq!(info.unbonding, 400);
+    assert!(c.try_withdraw_unbonded(&attester).is_err());
+
+    env.ledger().with_mut(|li| li.timestamp = 86_400);
+    assert_eq!(c.withdraw_unbonded(&attester), 400);
+    assert_eq!(TokenClient::new(&env, &token).balance(&attester), 400);
+    assert_eq!(c.get_attester_info(&attester).unbonding, 0);
+}
+
+fn staking_config(token: &Address) -> StakingConfig {
+    StakingConfig {
+        stake_token: token.clone(),
+        min_stake: 1_000,
+        attestation_reward: 100,
+        slash_bps: 1_000,
+        unbonding_period: 86_400,
+    }
+}
+
+#[test]
+fn test_stake_token_locked_while_stake_held() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (c, admin) = setup(&env);
+    let (token, sac) = setup_staking(&env, &c, &admin);
+    let other = env
+        .register_stellar_asset_contract_v2(admin.clone())
+        .address();
+    let attester = bonded_attester(&env, &c, &admin, &sac);
+
+    assert!(c
+        .try_set_staking_config(&admin, &staking_config(&other))
+        .is_err());
+    // Other parameters can still change
+    let mut config = staking_config(&token);
+    config.min_stake = 500;
+    c.set_staking_config(&admin, &config);
+
+    c.unbond_stake(&attester, &1_000i128);
+    env.ledger().with_mut(|li| li.timestamp = 86_400);
+    c.withdraw_unbonded(&attester);
+    c.set_staking_config(&admin, &staking_config(&other));
+    let newcomer = bonded_attester(&env, &c, &admin, &StellarAssetClient::new(&env, &other));
+    assert_eq!(c.get_attester_info(&newcomer).stake, 1_000);
+    assert_eq!(TokenClient::new(&env, &other).balance(&c.address), 1_000);
+}
+
+#[test]
+#[should_panic(expected = "stake token in use")]
+fn test_stake_token_locked_while_pool_funded() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (c, admin) = setup(&env);
+    let (_, sac) = setup_staking(&env, &c, &admin);
+    let funder = Address::generate(&env);
+    sac.mint(&funder, &500);
+    c.fund_reward_pool(&funder, &500i128);
+
+    let other = env
+        .register_stellar_asset_contract_v2(admin.clone())
+        .address();
+    c.set_staking_config(&admin, &staking_config(&other));
+}
+
+#[test]
+fn test_finalize_rewards_and_slashes() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (c, admin) = setup(&env);
+    let (token, sac) = setup_staking(&env, &c, &admin);
+    let funder = Address::generate(&env);
+    sac.mint(&funder, &500);
+    c.fund_reward_pool(&funder, &500i128);
+
+    let att1 = bonded_attester(&env, &c, &admin, &sac);
+    let att2 = bonded_attester(&env, &c, &admin, &sac);
+    let att3 = bonded_attester(&env, &c, &admin, &sac);
+    let data_hash = BytesN::from_array(&env, &[1u8; 32]);
+    c.submit_attestation(&att1, &1u64, &1000u64, &100u64, &500u32, &80u32, &data_hash);
+    c.submit_attestation(&att2, &1u64, &1100u64, &110u64, &600u32, &85u32, &data_hash);
+    c.submit_attestation(
+        &att3, &1u64, &9000u64, &900u64, &5000u32, &50u32, &data_hash,
+    );
+
+    c.finalize_consensus(&1u64);
+    assert!(c.is_finalized(&1u64));
+
+    let info1 = c.get_attester_info(&att1);
+    assert_eq!(info1.pending_rewards, 100);
+    assert_eq!(info1.accepted, 1);
+    let info3 = c.get_attester_info(&att3);
+    assert_eq!(info3.stake, 900);
+    assert_eq!(info3.slashed_total, 100);
+    assert_eq!(info3.rejected, 1);
+    // 500 funded - 2 rewards + 1 slash
+    assert_eq!(c.get_reward_pool(), 400);
+
+    let history = c.get_accuracy_history(&att3);
+    assert_eq!(history.len(), 1);
+    let record = history.get(0).unwrap();
+    assert!(!record.accepted);
+    assert_eq!(record.campaign_id, 1);
+    // 9000 vs the 1050 consensus
+    assert_eq!(record.deviation_bps, 75_714);
+    assert!(c.get_accuracy_history(&att2).get(0).unwrap().accepted);
+
+    assert_eq!(c.claim_rewards(&att1), 100);
+    assert_eq!(TokenClient::new(&env, &token).balance(&att1), 100);
+}
+
+#[test]
+fn test_slash_reaches_unbonding_stake() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (c, admin) = setup(&env);
+    let (_, sac) = setup_staking(&env, &c, &admin);
+
+    let att1 = bonded_attester(&env, &c, &admin, &sac);
+    let att2 = bonded_attester(&env, &c, &admin, &sac);
+    let att3 = bonded_attester(&env, &c, &admin, &sac);
+    let data_hash = BytesN::from_array(&env, &[1u8; 32]);
+    c.submit_attestation(&att1, &1u64, &1000u64, &100u64, &500u32, &80u32, &data_hash);
+    c.submit_attestation(&att2, &1u64, &1000u64, &100u64, &500u32, &80u32, &data_hash);
+    c.submit_attestation(&att3, &1u64, &5000u64, &100u64, &500u32, &80u32, &data_hash);
+
+    // Unbonding right after a bad attestation does not escape the slash
+    c.unbond_stake(&att3, &1_000i128);
+    c.finalize_consensus(&1u64);
+    let info = c.get_attester_info(&att3);
+    assert_eq!(info.stake, 0);
+    assert_eq!(info.unbonding, 900);
+    // Nothing in the pool to pay rewards from
+    assert_eq!(c.get_attester_info(&att1).pending_rewards, 0);
+}
+
+#[test]
+#[should_panic(expected = "consensus not reached")]
+fn test_finalize_without_agreement() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (c, admin) = setup(&env);
+    let att1 = Address::generate(&env);
+    let att2 = Address::generate(&env);
+    c.authorize_attester(&admin, &att1);
+    c.authorize_attester(&admin, &att2);
+    let data_hash = BytesN::from_array(&env, &[1u8; 32]);
+    c.submit_attestation(&att1, &1u64, &1000u64, &100u64, &500u32, &80u32, &data_hash);
+    c.submit_attestation(&att2, &1u64, &2000u64, &200u64, &500u32, &80u32, &data_hash);
+    c.finalize_consensus(&1u64);
+}
+
+#[test]
+fn test_unbonded_stake_locked_until_settlement() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (c, admin) = setup(&env);
+    let (_, sac) = setup_staking(&env, &c, &admin);
+
+    let att1 = bonded_attester(&env, &c, &admin, &sac);
+    let att2 = bonded_attester(&env, &c, &admin, &sac);
+    let att3 = bonded_attester(&env, &c, &admin, &sac);
+    let data_hash = BytesN::from_array(&env, &[1u8; 32]);
+    c.submit_attestation(&att1, &1u64, &1000u64, &100u64, &500u32, &80u32, &data_hash);
+    c.submit_attestation(&att2, &1u64, &1000u64, &100u64, &500u32, &80u32, &data_hash);
+    c.submit_attestation(&att3, &1u64, &5000u64, &100u64, &500u32, &80u32, &data_hash);
+    assert_eq!(c.get_open_attestations(&att3), 1);
+
+    c.unbond_stake(&att3, &1_000i128);
+    env.ledger().with_mut(|li| li.timestamp = 86_400);
+    assert!(c.try_withdraw_unbonded(&att3).is_err());
+
+    c.finalize_consensus(&1u64);
+    assert_eq!(c.get_open_attestations(&att3), 0);
+    assert_eq!(c.withdraw_unbonded(&att3), 900);
+}
+
+#[test]
+fn test_finalize_against_median_after_deadline() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (c, admin) = setup(&env);
+    let (_, sac) = setup_staking(&env, &c, &admin);
+
+    let attester = bonded_attester(&env, &c, &admin, &sac);
+    let data_hash = BytesN::from_array(&env, &[1u8; 32]);
+    // Nobody else attests, so consensus never forms
+    c.submit_attestation(
+        &attester, &1u64, &1000u64, &100u64, &500u32, &80u32, &data_hash,
+    );
+    assert!(c.get_consensus(&1u64).is_none());
+    assert!(c.try_finalize_consensus(&1u64).is_err());
+
+    env.ledger().with_mut(|li| li.timestamp = 7 * 86_400);
+    c.finalize_consensus(&1u64);
+    assert!(c.is_finalized(&1u64));
+    assert_eq!(c.get_attester_info(&attester).accepted, 1);
+    assert_eq!(c.get_open_attestations(&attester), 0);
+}
+
+#[test]
+#[should_panic(expected = "consensus finalized")]
+fn test_attest_after_finalize() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (c, admin) = setup(&env);
+    let data_hash = BytesN::from_array(&env, &[1u8; 32]);
+    let att1 = Address::generate(&env);
+    let att2 = Address::generate(&env);
+    let att3 = Address::generate(&env);
+    c.authorize_attester(&admin, &att1);
+    c.authorize_attester(&admin, &att2);
+    c.authorize_attester(&admin, &att3);
+    c.submit_attestation(&att1, &1u64, &1000u64, &100u64, &500u32, &80u32, &data_hash);
+    c.submit_attestation(&att2, &1u64, &1000u64, &100u64, &500u32, &80u32, &data_hash);
+    c.finalize_consensus(&1u64);
+    c.submit_attestation(&att3, &1u64, &1000u64, &100u64, &500u32, &80u32, &data_hash);
 }
@@ -45,6 +45,7 @@ pub enum DataKey {
     NodeCount,
     NetworkStats,
     Node(Address),
+    Suspended(Address),
 }
 
 const INSTANCE_LIFETIME_THRESHOLD: u32 = 17_280;
@@ -206,10 +207,86 @@ impl PublisherNetworkContract {
             panic!("unauthorized fraud contract");
         }
 
-        Self::_deactivate_node(&env, publisher);
+        // Only nodes taken offline by the suspension are brought back on reinstatement
+        if Self::_deactivate_node(&env, publisher.clone()) {
+            let _ttl_key = DataKey::Suspended(publisher.clone());
+            env.storage().persistent().set(&_ttl_key, &true);
+            env.storage().persistent().extend_ttl(
+                &_ttl_key,
+                PERSISTENT_LIFETIME_THRESHOLD,
+                PERSISTENT_BUMP_AMOUNT,
+            );
+            env.events().publish(
+                (symbol_short!("network"), symbol_short!("suspended")),
+                publisher,
+            );
+        }
     }
 
-    fn _deactivate_node(env: &Env, publisher: Address) {
+    /// Undo a fraud suspension, reactivating the node
+    pub fn reinstate_publisher(env: Env, fraud_contract: Address, publisher: Address) {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        fraud_contract.require_auth();
+
+        let stored_fraud: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::FraudContract)
+            .expect("fraud contract not set");
+        if fraud_contract != stored_fraud {
+            panic!("unauthorized fraud contract");
+        }
+
+        let suspended_key = DataKey::Suspended(publisher.clone());
+        if !env.storage().persistent().has(&suspended_key) {
+            panic!("publisher not suspended");
+        }
+        env.storage().persistent().remove(&suspended_key);
+
+        let mut node: NetworkNode = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Node(publisher.clone()))
+            .expect("not in network");
+        if node.is_active {
+            return;
+        }
+        node.is_active = true;
+        node.last_heartbeat = env.ledger().timestamp();
+        let _ttl_key = DataKey::Node(publisher.clone());
+        env.storage().persistent().set(&_ttl_key, &node);
+        env.storage().persistent().extend_ttl(
+            &_ttl_key,
+            PERSISTENT_LIFETIME_THRESHOLD,
+            PERSISTENT_BUMP_AMOUNT,
+        );
+
+        let mut stats: NetworkStats = env
+            .storage()
+            .instance()
+            .get(&DataKey::NetworkStats)
+            .unwrap();
+        stats.active_nodes += 1;
+        env.storage().instance().set(&DataKey::NetworkStats, &stats);
+
+        env.events().publish(
+            (symbol_short!("network"), symbol_short!("reinstate")),
+            publisher,
+        );
+    }
+
+    pub fn is_suspended(env: Env, publisher: Address) -> bool {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        env.storage()
+            .persistent()
+            .has(&DataKey::Suspended(publisher))
+    }
+
+    fn _deactivate_node(env: &Env, publisher: Address) -> bool {
         let mut node: NetworkNode = env
             .storage()
             .persistent()
@@ -217,7 +294,7 @@ impl PublisherNetworkContract {
             .expect("not in network");
 
         if !node.is_active {
-            return;
+            return false;
         }
 
         node.is_active = false;
@@ -238,6 +315,7 @@ impl PublisherNetworkContract {
             stats.active_nodes -= 1;
         }
         env.storage().instance().set(&DataKey::NetworkStats, &stats);
+        true
     }
 
     pub fn record_impression(env: Env, _publisher: Address) {
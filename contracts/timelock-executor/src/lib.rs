@@ -101,17 +101,7 @@ impl TimelockExecutorContract {
             panic!("unauthorized");
         }
 
-        let min_delay: u64 = env
-            .storage()
-            .instance()
-            .get(&DataKey::MinDelay)
-            .unwrap_or(86_400);
-        let max_delay: u64 = env
-            .storage()
-            .instance()
-            .get(&DataKey::MaxDelay)
-            .unwrap_or(2_592_000);
-
+        let (min_delay, max_delay) = Self::_delay_bounds(&env);
         if delay_secs < min_delay || delay_secs > max_delay {
             panic!("invalid delay");
         }
@@ -276,6 +266,31 @@ impl TimelockExecutorContract {
         }
     }
 
+    pub fn get_admin(env: Env) -> Address {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        env.storage().instance().get(&DataKey::Admin).unwrap()
+    }
+
+    pub fn get_executor(env: Env) -> Address {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        env.storage()
+            .instance()
+            .get(&DataKey::ExecutorAddress)
+            .unwrap()
+    }
+
+    /// (MinDelay, MaxDelay) that `queue` accepts
+    pub fn get_delay_bounds(env: Env) -> (u64, u64) {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        Self::_delay_bounds(&env)
+    }
+
     pub fn propose_admin(env: Env, current_admin: Address, new_admin: Address) {
         pulsar_common_admin::propose_admin(
             &env,
@@ -289,6 +304,20 @@ impl TimelockExecutorContract {
     pub fn accept_admin(env: Env, new_admin: Address) {
         pulsar_common_admin::accept_admin(&env, &DataKey::Admin, &DataKey::PendingAdmin, new_admin);
     }
+
+    fn _delay_bounds(env: &Env) -> (u64, u64) {
+        let min_delay: u64 = env
+            .storage()
+            .instance()
+            .get(&DataKey::MinDelay)
+            .unwrap_or(86_400);
+        let max_delay: u64 = env
+            .storage()
+            .instance()
+            .get(&DataKey::MaxDelay)
+            .unwrap_or(2_592_000);
+        (min_delay, max_delay)
+    }
 }
 
 mod test;
@@ -236,7 +236,6 @@ export default function GovernancePage() {
                               castVote({
                                 proposalId: proposal.id,
                                 voteType: 'For',
-                                votePower: balance || 0n,
                               })
                             }
                             disabled={voteLoading}
@@ -249,7 +248,6 @@ export default function GovernancePage() {
                               castVote({
                                 proposalId: proposal.id,
                                 voteType: 'Against',
-                                votePower: balance || 0n,
                               })
                             }
                             disabled={voteLoading}
@@ -262,7 +260,6 @@ export default function GovernancePage() {
                               castVote({
                                 proposalId: proposal.id,
                                 voteType: 'Abstain',
-                                votePower: balance || 0n,
                               })
                             }
                             disabled={voteLoading}
@@ -371,7 +371,6 @@ export function useCastVote() {
   const castVote = async (params: {
     proposalId: number;
     voteType: 'For' | 'Against' | 'Abstain';
-    votePower: bigint;
   }) => {
     if (!address) throw new Error('Wallet not connected');
     return mutateAsync({
@@ -382,7 +381,6 @@ export function useCastVote() {
         addressToScVal(address),
         u64ToScVal(params.proposalId),
         stringToScVal(params.voteType),
-        i128ToScVal(params.votePower),
       ],
     });
   };